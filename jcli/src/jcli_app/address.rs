@@ -1,7 +1,7 @@
 use chain_addr::{AddressReadable, Discrimination, Kind};
 use chain_crypto::bech32::Bech32 as _;
 use chain_crypto::{AsymmetricPublicKey, Ed25519, PublicKey};
-use jcli_app::utils::key_parser::parse_pub_key;
+use jcli_app::utils::key_parser::parse_ed25519_pub_key;
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -29,11 +29,11 @@ pub struct InfoArgs {
 #[derive(StructOpt)]
 pub struct SingleArgs {
     /// A public key in bech32 encoding with the key type prefix
-    #[structopt(name = "PUBLIC_KEY", parse(try_from_str = "parse_pub_key"))]
+    #[structopt(name = "PUBLIC_KEY", parse(try_from_str = "parse_ed25519_pub_key"))]
     key: PublicKey<Ed25519>,
 
     /// A public key in bech32 encoding with the key type prefix
-    #[structopt(name = "DELEGATION_KEY", parse(try_from_str = "parse_pub_key"))]
+    #[structopt(name = "DELEGATION_KEY", parse(try_from_str = "parse_ed25519_pub_key"))]
     delegation: Option<PublicKey<Ed25519>>,
 
     /// set the discrimination type to testing (default is production)
@@ -44,7 +44,7 @@ pub struct SingleArgs {
 #[derive(StructOpt)]
 pub struct AccountArgs {
     /// A public key in bech32 encoding with the key type prefix
-    #[structopt(name = "PUBLIC_KEY", parse(try_from_str = "parse_pub_key"))]
+    #[structopt(name = "PUBLIC_KEY", parse(try_from_str = "parse_ed25519_pub_key"))]
     key: PublicKey<Ed25519>,
 
     /// set the discrimination type to testing (default is production)
@@ -0,0 +1,306 @@
+use chain_addr::{Discrimination, Kind};
+use chain_core::property::{Block as _, HasMessages as _, Serialize as _};
+use chain_crypto::{
+    AsymmetricKey, AsymmetricPublicKey, Curve25519_2HashDH, Ed25519, PublicKey, SecretKey,
+    SumEd25519_12,
+};
+use chain_impl_mockchain::{
+    account,
+    certificate::{self, CertificateContent, StakeDelegation},
+    leadership::genesis::GenesisPraosLeader,
+    ledger::Ledger,
+    stake::StakePoolInfo,
+    transaction::AccountIdentifier,
+};
+use jcli_app::block::Error;
+use jcli_app::utils::{error::CustomErrorFiller, io, key_parser};
+use jormungandr_lib::interfaces::{
+    Address, Block0Configuration, BlockchainConfiguration, ConsensusLeaderId, Initial, InitialUTxO,
+    Value,
+};
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// name of the file, in a stake pool directory, holding the owner's secret key
+const STAKE_POOL_OWNER_KEY: &str = "owner.sk";
+/// name of the file, in a stake pool directory, holding the KES key
+const STAKE_POOL_KES_KEY: &str = "kes";
+/// name of the file, in a stake pool directory, holding the VRF key
+const STAKE_POOL_VRF_KEY: &str = "vrf";
+/// name of the optional file, in a stake pool directory, holding the serial
+const STAKE_POOL_SERIAL: &str = "serial";
+/// maximum number of initial funds in one `Initial::Fund`, every fund being
+/// an output of the same transaction whose output count is a `u8`
+const MAX_FUNDS_PER_INITIAL: usize = 254;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Build {
+    /// the YAML file with the blockchain parameters (the content of the
+    /// `blockchain_configuration` section of the genesis file)
+    #[structopt(long = "parameters", parse(from_os_str))]
+    parameters: PathBuf,
+
+    /// the CSV file with the initial funds, one `address,value` per line
+    #[structopt(long = "allocations", parse(from_os_str))]
+    allocations: Option<PathBuf>,
+
+    /// directory with the consensus leaders keys, one key per file
+    ///
+    /// the files may contain either the public key or the secret key
+    /// of the leader.
+    #[structopt(long = "leaders", parse(from_os_str))]
+    leaders: Option<PathBuf>,
+
+    /// directory with one sub directory per stake pool
+    ///
+    /// every stake pool directory contains the owner's secret key (`owner.sk`),
+    /// the KES and the VRF keys (`kes.pk` and `vrf.pk`, or `kes.sk` and
+    /// `vrf.sk`) and optionally the serial of the pool (`serial`). The
+    /// registration of the stake pool will be signed by the owner and, if
+    /// the owner's account is in the allocations, the owner's stake will
+    /// be delegated to the pool.
+    #[structopt(long = "stake-pools", parse(from_os_str))]
+    stake_pools: Option<PathBuf>,
+
+    /// the file path to the genesis YAML file to create
+    #[structopt(long = "output-genesis", parse(from_os_str))]
+    output_genesis: PathBuf,
+
+    /// the file path to the block 0 to create
+    #[structopt(long = "output-block", parse(from_os_str))]
+    output_block: PathBuf,
+}
+
+impl Build {
+    pub fn exec(self) -> Result<(), Error> {
+        let mut blockchain_configuration = read_parameters(&self.parameters)?;
+        let mut initial = Vec::new();
+
+        let funds = match &self.allocations {
+            Some(path) => read_allocations(path)?,
+            None => Vec::new(),
+        };
+
+        if let Some(dir) = &self.leaders {
+            for path in read_dir_sorted(dir)? {
+                let leader_id = read_leader_id(&path)?;
+                blockchain_configuration.add_consensus_leader_id(leader_id);
+            }
+        }
+
+        if let Some(dir) = &self.stake_pools {
+            for path in read_dir_sorted(dir)? {
+                if path.is_dir() {
+                    initial.extend(stake_pool_certificates(
+                        &path,
+                        blockchain_configuration.discrimination(),
+                        &funds,
+                    )?);
+                }
+            }
+        }
+
+        let fund_initials = funds
+            .chunks(MAX_FUNDS_PER_INITIAL)
+            .map(|funds| Initial::Fund(funds.to_vec()));
+        initial.splice(0..0, fund_initials);
+
+        let genesis = Block0Configuration {
+            blockchain_configuration,
+            initial,
+        };
+        let block = genesis.to_block();
+        Ledger::new(block.id(), block.messages())?;
+
+        let genesis_file = Some(&self.output_genesis);
+        let writer = io::open_file_write(&genesis_file).map_err(|source| Error::OutputInvalid {
+            source,
+            path: self.output_genesis.clone(),
+        })?;
+        serde_yaml::to_writer(writer, &genesis).map_err(|source| {
+            Error::GenesisSerializationFailed {
+                source,
+                filler: CustomErrorFiller,
+            }
+        })?;
+
+        let block_file = Some(&self.output_block);
+        let writer = io::open_file_write(&block_file).map_err(|source| Error::OutputInvalid {
+            source,
+            path: self.output_block.clone(),
+        })?;
+        block
+            .serialize(writer)
+            .map_err(|source| Error::BlockSerializationFailed {
+                source,
+                filler: CustomErrorFiller,
+            })
+    }
+}
+
+fn read_parameters(path: &Path) -> Result<BlockchainConfiguration, Error> {
+    let reader = io::open_file_read(&Some(path)).map_err(|source| Error::InputInvalid {
+        source,
+        path: path.to_path_buf(),
+    })?;
+    serde_yaml::from_reader(reader).map_err(|source| Error::ParametersFileCorrupted {
+        source,
+        path: path.to_path_buf(),
+    })
+}
+
+/// read the initial funds from a CSV file of `address,value` lines
+///
+/// empty lines, lines starting with `#` and an `address,value` header are
+/// ignored.
+fn read_allocations(path: &Path) -> Result<Vec<InitialUTxO>, Error> {
+    let content = std::fs::read_to_string(path).map_err(|source| Error::InputInvalid {
+        source,
+        path: path.to_path_buf(),
+    })?;
+    let mut funds = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let allocation_invalid = |reason: String| Error::AllocationInvalid {
+            path: path.to_path_buf(),
+            line: index + 1,
+            reason,
+        };
+        let mut fields = line.split(',').map(str::trim);
+        let (address, value) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(address), Some(value), None) => (address, value),
+            _ => {
+                return Err(allocation_invalid(
+                    "expected 2 fields: address,value".to_owned(),
+                ))
+            }
+        };
+        if index == 0 && address == "address" && value == "value" {
+            continue;
+        }
+        funds.push(InitialUTxO {
+            address: address
+                .parse::<Address>()
+                .map_err(|e| allocation_invalid(format!("invalid address: {}", e)))?,
+            value: value
+                .parse::<Value>()
+                .map_err(|e| allocation_invalid(format!("invalid value: {}", e)))?,
+        });
+    }
+    Ok(funds)
+}
+
+fn read_dir_sorted(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = std::fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|source| Error::InputInvalid {
+            source,
+            path: dir.to_path_buf(),
+        })?;
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_key_file(path: &Path) -> Result<String, Error> {
+    io::read_line(&Some(path)).map_err(|source| Error::InputInvalid {
+        source,
+        path: path.to_path_buf(),
+    })
+}
+
+fn read_leader_id(path: &Path) -> Result<ConsensusLeaderId, Error> {
+    let key_str = read_key_file(path)?;
+    let public_key = match key_parser::parse_pub_key::<Ed25519>(&key_str) {
+        Ok(public_key) => public_key,
+        Err(_) => key_parser::parse_ed25519_secret_key(&key_str)
+            .map_err(|_| Error::KeyFileInvalid {
+                path: path.to_path_buf(),
+            })?
+            .to_public(),
+    };
+    Ok(ConsensusLeaderId(public_key.into()))
+}
+
+/// read the public key from `<name>.pk` or derive it from `<name>.sk`
+fn read_public_key<A>(dir: &Path, name: &str) -> Result<PublicKey<A::PubAlg>, Error>
+where
+    A: AsymmetricKey,
+    A::PubAlg: AsymmetricPublicKey,
+{
+    let public_key_path = dir.join(name).with_extension("pk");
+    if public_key_path.exists() {
+        let key_str = read_key_file(&public_key_path)?;
+        return key_parser::parse_pub_key(&key_str).map_err(|_| Error::KeyFileInvalid {
+            path: public_key_path,
+        });
+    }
+    let secret_key_path = dir.join(name).with_extension("sk");
+    let secret_key: SecretKey<A> = key_parser::read_secret_key_from_file(&Some(&secret_key_path))?;
+    Ok(secret_key.to_public())
+}
+
+/// build the signed registration certificate of the stake pool in the
+/// given directory and, if the owner's account is funded, the signed
+/// delegation of the owner's stake to the pool
+fn stake_pool_certificates(
+    dir: &Path,
+    discrimination: Discrimination,
+    funds: &[InitialUTxO],
+) -> Result<Vec<Initial>, Error> {
+    let owner_key_path = dir.join(STAKE_POOL_OWNER_KEY);
+    let owner_key = key_parser::read_ed25519_secret_key_from_file(&Some(&owner_key_path))?;
+    let owner = owner_key.to_public();
+    let kes_public_key = read_public_key::<SumEd25519_12>(dir, STAKE_POOL_KES_KEY)?;
+    let vrf_public_key = read_public_key::<Curve25519_2HashDH>(dir, STAKE_POOL_VRF_KEY)?;
+
+    let serial_path = dir.join(STAKE_POOL_SERIAL);
+    let serial = if serial_path.exists() {
+        read_key_file(&serial_path)?
+            .trim()
+            .parse()
+            .map_err(|_| Error::StakePoolSerialInvalid { path: serial_path })?
+    } else {
+        0
+    };
+
+    let info = StakePoolInfo {
+        serial,
+        owners: vec![account::Identifier::from(owner.clone())],
+        initial_key: GenesisPraosLeader {
+            kes_public_key,
+            vrf_public_key,
+        },
+    };
+    let pool_id = info.to_id();
+    let registration = certificate::Certificate {
+        signatures: vec![info.make_certificate(&owner_key)],
+        content: CertificateContent::StakePoolRegistration(info),
+    };
+    let mut certificates = vec![Initial::Cert(registration.into())];
+
+    let owner_account = chain_addr::Address(discrimination, Kind::Account(owner.clone()));
+    if funds
+        .iter()
+        .any(|utxo| utxo.address.as_ref() == &owner_account)
+    {
+        let delegation = StakeDelegation {
+            stake_key_id: AccountIdentifier::from_single_account(owner.into()),
+            pool_id,
+        };
+        let delegation = certificate::Certificate {
+            signatures: vec![delegation.make_certificate(&owner_key)],
+            content: CertificateContent::StakeDelegation(delegation),
+        };
+        certificates.push(Initial::Cert(delegation.into()));
+    }
+
+    Ok(certificates)
+}
@@ -0,0 +1,261 @@
+use jcli_app::block::{load_genesis, Error};
+use jormungandr_lib::interfaces::{Block0Configuration, Initial};
+use serde_yaml::Value as Yaml;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct Diff {
+    /// the reference genesis file or block 0
+    #[structopt(name = "FROM", parse(from_os_str))]
+    from: PathBuf,
+
+    /// the genesis file or block 0 to compare with the reference
+    #[structopt(name = "TO", parse(from_os_str))]
+    to: PathBuf,
+}
+
+/// a semantic difference between two genesis
+///
+/// the initial funds are compared by address (the values of the
+/// same address are summed up) and the certificates are compared
+/// as a set, so the order of the entries in the genesis does not
+/// matter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added {
+        path: String,
+        value: String,
+    },
+    Removed {
+        path: String,
+        value: String,
+    },
+    Modified {
+        path: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl Diff {
+    pub fn exec(self) -> Result<(), Error> {
+        let from = load_genesis(&Some(self.from))?;
+        let to = load_genesis(&Some(self.to))?;
+
+        for change in diff(&from, &to) {
+            match change {
+                Change::Added { path, value } => println!("+ {}: {}", path, value),
+                Change::Removed { path, value } => println!("- {}: {}", path, value),
+                Change::Modified { path, from, to } => println!("~ {}: {} -> {}", path, from, to),
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn diff(from: &Block0Configuration, to: &Block0Configuration) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    diff_yaml(
+        "blockchain_configuration",
+        &to_yaml(&from.blockchain_configuration),
+        &to_yaml(&to.blockchain_configuration),
+        &mut changes,
+    );
+    diff_maps(
+        "initial.fund",
+        &funds(from, false),
+        &funds(to, false),
+        &mut changes,
+    );
+    diff_maps(
+        "initial.legacy_fund",
+        &funds(from, true),
+        &funds(to, true),
+        &mut changes,
+    );
+    diff_sets("initial.cert", &certs(from), &certs(to), &mut changes);
+
+    changes
+}
+
+fn to_yaml<T: serde::Serialize>(value: &T) -> Yaml {
+    serde_yaml::to_value(value).unwrap_or(Yaml::Null)
+}
+
+fn yaml_to_string(value: &Yaml) -> String {
+    match value {
+        Yaml::Null => "~".to_owned(),
+        Yaml::Bool(b) => b.to_string(),
+        Yaml::Number(n) => n.to_string(),
+        Yaml::String(s) => s.clone(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
+
+fn diff_yaml(path: &str, from: &Yaml, to: &Yaml, changes: &mut Vec<Change>) {
+    match (from, to) {
+        (Yaml::Mapping(from), Yaml::Mapping(to)) => {
+            let keys: BTreeSet<String> = from
+                .iter()
+                .chain(to.iter())
+                .map(|(key, _)| yaml_to_string(key))
+                .collect();
+            for key in keys {
+                let key_yaml = Yaml::String(key.clone());
+                let path = format!("{}.{}", path, key);
+                match (from.get(&key_yaml), to.get(&key_yaml)) {
+                    (Some(from), Some(to)) => diff_yaml(&path, from, to, changes),
+                    (None, Some(to)) => changes.push(Change::Added {
+                        path,
+                        value: yaml_to_string(to),
+                    }),
+                    (Some(from), None) => changes.push(Change::Removed {
+                        path,
+                        value: yaml_to_string(from),
+                    }),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (Yaml::Sequence(from), Yaml::Sequence(to)) => {
+            let from = from.iter().map(yaml_to_string).collect();
+            let to = to.iter().map(yaml_to_string).collect();
+            diff_sets(path, &from, &to, changes)
+        }
+        (from, to) => {
+            if from != to {
+                changes.push(Change::Modified {
+                    path: path.to_owned(),
+                    from: yaml_to_string(from),
+                    to: yaml_to_string(to),
+                })
+            }
+        }
+    }
+}
+
+fn diff_maps(
+    path: &str,
+    from: &BTreeMap<String, u64>,
+    to: &BTreeMap<String, u64>,
+    changes: &mut Vec<Change>,
+) {
+    let keys: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+    for key in keys {
+        let path = format!("{}.{}", path, key);
+        match (from.get(key), to.get(key)) {
+            (Some(from), Some(to)) => {
+                if from != to {
+                    changes.push(Change::Modified {
+                        path,
+                        from: from.to_string(),
+                        to: to.to_string(),
+                    })
+                }
+            }
+            (None, Some(to)) => changes.push(Change::Added {
+                path,
+                value: to.to_string(),
+            }),
+            (Some(from), None) => changes.push(Change::Removed {
+                path,
+                value: from.to_string(),
+            }),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn diff_sets(
+    path: &str,
+    from: &BTreeSet<String>,
+    to: &BTreeSet<String>,
+    changes: &mut Vec<Change>,
+) {
+    changes.extend(from.difference(to).map(|value| Change::Removed {
+        path: path.to_owned(),
+        value: value.clone(),
+    }));
+    changes.extend(to.difference(from).map(|value| Change::Added {
+        path: path.to_owned(),
+        value: value.clone(),
+    }));
+}
+
+/// collect the initial funds by address, summing up the values
+/// funded to the same address
+fn funds(genesis: &Block0Configuration, legacy: bool) -> BTreeMap<String, u64> {
+    let mut funds = BTreeMap::new();
+    for initial in genesis.initial.iter() {
+        let entries: Vec<(String, u64)> = match (initial, legacy) {
+            (Initial::Fund(utxos), false) => utxos
+                .iter()
+                .map(|utxo| (utxo.address.to_string(), utxo.value.as_ref().0))
+                .collect(),
+            (Initial::LegacyFund(utxos), true) => utxos
+                .iter()
+                .map(|utxo| (utxo.address.to_string(), utxo.value.as_ref().0))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (address, value) in entries {
+            let total = funds.entry(address).or_insert(0u64);
+            *total = total.saturating_add(value);
+        }
+    }
+    funds
+}
+
+fn certs(genesis: &Block0Configuration) -> BTreeSet<String> {
+    genesis
+        .initial
+        .iter()
+        .filter_map(|initial| match initial {
+            Initial::Cert(cert) => Some(cert.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jormungandr_lib::interfaces::block0_configuration_documented_example;
+
+    fn genesis() -> Block0Configuration {
+        serde_yaml::from_str(&block0_configuration_documented_example()).unwrap()
+    }
+
+    #[test]
+    fn same_genesis_has_no_changes() {
+        assert_eq!(diff(&genesis(), &genesis()), vec![]);
+    }
+
+    #[test]
+    fn reordered_initial_fragments_have_no_changes() {
+        let from = genesis();
+        let mut to = genesis();
+        to.initial.reverse();
+        assert_eq!(diff(&from, &to), vec![]);
+    }
+
+    #[test]
+    fn modified_fund_is_reported() {
+        let from = genesis();
+        let mut to = genesis();
+        if let Initial::Fund(utxos) = &mut to.initial[0] {
+            utxos[0].value = 42.into();
+        }
+        let changes = diff(&from, &to);
+        assert_eq!(changes.len(), 1);
+        match &changes[0] {
+            Change::Modified { to, .. } => assert_eq!(to, "42"),
+            change => panic!("unexpected change {:?}", change),
+        }
+    }
+}
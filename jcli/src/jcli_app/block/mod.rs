@@ -8,7 +8,7 @@ use chain_impl_mockchain::{
     block::Block,
     ledger::{self, Ledger},
 };
-use jcli_app::utils::{error::CustomErrorFiller, io};
+use jcli_app::utils::{error::CustomErrorFiller, io, key_parser};
 use jormungandr_lib::interfaces::{
     block0_configuration_documented_example, Block0Configuration, Block0ConfigurationError,
 };
@@ -16,6 +16,10 @@ use std::io::{BufRead, Write};
 use std::path::PathBuf;
 use structopt::StructOpt;
 
+mod build;
+mod diff;
+mod validate;
+
 custom_error! {pub Error
     InputInvalid { source: std::io::Error, path: PathBuf }
         = @{{ let _ = source; format_args!("invalid input file path '{}'", path.display()) }},
@@ -27,6 +31,18 @@ custom_error! {pub Error
     BlockSerializationFailed { source: std::io::Error, filler: CustomErrorFiller } = "failed to serialize block",
     GenesisSerializationFailed { source: serde_yaml::Error, filler: CustomErrorFiller } = "failed to serialize genesis",
     BuildingGenesisFromBlock0Failed { source: Block0ConfigurationError } = "failed to build genesis from block 0",
+    GenesisOrBlockInvalid { path: PathBuf }
+        = @{{ format_args!("'{}' is neither a genesis file nor a block 0", path.display()) }},
+    GenesisValidationFailed { errors: usize } = "genesis validation failed with {errors} error(s)",
+    ParametersFileCorrupted { source: serde_yaml::Error, path: PathBuf }
+        = @{{ format_args!("blockchain parameters file '{}' corrupted: {}", path.display(), source) }},
+    AllocationInvalid { path: PathBuf, line: usize, reason: String }
+        = @{{ format_args!("invalid allocation in '{}' at line {}: {}", path.display(), line, reason) }},
+    SecretKeyInvalid { source: key_parser::Error } = "invalid secret key",
+    KeyFileInvalid { path: PathBuf }
+        = @{{ format_args!("'{}' does not contain a valid key", path.display()) }},
+    StakePoolSerialInvalid { path: PathBuf }
+        = @{{ format_args!("'{}' does not contain a valid stake pool serial", path.display()) }},
 }
 
 impl Genesis {
@@ -36,6 +52,9 @@ impl Genesis {
             Genesis::Encode(create_arguments) => encode_block_0(create_arguments),
             Genesis::Decode(info_arguments) => decode_block_0(info_arguments),
             Genesis::Hash(hash_arguments) => print_hash(hash_arguments),
+            Genesis::Validate(validate) => validate.exec(),
+            Genesis::Diff(diff) => diff.exec(),
+            Genesis::Build(build) => build.exec(),
         }
     }
 }
@@ -97,6 +116,17 @@ pub enum Genesis {
 
     /// print the block hash (aka the block id) of the block 0
     Hash(Input),
+
+    /// check the genesis file (or the block 0) for common mistakes
+    /// (total supply overflow, duplicate addresses, missing leaders...)
+    Validate(validate::Validate),
+
+    /// compare two genesis files (or block 0) and print the differences
+    Diff(diff::Diff),
+
+    /// build the genesis file and the block 0 from the blockchain parameters,
+    /// the initial funds allocations and the leaders and stake pools keys
+    Build(build::Build),
 }
 
 #[derive(StructOpt)]
@@ -124,6 +154,34 @@ impl Input {
             filler: CustomErrorFiller,
         })
     }
+
+    /// load the genesis from either a YAML genesis file or from
+    /// an encoded block 0
+    fn load_genesis(&self) -> Result<Block0Configuration, Error> {
+        load_genesis(&self.input_file)
+    }
+}
+
+fn load_genesis(path: &Option<PathBuf>) -> Result<Block0Configuration, Error> {
+    use std::io::Read as _;
+
+    let mut bytes = Vec::new();
+    io::open_file_read(path)
+        .and_then(|mut reader| reader.read_to_end(&mut bytes))
+        .map_err(|source| Error::InputInvalid {
+            source,
+            path: io::path_to_path_buf(path),
+        })?;
+
+    if let Ok(genesis) = serde_yaml::from_slice(&bytes) {
+        return Ok(genesis);
+    }
+    match Block::deserialize(bytes.as_slice()) {
+        Ok(block) => Ok(Block0Configuration::from_block(&block)?),
+        Err(_) => Err(Error::GenesisOrBlockInvalid {
+            path: io::path_to_path_buf(path),
+        }),
+    }
 }
 
 #[derive(StructOpt)]
@@ -0,0 +1,268 @@
+use chain_addr::Discrimination;
+use chain_core::property::{Block as _, HasMessages as _};
+use chain_impl_mockchain::{
+    block::ConsensusVersion,
+    certificate::{self, CertificateContent},
+    ledger::Ledger,
+};
+use jcli_app::block::{Error, Input};
+use jormungandr_lib::interfaces::{Block0Configuration, Initial};
+use std::{collections::BTreeMap, fmt};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct Validate {
+    #[structopt(flatten)]
+    input: Input,
+}
+
+/// how much a given issue prevents the genesis from being used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// the genesis is valid but this is likely a mistake
+    Warning,
+    /// the genesis cannot be used as is
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    TotalSupplyOverflow,
+    DuplicateAddress {
+        address: String,
+        occurrences: usize,
+    },
+    NoConsensusLeaders,
+    DiscriminationMismatch {
+        address: String,
+        expected: Discrimination,
+    },
+    UnsignedStakePoolRegistration {
+        pool_id: String,
+    },
+    InvalidLedger {
+        reason: String,
+    },
+}
+
+impl Validate {
+    pub fn exec(self) -> Result<(), Error> {
+        let genesis = self.input.load_genesis()?;
+        let issues = validate(&genesis);
+
+        let mut errors = 0;
+        for issue in issues.iter() {
+            if issue.severity() == Severity::Error {
+                errors += 1;
+            }
+            println!("{}: {}", issue.severity(), issue);
+        }
+
+        if errors > 0 {
+            Err(Error::GenesisValidationFailed { errors })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// run all the checks on the given genesis and collect the found issues
+pub fn validate(genesis: &Block0Configuration) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    check_consensus_leaders(genesis, &mut issues);
+    check_total_supply(genesis, &mut issues);
+    check_duplicate_addresses(genesis, &mut issues);
+    check_discrimination(genesis, &mut issues);
+    check_stake_pool_registrations(genesis, &mut issues);
+    check_ledger(genesis, &mut issues);
+
+    issues
+}
+
+fn check_consensus_leaders(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    let config = &genesis.blockchain_configuration;
+    if config.block0_consensus() == ConsensusVersion::Bft
+        && config.consensus_leader_ids().is_empty()
+    {
+        issues.push(Issue::NoConsensusLeaders);
+    }
+}
+
+fn check_total_supply(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    let values = genesis.initial.iter().flat_map(|initial| -> Vec<u64> {
+        match initial {
+            Initial::Fund(utxos) => utxos.iter().map(|utxo| utxo.value.as_ref().0).collect(),
+            Initial::LegacyFund(utxos) => utxos.iter().map(|utxo| utxo.value.as_ref().0).collect(),
+            Initial::Cert(_) => Vec::new(),
+        }
+    });
+
+    let mut total = 0u64;
+    for value in values {
+        match total.checked_add(value) {
+            Some(sum) => total = sum,
+            None => {
+                issues.push(Issue::TotalSupplyOverflow);
+                return;
+            }
+        }
+    }
+}
+
+fn check_duplicate_addresses(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    let mut occurrences: BTreeMap<String, usize> = BTreeMap::new();
+    for initial in genesis.initial.iter() {
+        let addresses: Vec<String> = match initial {
+            Initial::Fund(utxos) => utxos.iter().map(|utxo| utxo.address.to_string()).collect(),
+            Initial::LegacyFund(utxos) => {
+                utxos.iter().map(|utxo| utxo.address.to_string()).collect()
+            }
+            Initial::Cert(_) => Vec::new(),
+        };
+        for address in addresses {
+            *occurrences.entry(address).or_insert(0) += 1;
+        }
+    }
+
+    issues.extend(
+        occurrences
+            .into_iter()
+            .filter(|(_, occurrences)| *occurrences > 1)
+            .map(|(address, occurrences)| Issue::DuplicateAddress {
+                address,
+                occurrences,
+            }),
+    );
+}
+
+fn check_discrimination(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    let expected = genesis.blockchain_configuration.discrimination();
+    for initial in genesis.initial.iter() {
+        if let Initial::Fund(utxos) = initial {
+            issues.extend(
+                utxos
+                    .iter()
+                    .filter(|utxo| utxo.address.as_ref().0 != expected)
+                    .map(|utxo| Issue::DiscriminationMismatch {
+                        address: utxo.address.to_string(),
+                        expected,
+                    }),
+            );
+        }
+    }
+}
+
+fn check_stake_pool_registrations(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    for initial in genesis.initial.iter() {
+        if let Initial::Cert(cert) = initial {
+            let cert: certificate::Certificate = cert.clone().into();
+            if let CertificateContent::StakePoolRegistration(info) = &cert.content {
+                if cert.signatures.is_empty() {
+                    issues.push(Issue::UnsignedStakePoolRegistration {
+                        pool_id: info.to_id().to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn check_ledger(genesis: &Block0Configuration, issues: &mut Vec<Issue>) {
+    let block = genesis.to_block();
+    if let Err(error) = Ledger::new(block.id(), block.messages()) {
+        issues.push(Issue::InvalidLedger {
+            reason: error.to_string(),
+        });
+    }
+}
+
+impl Issue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::DuplicateAddress { .. } => Severity::Warning,
+            Issue::TotalSupplyOverflow
+            | Issue::NoConsensusLeaders
+            | Issue::DiscriminationMismatch { .. }
+            | Issue::UnsignedStakePoolRegistration { .. }
+            | Issue::InvalidLedger { .. } => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::TotalSupplyOverflow => {
+                write!(f, "the total supply of the initial funds overflows")
+            }
+            Issue::DuplicateAddress {
+                address,
+                occurrences,
+            } => write!(
+                f,
+                "address {} is funded {} times in the initial funds",
+                address, occurrences
+            ),
+            Issue::NoConsensusLeaders => write!(
+                f,
+                "the block0 consensus is BFT but there is no consensus leader ids"
+            ),
+            Issue::DiscriminationMismatch { address, expected } => write!(
+                f,
+                "address {} does not have the blockchain's discrimination ({})",
+                address, expected
+            ),
+            Issue::UnsignedStakePoolRegistration { pool_id } => write!(
+                f,
+                "the stake pool registration certificate of pool {} is not signed",
+                pool_id
+            ),
+            Issue::InvalidLedger { reason } => {
+                write!(f, "the ledger cannot be built from the block 0: {}", reason)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jormungandr_lib::interfaces::block0_configuration_documented_example;
+
+    fn genesis() -> Block0Configuration {
+        serde_yaml::from_str(&block0_configuration_documented_example()).unwrap()
+    }
+
+    #[test]
+    fn duplicate_addresses_are_reported() {
+        let mut genesis = genesis();
+        genesis.initial.push(genesis.initial[0].clone());
+        let issues = validate(&genesis);
+        assert!(issues.iter().any(|issue| match issue {
+            Issue::DuplicateAddress { occurrences, .. } => *occurrences == 2,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn total_supply_overflow_is_reported() {
+        let mut genesis = genesis();
+        if let Initial::Fund(utxos) = &mut genesis.initial[0] {
+            let mut utxo = utxos[0].clone();
+            utxo.value = std::u64::MAX.into();
+            utxos.push(utxo);
+        }
+        let issues = validate(&genesis);
+        assert!(issues.contains(&Issue::TotalSupplyOverflow));
+    }
+}
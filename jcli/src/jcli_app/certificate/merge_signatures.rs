@@ -0,0 +1,45 @@
+use chain_impl_mockchain::certificate::Certificate;
+use jcli_app::certificate::{self, Error};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct MergeSignatures {
+    /// get the certificate to merge the signatures into from the given
+    /// file. If no file provided, it will be read from the standard input
+    #[structopt(long = "input", parse(from_os_str))]
+    pub input: Option<PathBuf>,
+    /// write the certificate with all the signatures into the given file.
+    /// If no file provided it will be written into the standard output
+    #[structopt(long = "output", parse(from_os_str))]
+    pub output: Option<PathBuf>,
+    /// the copies of the same certificate, signed by the other owners
+    #[structopt(name = "OTHER_CERTIFICATE", parse(from_os_str), raw(required = "true"))]
+    pub others: Vec<PathBuf>,
+}
+
+impl MergeSignatures {
+    pub fn exec(self) -> Result<(), Error> {
+        let mut cert: Certificate = certificate::read_cert(self.input)?.into();
+        for other in self.others {
+            let other: Certificate = certificate::read_cert(Some(other))?.into();
+            merge_signatures(&mut cert, other)?;
+        }
+        certificate::write_cert(self.output, cert.into())
+    }
+}
+
+/// add the signatures of the other copy of the certificate, the ones
+/// already present being skipped
+fn merge_signatures(cert: &mut Certificate, other: Certificate) -> Result<(), Error> {
+    if cert.content != other.content {
+        return Err(Error::MergedCertificateMismatch);
+    }
+    for signature in other.signatures {
+        if !cert.signatures.contains(&signature) {
+            cert.signatures.push(signature);
+        }
+    }
+    Ok(())
+}
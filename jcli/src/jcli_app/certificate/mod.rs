@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use structopt::StructOpt;
 
 mod get_stake_pool_id;
+mod merge_signatures;
 mod new_stake_delegation;
 mod new_stake_pool_registration;
 mod sign;
@@ -19,6 +20,7 @@ custom_error! {pub Error
     OutputInvalid { source: std::io::Error, path: PathBuf }
         = @{{ let _ = source; format_args!("invalid output file path '{}'", path.display()) }},
     InvalidCertificate { source: CertificateFromStrError } = "Invalid certificate",
+    MergedCertificateMismatch = "cannot merge the signatures of a different certificate",
 }
 
 #[derive(StructOpt)]
@@ -29,6 +31,9 @@ pub enum Certificate {
     /// Sign certificate, you can call this command multiple
     /// time to add multiple signatures if this is required.
     Sign(sign::Sign),
+    /// merge the signatures of copies of the same certificate, signed
+    /// offline by the different owners
+    MergeSignatures(merge_signatures::MergeSignatures),
     /// get the stake pool id from the given stake pool registration certificate
     GetStakePoolId(get_stake_pool_id::GetStakePoolId),
 }
@@ -67,6 +72,7 @@ impl Certificate {
         match self {
             Certificate::New(args) => args.exec()?,
             Certificate::Sign(args) => args.exec()?,
+            Certificate::MergeSignatures(args) => args.exec()?,
             Certificate::GetStakePoolId(args) => args.exec()?,
         }
 
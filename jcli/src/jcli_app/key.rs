@@ -1,11 +1,15 @@
 use bech32::{u5, Bech32, FromBase32, ToBase32};
+use bip39::{Language, Mnemonic, MnemonicType};
 use chain_crypto::{
     AsymmetricKey, AsymmetricPublicKey, Curve25519_2HashDH, Ed25519, Ed25519Bip32, Ed25519Extended,
-    SumEd25519_12,
+    PublicKey, SecretKey, SigningAlgorithm, SumEd25519_12, Verification, VerificationAlgorithm,
 };
+use cryptoxide::{hmac::Hmac, pbkdf2::pbkdf2, sha2::Sha512};
+use ed25519_bip32::{DerivationScheme, XPrv, XPRV_SIZE};
 use hex;
 use jcli_app::utils::io;
-use rand::{rngs::EntropyRng, SeedableRng};
+use jormungandr_lib::crypto::key::{Identifier, Signature, SigningKey};
+use rand::{rngs::EntropyRng, RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 use std::{
     io::{BufRead, BufReader, Write},
@@ -25,6 +29,18 @@ custom_error! { pub Error
     InvalidOutput { source: std::io::Error, path: PathBuf }
         = @{{ let _ = source; format_args!("invalid output file path '{}'", path.display()) }},
     UnknownBech32PrivKeyHrp { hrp: String } = "unrecognized private key bech32 HRP: {hrp}",
+    MnemonicWordCount { count: usize }
+        = "invalid number of mnemonic words {count}, expected 12, 15, 18, 21 or 24",
+    MnemonicInvalid { reason: String } = "invalid mnemonic: {reason}",
+    Bip32KeyExpected { hrp: String }
+        = "expected an ed25519bip32 private key but received a key with bech32 HRP: {hrp}",
+    Bip32KeyInvalid { reason: String } = "invalid ed25519bip32 private key: {reason}",
+    DerivationPathInvalid { path: String } = "invalid derivation path '{path}'",
+    PublicKey { source: chain_crypto::PublicKeyError } = "invalid public key",
+    UnknownBech32PubKeyHrp { hrp: String } = "unrecognized public key bech32 HRP: {hrp}",
+    SigningNotSupported { hrp: String } = "private key with bech32 HRP {hrp} cannot be used for signing",
+    SignatureMalformed = "signature is neither a valid bech32 nor a valid hexadecimal signature",
+    SignatureVerificationFailed = "signature verification failed",
 }
 
 #[derive(StructOpt, Debug)]
@@ -38,6 +54,16 @@ pub enum Key {
     FromBytes(FromBytes),
     /// get the bytes out of a private key
     ToBytes(ToBytes),
+    /// generate a BIP39 mnemonic phrase
+    GenerateMnemonic(GenerateMnemonic),
+    /// restore the root ed25519bip32 private key from a BIP39 mnemonic phrase
+    FromMnemonic(FromMnemonic),
+    /// derive a child ed25519bip32 private key along the given derivation path
+    Derive(Derive),
+    /// sign the content of a file with a private key
+    Sign(Sign),
+    /// verify the signature of the content of a file with a public key
+    Verify(Verify),
 }
 
 #[derive(StructOpt, Debug)]
@@ -99,6 +125,91 @@ pub struct ToPublic {
     output_file: OutputFile,
 }
 
+#[derive(StructOpt, Debug)]
+pub struct GenerateMnemonic {
+    /// number of words of the mnemonic phrase: 12, 15, 18, 21 or 24
+    #[structopt(long = "words", default_value = "24")]
+    words: usize,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+
+    /// optional seed to generate the mnemonic, for the same entropy the same
+    /// mnemonic will be generated (32 bytes in hexadecimal). This seed will be
+    /// fed to ChaChaRNG and allow pseudo random mnemonic generation. Do not use
+    /// if you are not sure.
+    #[structopt(long = "seed", short = "s", name = "SEED", parse(try_from_str))]
+    seed: Option<Seed>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct FromMnemonic {
+    /// the file with the mnemonic phrase (space separated words on one line)
+    ///
+    /// if no value passed, the mnemonic phrase will be read from the
+    /// standard input
+    #[structopt(long = "input")]
+    input_mnemonic: Option<PathBuf>,
+
+    /// optional password protecting the root key
+    #[structopt(long = "password", default_value = "")]
+    password: String,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct Derive {
+    /// the derivation path, e.g. `m/1852'/1815'/0'/0/0`, indices followed
+    /// by `'` or `h` are hardened
+    #[structopt(long = "path", parse(try_from_str))]
+    path: DerivationPath,
+
+    /// the ed25519bip32 private key to derive from
+    ///
+    /// if no value passed, the private key will be read from the
+    /// standard input
+    #[structopt(long = "input")]
+    input_key: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct Sign {
+    /// path to the private key to sign with
+    #[structopt(long = "secret-key")]
+    secret_key: PathBuf,
+
+    /// output the signature in hexadecimal instead of bech32
+    #[structopt(long = "hex")]
+    hex: bool,
+
+    /// path to the file to sign, or read from the standard input
+    #[structopt(name = "INPUT_FILE")]
+    input: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    output_file: OutputFile,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct Verify {
+    /// path to the public key of the signer
+    #[structopt(long = "public-key")]
+    public_key: PathBuf,
+
+    /// path to the signature, in bech32 or in hexadecimal
+    #[structopt(long = "signature")]
+    signature: PathBuf,
+
+    /// path to the signed file, or read from the standard input
+    #[structopt(name = "INPUT_FILE")]
+    input: Option<PathBuf>,
+}
+
 #[derive(StructOpt, Debug)]
 struct OutputFile {
     /// output the key to the given file or to stdout if not provided
@@ -133,6 +244,11 @@ impl Key {
             Key::ToPublic(args) => args.exec(),
             Key::ToBytes(args) => args.exec(),
             Key::FromBytes(args) => args.exec(),
+            Key::GenerateMnemonic(args) => args.exec(),
+            Key::FromMnemonic(args) => args.exec(),
+            Key::Derive(args) => args.exec(),
+            Key::Sign(args) => args.exec(),
+            Key::Verify(args) => args.exec(),
         }
     }
 }
@@ -214,10 +330,187 @@ impl FromBytes {
     }
 }
 
+impl GenerateMnemonic {
+    fn exec(self) -> Result<(), Error> {
+        let mnemonic_type = MnemonicType::for_word_count(self.words)
+            .map_err(|_| Error::MnemonicWordCount { count: self.words })?;
+        let mut entropy = vec![0; mnemonic_type.entropy_bits() / 8];
+        rng(self.seed)?.fill_bytes(&mut entropy);
+        let mnemonic = Mnemonic::from_entropy(&entropy, Language::English).map_err(|e| {
+            Error::MnemonicInvalid {
+                reason: e.to_string(),
+            }
+        })?;
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", mnemonic.phrase())?;
+        Ok(())
+    }
+}
+
+impl FromMnemonic {
+    fn exec(self) -> Result<(), Error> {
+        let phrase = read_line(self.input_mnemonic)?;
+        let mnemonic = Mnemonic::from_phrase(phrase.trim(), Language::English).map_err(|e| {
+            Error::MnemonicInvalid {
+                reason: e.to_string(),
+            }
+        })?;
+        let root = root_key_from_entropy(mnemonic.entropy(), self.password.as_bytes())?;
+        let mut output = self.output_file.open()?;
+        writeln!(
+            output,
+            "{}",
+            bytes_to_priv_key::<Ed25519Bip32>(root.as_ref())?
+        )?;
+        Ok(())
+    }
+}
+
+impl Derive {
+    fn exec(self) -> Result<(), Error> {
+        let bech32 = read_bech32(self.input_key)?;
+        if bech32.hrp() != Ed25519Bip32::SECRET_BECH32_HRP {
+            return Err(Error::Bip32KeyExpected {
+                hrp: bech32.hrp().to_string(),
+            });
+        }
+        let bytes = Vec::<u8>::from_base32(bech32.data())?;
+        if bytes.len() != XPRV_SIZE {
+            return Err(Error::Bip32KeyInvalid {
+                reason: format!("expected {} bytes, received {}", XPRV_SIZE, bytes.len()),
+            });
+        }
+        let mut xprv_bytes = [0; XPRV_SIZE];
+        xprv_bytes.copy_from_slice(&bytes);
+        let mut xprv =
+            XPrv::from_bytes_verified(xprv_bytes).map_err(|e| Error::Bip32KeyInvalid {
+                reason: e.to_string(),
+            })?;
+        for index in self.path.0 {
+            xprv = xprv.derive(DerivationScheme::V2, index);
+        }
+        let mut output = self.output_file.open()?;
+        writeln!(
+            output,
+            "{}",
+            bytes_to_priv_key::<Ed25519Bip32>(xprv.as_ref())?
+        )?;
+        Ok(())
+    }
+}
+
+impl Sign {
+    fn exec(self) -> Result<(), Error> {
+        let bech32 = read_bech32(Some(&self.secret_key))?;
+        let key = bech32.data();
+        let data = read_bytes(self.input)?;
+        let signature = match bech32.hrp() {
+            Ed25519::SECRET_BECH32_HRP => sign::<Ed25519>(key, data, self.hex),
+            Ed25519Bip32::SECRET_BECH32_HRP => sign::<Ed25519Bip32>(key, data, self.hex),
+            Ed25519Extended::SECRET_BECH32_HRP => sign::<Ed25519Extended>(key, data, self.hex),
+            SumEd25519_12::SECRET_BECH32_HRP => sign::<SumEd25519_12>(key, data, self.hex),
+            Curve25519_2HashDH::SECRET_BECH32_HRP => Err(Error::SigningNotSupported {
+                hrp: bech32.hrp().to_string(),
+            }),
+            other => Err(Error::UnknownBech32PrivKeyHrp {
+                hrp: other.to_string(),
+            }),
+        }?;
+        let mut output = self.output_file.open()?;
+        writeln!(output, "{}", signature)?;
+        Ok(())
+    }
+}
+
+impl Verify {
+    fn exec(self) -> Result<(), Error> {
+        let bech32 = read_bech32(Some(&self.public_key))?;
+        let key = bech32.data();
+        let signature = read_line(Some(&self.signature))?;
+        let signature = signature.trim();
+        let data = read_bytes(self.input)?;
+        let verification = match bech32.hrp() {
+            Ed25519::PUBLIC_BECH32_HRP => verify::<Ed25519>(key, signature, data),
+            Ed25519Bip32::PUBLIC_BECH32_HRP => verify::<Ed25519Bip32>(key, signature, data),
+            SumEd25519_12::PUBLIC_BECH32_HRP => verify::<SumEd25519_12>(key, signature, data),
+            other => Err(Error::UnknownBech32PubKeyHrp {
+                hrp: other.to_string(),
+            }),
+        }?;
+        match verification {
+            Verification::Success => {
+                println!("Success");
+                Ok(())
+            }
+            Verification::Failed => Err(Error::SignatureVerificationFailed),
+        }
+    }
+}
+
+fn sign<A>(key: &[u5], data: Vec<u8>, hex: bool) -> Result<String, Error>
+where
+    A: SigningAlgorithm,
+    <A as AsymmetricKey>::PubAlg: VerificationAlgorithm,
+{
+    let key_bytes = Vec::<u8>::from_base32(key)?;
+    let key: SigningKey<A> = SecretKey::from_binary(&key_bytes)?.into();
+    let signature = key.sign(&data);
+    if hex {
+        Ok(signature.to_hex())
+    } else {
+        Ok(signature.to_bech32_str())
+    }
+}
+
+fn verify<A: VerificationAlgorithm>(
+    key: &[u5],
+    signature: &str,
+    data: Vec<u8>,
+) -> Result<Verification, Error> {
+    let key_bytes = Vec::<u8>::from_base32(key)?;
+    let identifier: Identifier<A> = PublicKey::from_binary(&key_bytes)?.into();
+    let signature: Signature<Vec<u8>, A> = Signature::from_bech32_str(signature)
+        .or_else(|_| Signature::from_hex(signature))
+        .map_err(|_| Error::SignatureMalformed)?;
+    Ok(signature.verify(&identifier, &data))
+}
+
+/// compute the root key of the mnemonic's entropy the same way as the
+/// Icarus wallets: PBKDF2-HMAC-SHA512 of the password salted with the
+/// entropy, with the scalar bits cleared and set as required by ed25519bip32
+fn root_key_from_entropy(entropy: &[u8], password: &[u8]) -> Result<XPrv, Error> {
+    const ITERATIONS: u32 = 4096;
+
+    let mut bytes = [0; XPRV_SIZE];
+    let mut mac = Hmac::new(Sha512::new(), password);
+    pbkdf2(&mut mac, entropy, ITERATIONS, &mut bytes);
+    bytes[0] &= 0b1111_1000;
+    bytes[31] &= 0b0001_1111;
+    bytes[31] |= 0b0100_0000;
+    XPrv::from_bytes_verified(bytes).map_err(|e| Error::Bip32KeyInvalid {
+        reason: e.to_string(),
+    })
+}
+
 fn read_hex<P: AsRef<Path>>(path: Option<P>) -> Result<Vec<u8>, Error> {
     hex::decode(read_line(path)?.trim()).map_err(Into::into)
 }
 
+fn read_bytes<P: AsRef<Path>>(path: Option<P>) -> Result<Vec<u8>, Error> {
+    use std::io::Read as _;
+
+    let mut input = io::open_file_read(&path).map_err(|source| Error::InvalidInput {
+        source,
+        path: path
+            .as_ref()
+            .map(|path| path.as_ref().to_owned())
+            .unwrap_or_default(),
+    })?;
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
 fn read_bech32<P: AsRef<Path>>(path: Option<P>) -> Result<Bech32, Error> {
     read_line(path)?.trim().parse().map_err(Into::into)
 }
@@ -234,13 +527,17 @@ fn read_line<P: AsRef<Path>>(path: Option<P>) -> Result<String, Error> {
     Ok(line)
 }
 
-fn gen_priv_key<K: AsymmetricKey>(seed: Option<Seed>) -> Result<Bech32, Error> {
+fn rng(seed: Option<Seed>) -> Result<ChaChaRng, Error> {
     let rng = if let Some(seed) = seed {
         ChaChaRng::from_seed(seed.0)
     } else {
         ChaChaRng::from_rng(EntropyRng::new())?
     };
-    let secret = K::generate(rng);
+    Ok(rng)
+}
+
+fn gen_priv_key<K: AsymmetricKey>(seed: Option<Seed>) -> Result<Bech32, Error> {
+    let secret = K::generate(rng(seed)?);
     let hrp = K::SECRET_BECH32_HRP.to_string();
     Ok(Bech32::new(hrp, secret.to_base32())?)
 }
@@ -275,3 +572,78 @@ impl std::str::FromStr for Seed {
         Ok(Seed(bytes))
     }
 }
+
+/// a BIP32 derivation path, hardened indices have the `0x80000000` bit set
+#[derive(Debug)]
+struct DerivationPath(Vec<u32>);
+impl std::str::FromStr for DerivationPath {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const HARDENED: u32 = 0x8000_0000;
+
+        let invalid = || Error::DerivationPathInvalid { path: s.to_owned() };
+        let mut segments = s.trim().split('/').peekable();
+        if segments.peek() == Some(&"m") {
+            segments.next();
+        }
+        segments
+            .map(|segment| {
+                let (index, hardened) = if segment.ends_with('\'') || segment.ends_with('h') {
+                    (&segment[..segment.len() - 1], true)
+                } else {
+                    (segment, false)
+                };
+                let index: u32 = index.parse().map_err(|_| invalid())?;
+                if index >= HARDENED {
+                    return Err(invalid());
+                }
+                Ok(if hardened { index | HARDENED } else { index })
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the mnemonic of the Icarus test vectors of CIP-3
+    const MNEMONIC: &str = "eight country switch draw meat scout mystery blade tip drift useless good keep usage title";
+
+    fn root_key(password: &str) -> XPrv {
+        let mnemonic = Mnemonic::from_phrase(MNEMONIC, Language::English).unwrap();
+        root_key_from_entropy(mnemonic.entropy(), password.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn root_key_matches_icarus_test_vectors() {
+        assert_eq!(
+            hex::encode(root_key("").as_ref()),
+            "c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245\
+             d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a\
+             23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620"
+        );
+        assert_eq!(
+            hex::encode(root_key("foo").as_ref()),
+            "70531039904019351e1afb361cd1b312a4d0565d4ff9f8062d38acf4b15cce41\
+             d7b5738d9c893feea55512a3004acb0d222c35d3e3d5cde943a15a9824cbac59\
+             443cf67e589614076ba01e354b1a432e0e6db3b59e37fc56b5fb0222970a010e"
+        );
+    }
+
+    /// the public key of the CIP-3 mnemonic at m/1852'/1815'/0'/0/0,
+    /// computed with an independent implementation of ed25519bip32 V2
+    #[test]
+    fn derived_public_key_matches_known_answer() {
+        let path: DerivationPath = "m/1852'/1815'/0'/0/0".parse().unwrap();
+        let xprv = path.0.iter().fold(root_key(""), |xprv, index| {
+            xprv.derive(DerivationScheme::V2, *index)
+        });
+        assert_eq!(
+            hex::encode(xprv.public().as_ref()),
+            "cc9809944150c00f3913cd2b103e9b42fe6243fc36a76f9eb800692e2bda3f2e\
+             2b2dd0a9b83141f6650c40abec9ed52ecaa6a567825cb2c7a14b9452bca0c020"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use jcli_app::utils::{AccountId, DebugFlag, HostAddr, OutputFormat, RestApiSender};
+use jcli_app::utils::{
+    AccountId, DebugFlag, HostAddr, LedgerAt, OutputFormat, RestApiSender, RestCredentials,
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -11,30 +13,102 @@ pub enum Account {
         #[structopt(flatten)]
         debug: DebugFlag,
         #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
         output_format: OutputFormat,
+        #[structopt(flatten)]
+        at: LedgerAt,
         /// An Account ID either in the form of an address of kind account, or an account public key
         #[structopt(parse(try_from_str = "AccountId::try_from_str"))]
         account_id: AccountId,
     },
+    /// Get the state of many accounts at once. The accounts not found
+    /// in the ledger are `null`
+    GetMany {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
+        output_format: OutputFormat,
+        #[structopt(flatten)]
+        at: LedgerAt,
+        /// Account IDs either in the form of addresses of kind account, or account public keys
+        #[structopt(
+            name = "ACCOUNT_ID",
+            parse(try_from_str = "AccountId::try_from_str"),
+            raw(required = "true")
+        )]
+        account_ids: Vec<AccountId>,
+    },
 }
 
 impl Account {
     pub fn exec(self) {
-        let Account::Get {
-            addr,
-            debug,
-            output_format,
-            account_id,
-        } = self;
-        let url = addr
-            .with_segments(&["v0", "account", &account_id.to_url_arg()])
-            .unwrap()
-            .into_url();
-        let builder = reqwest::Client::new().get(url);
-        let response = RestApiSender::new(builder, &debug).send().unwrap();
-        response.response().error_for_status_ref().unwrap();
-        let state = response.body().json_value().unwrap();
-        let formatted = output_format.format_json(state).unwrap();
-        println!("{}", formatted);
+        match self {
+            Account::Get {
+                addr,
+                debug,
+                credentials,
+                output_format,
+                at,
+                account_id,
+            } => get(addr, debug, credentials, output_format, at, account_id),
+            Account::GetMany {
+                addr,
+                debug,
+                credentials,
+                output_format,
+                at,
+                account_ids,
+            } => get_many(addr, debug, credentials, output_format, at, account_ids),
+        }
     }
 }
+
+fn get(
+    addr: HostAddr,
+    debug: DebugFlag,
+    credentials: RestCredentials,
+    output_format: OutputFormat,
+    at: LedgerAt,
+    account_id: AccountId,
+) {
+    let url = addr
+        .with_segments(&["v0", "account", &account_id.to_url_arg()])
+        .unwrap()
+        .into_url();
+    let builder = reqwest::Client::new().get(url).query(&at.query());
+    let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
+        .send()
+        .unwrap();
+    response.response().error_for_status_ref().unwrap();
+    let state = response.body().json_value().unwrap();
+    let formatted = output_format.format_json(state).unwrap();
+    println!("{}", formatted);
+}
+
+fn get_many(
+    addr: HostAddr,
+    debug: DebugFlag,
+    credentials: RestCredentials,
+    output_format: OutputFormat,
+    at: LedgerAt,
+    account_ids: Vec<AccountId>,
+) {
+    let url = addr.with_segments(&["v0", "accounts"]).unwrap().into_url();
+    let account_ids: Vec<String> = account_ids.iter().map(AccountId::to_url_arg).collect();
+    let builder = reqwest::Client::new().post(url).query(&at.query());
+    let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
+        .with_json_body(&account_ids)
+        .send()
+        .unwrap();
+    response.response().error_for_status_ref().unwrap();
+    let states = response.body().json_value().unwrap();
+    let formatted = output_format.format_json(states).unwrap();
+    println!("{}", formatted);
+}
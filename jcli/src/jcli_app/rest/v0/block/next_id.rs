@@ -1,6 +1,6 @@
 use chain_crypto::Blake2b256;
 use hex;
-use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender};
+use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender, RestCredentials};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -12,6 +12,8 @@ pub enum NextId {
         addr: HostAddr,
         #[structopt(flatten)]
         debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
         /// Maximum number of IDs, must be between 1 and 100, default 1
         #[structopt(short, long)]
         count: Option<usize>,
@@ -21,18 +23,32 @@ pub enum NextId {
 impl NextId {
     pub fn exec(self, block_id: String) {
         match self {
-            NextId::Get { addr, debug, count } => exec_get(block_id, addr, debug, count),
+            NextId::Get {
+                addr,
+                debug,
+                credentials,
+                count,
+            } => exec_get(block_id, addr, debug, credentials, count),
         }
     }
 }
 
-fn exec_get(block_id: String, addr: HostAddr, debug: DebugFlag, count: Option<usize>) {
+fn exec_get(
+    block_id: String,
+    addr: HostAddr,
+    debug: DebugFlag,
+    credentials: RestCredentials,
+    count: Option<usize>,
+) {
     let url = addr
         .with_segments(&["v0", "block", &block_id, "next_id"])
         .unwrap()
         .into_url();
     let builder = reqwest::Client::new().get(url).query(&[("count", count)]);
-    let response = RestApiSender::new(builder, &debug).send().unwrap();
+    let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
+        .send()
+        .unwrap();
     response.response().error_for_status_ref().unwrap();
     let body = response.body().binary();
     for block_id in body.chunks(Blake2b256::HASH_SIZE) {
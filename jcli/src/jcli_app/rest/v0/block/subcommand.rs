@@ -1,6 +1,6 @@
 use super::next_id::NextId;
 use hex;
-use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender};
+use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender, RestCredentials};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -12,6 +12,8 @@ pub enum Subcommand {
         addr: HostAddr,
         #[structopt(flatten)]
         debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
     },
     /// Get block descendant ID
     NextId(NextId),
@@ -20,19 +22,26 @@ pub enum Subcommand {
 impl Subcommand {
     pub fn exec(self, block_id: String) {
         match self {
-            Subcommand::Get { addr, debug } => exec_get(block_id, addr, debug),
+            Subcommand::Get {
+                addr,
+                debug,
+                credentials,
+            } => exec_get(block_id, addr, debug, credentials),
             Subcommand::NextId(next_id) => next_id.exec(block_id),
         }
     }
 }
 
-fn exec_get(block_id: String, addr: HostAddr, debug: DebugFlag) {
+fn exec_get(block_id: String, addr: HostAddr, debug: DebugFlag, credentials: RestCredentials) {
     let url = addr
         .with_segments(&["v0", "block", &block_id])
         .unwrap()
         .into_url();
     let builder = reqwest::Client::new().get(url);
-    let response = RestApiSender::new(builder, &debug).send().unwrap();
+    let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
+        .send()
+        .unwrap();
     response.response().error_for_status_ref().unwrap();
     let body = response.body().binary();
     println!("{}", hex::encode(&body));
@@ -1,5 +1,5 @@
 use hex;
-use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender};
+use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender, RestCredentials};
 use std::fs;
 use std::io::{stdin, BufRead};
 use std::path::PathBuf;
@@ -14,6 +14,8 @@ pub enum Message {
         addr: HostAddr,
         #[structopt(flatten)]
         debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
         /// File containing hex-encoded message.
         /// If not provided, message will be read from stdin.
         #[structopt(short, long)]
@@ -29,6 +31,8 @@ pub enum Message {
         #[structopt(flatten)]
         debug: DebugFlag,
         #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
         output_format: OutputFormat,
     },
 }
@@ -36,30 +40,49 @@ pub enum Message {
 impl Message {
     pub fn exec(self) {
         match self {
-            Message::Post { addr, debug, file } => post_message(file, addr, debug),
+            Message::Post {
+                addr,
+                debug,
+                credentials,
+                file,
+            } => post_message(file, addr, debug, credentials),
             Message::Logs {
                 addr,
                 debug,
+                credentials,
                 output_format,
-            } => get_logs(addr, debug, output_format),
+            } => get_logs(addr, debug, credentials, output_format),
         }
     }
 }
 
-fn get_logs(addr: HostAddr, debug: DebugFlag, output_format: OutputFormat) {
+fn get_logs(
+    addr: HostAddr,
+    debug: DebugFlag,
+    credentials: RestCredentials,
+    output_format: OutputFormat,
+) {
     let url = addr
         .with_segments(&["v0", "fragment", "logs"])
         .unwrap()
         .into_url();
     let builder = reqwest::Client::new().get(url);
-    let response = RestApiSender::new(builder, &debug).send().unwrap();
+    let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
+        .send()
+        .unwrap();
     response.response().error_for_status_ref().unwrap();
     let status = response.body().json_value().unwrap();
     let formatted = output_format.format_json(status).unwrap();
     println!("{}", formatted);
 }
 
-fn post_message(file: Option<PathBuf>, addr: HostAddr, debug: DebugFlag) {
+fn post_message(
+    file: Option<PathBuf>,
+    addr: HostAddr,
+    debug: DebugFlag,
+    credentials: RestCredentials,
+) {
     let msg_hex = match file {
         Some(path) => fs::read_to_string(path).unwrap(),
         None => {
@@ -72,6 +95,7 @@ fn post_message(file: Option<PathBuf>, addr: HostAddr, debug: DebugFlag) {
     let url = addr.with_segments(&["v0", "message"]).unwrap().into_url();
     let builder = reqwest::Client::new().post(url);
     let response = RestApiSender::new(builder, &debug)
+        .with_credentials(&credentials)
         .with_binary_body(msg_bin)
         .send()
         .unwrap();
@@ -2,6 +2,7 @@ mod account;
 mod block;
 mod message;
 mod node;
+mod stake_distribution;
 mod tip;
 mod utxo;
 
@@ -18,6 +19,8 @@ pub enum V0 {
     Message(message::Message),
     /// Node information
     Node(node::Node),
+    /// Stake distribution information
+    StakeDistribution(stake_distribution::StakeDistribution),
     /// Blockchain tip information
     Tip(tip::Tip),
     /// UTXO information
@@ -31,6 +34,7 @@ impl V0 {
             V0::Block(block) => block.exec(),
             V0::Message(message) => message.exec(),
             V0::Node(node) => node.exec(),
+            V0::StakeDistribution(stake_distribution) => stake_distribution.exec(),
             V0::Tip(tip) => tip.exec(),
             V0::Utxo(utxo) => utxo.exec(),
         }
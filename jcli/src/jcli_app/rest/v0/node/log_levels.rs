@@ -0,0 +1,119 @@
+use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender, RestCredentials};
+use serde_json::{Map, Value};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum LogLevels {
+    /// Get the log levels of the node
+    Get {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
+        output_format: OutputFormat,
+    },
+    /// Change the log levels of the running node
+    Set {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
+        output_format: OutputFormat,
+        /// level of the tasks without their own level: critical, error,
+        /// warning, info, debug or trace
+        #[structopt(long = "default")]
+        default: Option<String>,
+        /// level of a task, in the format `<task>=<level>`.
+        /// Can be given multiple times.
+        #[structopt(long = "task", parse(try_from_str = "parse_task_level"))]
+        tasks: Vec<(String, String)>,
+        /// task to log with the default level again.
+        /// Can be given multiple times.
+        #[structopt(long = "reset-task")]
+        reset_tasks: Vec<String>,
+    },
+}
+
+fn parse_task_level(task_level: &str) -> Result<(String, String), String> {
+    let mut parts = task_level.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(task), Some(level)) if !task.is_empty() && !level.is_empty() => {
+            Ok((task.to_owned(), level.to_owned()))
+        }
+        _ => Err(format!(
+            "invalid task level '{}', expected `<task>=<level>`",
+            task_level
+        )),
+    }
+}
+
+impl LogLevels {
+    pub fn exec(self) {
+        match self {
+            LogLevels::Get {
+                addr,
+                debug,
+                credentials,
+                output_format,
+            } => {
+                let url = addr
+                    .with_segments(&["v0", "node", "log_levels"])
+                    .unwrap()
+                    .into_url();
+                let builder = reqwest::Client::new().get(url);
+                let response = RestApiSender::new(builder, &debug)
+                    .with_credentials(&credentials)
+                    .send()
+                    .unwrap();
+                response.response().error_for_status_ref().unwrap();
+                let levels = response.body().json_value().unwrap();
+                let formatted = output_format.format_json(levels).unwrap();
+                println!("{}", formatted);
+            }
+            LogLevels::Set {
+                addr,
+                debug,
+                credentials,
+                output_format,
+                default,
+                tasks,
+                reset_tasks,
+            } => {
+                let url = addr
+                    .with_segments(&["v0", "node", "log_levels"])
+                    .unwrap()
+                    .into_url();
+                let mut task_levels = Map::new();
+                for (task, level) in tasks {
+                    task_levels.insert(task, Value::String(level));
+                }
+                for task in reset_tasks {
+                    task_levels.insert(task, Value::Null);
+                }
+                let mut update = Map::new();
+                if let Some(default) = default {
+                    update.insert("default".to_owned(), Value::String(default));
+                }
+                update.insert("tasks".to_owned(), Value::Object(task_levels));
+
+                let builder = reqwest::Client::new().post(url);
+                let response = RestApiSender::new(builder, &debug)
+                    .with_credentials(&credentials)
+                    .with_json_body(&update)
+                    .send()
+                    .unwrap();
+                response.response().error_for_status_ref().unwrap();
+                let levels = response.body().json_value().unwrap();
+                let formatted = output_format.format_json(levels).unwrap();
+                println!("{}", formatted);
+            }
+        }
+    }
+}
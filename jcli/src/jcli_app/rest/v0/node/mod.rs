@@ -1,5 +1,13 @@
+mod config;
+mod log_levels;
+mod services;
+mod shutdown;
 mod stats;
 
+use self::config::Config;
+use self::log_levels::LogLevels;
+use self::services::Services;
+use self::shutdown::Shutdown;
 use self::stats::Stats;
 use structopt::StructOpt;
 
@@ -8,12 +16,24 @@ use structopt::StructOpt;
 pub enum Node {
     /// Node information
     Stats(Stats),
+    /// Node services status
+    Services(Services),
+    /// Node effective configuration
+    Config(Config),
+    /// Node log levels
+    LogLevels(LogLevels),
+    /// Node shutdown
+    Shutdown(Shutdown),
 }
 
 impl Node {
     pub fn exec(self) {
         match self {
             Node::Stats(stats) => stats.exec(),
+            Node::Services(services) => services.exec(),
+            Node::Config(config) => config.exec(),
+            Node::LogLevels(log_levels) => log_levels.exec(),
+            Node::Shutdown(shutdown) => shutdown.exec(),
         }
     }
 }
@@ -0,0 +1,42 @@
+use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender, RestCredentials};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum Services {
+    /// Get the status of the node services
+    Get {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
+        output_format: OutputFormat,
+    },
+}
+
+impl Services {
+    pub fn exec(self) {
+        let Services::Get {
+            addr,
+            debug,
+            credentials,
+            output_format,
+        } = self;
+        let url = addr
+            .with_segments(&["v0", "node", "services"])
+            .unwrap()
+            .into_url();
+        let builder = reqwest::Client::new().get(url);
+        let response = RestApiSender::new(builder, &debug)
+            .with_credentials(&credentials)
+            .send()
+            .unwrap();
+        response.response().error_for_status_ref().unwrap();
+        let status = response.body().json_value().unwrap();
+        let formatted = output_format.format_json(status).unwrap();
+        println!("{}", formatted);
+    }
+}
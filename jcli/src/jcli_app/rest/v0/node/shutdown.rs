@@ -0,0 +1,37 @@
+use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender, RestCredentials};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum Shutdown {
+    /// Request the node to shut down
+    Post {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+    },
+}
+
+impl Shutdown {
+    pub fn exec(self) {
+        let Shutdown::Post {
+            addr,
+            debug,
+            credentials,
+        } = self;
+        let url = addr
+            .with_segments(&["v0", "node", "shutdown"])
+            .unwrap()
+            .into_url();
+        let builder = reqwest::Client::new().post(url);
+        let response = RestApiSender::new(builder, &debug)
+            .with_credentials(&credentials)
+            .send()
+            .unwrap();
+        response.response().error_for_status_ref().unwrap();
+        println!("Success!");
+    }
+}
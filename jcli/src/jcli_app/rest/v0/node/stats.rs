@@ -1,4 +1,4 @@
-use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender};
+use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender, RestCredentials};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -11,6 +11,8 @@ pub enum Stats {
         #[structopt(flatten)]
         debug: DebugFlag,
         #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
         output_format: OutputFormat,
     },
 }
@@ -20,6 +22,7 @@ impl Stats {
         let Stats::Get {
             addr,
             debug,
+            credentials,
             output_format,
         } = self;
         let url = addr
@@ -27,7 +30,10 @@ impl Stats {
             .unwrap()
             .into_url();
         let builder = reqwest::Client::new().get(url);
-        let response = RestApiSender::new(builder, &debug).send().unwrap();
+        let response = RestApiSender::new(builder, &debug)
+            .with_credentials(&credentials)
+            .send()
+            .unwrap();
         response.response().error_for_status_ref().unwrap();
         let status = response.body().json_value().unwrap();
         let formatted = output_format.format_json(status).unwrap();
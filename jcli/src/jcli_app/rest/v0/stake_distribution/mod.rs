@@ -0,0 +1,42 @@
+use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender, RestCredentials};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub enum StakeDistribution {
+    /// Get the stake distribution the leaders of each epoch are elected with
+    Get {
+        #[structopt(flatten)]
+        addr: HostAddr,
+        #[structopt(flatten)]
+        debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
+        output_format: OutputFormat,
+    },
+}
+
+impl StakeDistribution {
+    pub fn exec(self) {
+        let StakeDistribution::Get {
+            addr,
+            debug,
+            credentials,
+            output_format,
+        } = self;
+        let url = addr
+            .with_segments(&["v0", "stake_distribution"])
+            .unwrap()
+            .into_url();
+        let builder = reqwest::Client::new().get(url);
+        let response = RestApiSender::new(builder, &debug)
+            .with_credentials(&credentials)
+            .send()
+            .unwrap();
+        response.response().error_for_status_ref().unwrap();
+        let distributions = response.body().json_value().unwrap();
+        let formatted = output_format.format_json(distributions).unwrap();
+        println!("{}", formatted);
+    }
+}
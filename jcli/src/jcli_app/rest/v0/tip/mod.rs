@@ -1,4 +1,4 @@
-use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender};
+use jcli_app::utils::{DebugFlag, HostAddr, RestApiSender, RestCredentials};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -10,17 +10,26 @@ pub enum Tip {
         addr: HostAddr,
         #[structopt(flatten)]
         debug: DebugFlag,
+        #[structopt(flatten)]
+        credentials: RestCredentials,
     },
 }
 
 impl Tip {
     pub fn exec(self) {
-        let (addr, debug) = match self {
-            Tip::Get { addr, debug } => (addr, debug),
+        let (addr, debug, credentials) = match self {
+            Tip::Get {
+                addr,
+                debug,
+                credentials,
+            } => (addr, debug, credentials),
         };
         let url = addr.with_segments(&["v0", "tip"]).unwrap().into_url();
         let builder = reqwest::Client::new().get(url);
-        let response = RestApiSender::new(builder, &debug).send().unwrap();
+        let response = RestApiSender::new(builder, &debug)
+            .with_credentials(&credentials)
+            .send()
+            .unwrap();
         response.response().error_for_status_ref().unwrap();
         let tip = response.body().text();
         println!("{}", tip.as_ref());
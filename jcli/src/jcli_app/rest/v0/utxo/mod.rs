@@ -1,6 +1,11 @@
-use jcli_app::utils::{DebugFlag, HostAddr, OutputFormat, RestApiSender};
+use jcli_app::utils::{
+    DebugFlag, HostAddr, LedgerAt, OutputFormat, RestApiSender, RestCredentials,
+};
 use structopt::StructOpt;
 
+/// name of the response header holding the cursor to the next page of UTxOs
+const NEXT_CURSOR_HEADER: &str = "Next-Cursor";
+
 #[derive(StructOpt)]
 #[structopt(rename_all = "kebab-case")]
 pub enum Utxo {
@@ -11,23 +16,91 @@ pub enum Utxo {
         #[structopt(flatten)]
         debug: DebugFlag,
         #[structopt(flatten)]
+        credentials: RestCredentials,
+        #[structopt(flatten)]
         output_format: OutputFormat,
+        #[structopt(flatten)]
+        filter: UtxoFilter,
+        #[structopt(flatten)]
+        at: LedgerAt,
     },
 }
 
+#[derive(StructOpt)]
+pub struct UtxoFilter {
+    /// only get the UTXOs of this address
+    #[structopt(long = "address")]
+    address: Option<String>,
+    /// only get the UTXOs created by this transaction
+    #[structopt(long = "transaction-id")]
+    transaction_id: Option<String>,
+    /// only get the UTXOs with at least this value
+    #[structopt(long = "min-value")]
+    min_value: Option<u64>,
+    /// number of UTXOs to request at once, the node caps it to 1000. All
+    /// the UTXOs are requested at once if not set
+    #[structopt(long = "page-size")]
+    page_size: Option<usize>,
+}
+
+impl UtxoFilter {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(address) = &self.address {
+            query.push(("address", address.clone()));
+        }
+        if let Some(transaction_id) = &self.transaction_id {
+            query.push(("transaction_id", transaction_id.clone()));
+        }
+        if let Some(min_value) = self.min_value {
+            query.push(("min_value", min_value.to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            query.push(("limit", page_size.to_string()));
+        }
+        query
+    }
+}
+
 impl Utxo {
     pub fn exec(self) {
         let Utxo::Get {
             addr,
             debug,
+            credentials,
             output_format,
+            filter,
+            at,
         } = self;
         let url = addr.with_segments(&["v0", "utxo"]).unwrap().into_url();
-        let builder = reqwest::Client::new().get(url);
-        let response = RestApiSender::new(builder, &debug).send().unwrap();
-        response.response().error_for_status_ref().unwrap();
-        let status = response.body().json_value().unwrap();
-        let formatted = output_format.format_json(status).unwrap();
+        let mut utxos = Vec::new();
+        let mut cursor = None;
+        loop {
+            let mut query = filter.query();
+            query.extend(at.query());
+            if let Some(cursor) = cursor {
+                query.push(("after", cursor));
+            }
+            let builder = reqwest::Client::new().get(url.clone()).query(&query);
+            let response = RestApiSender::new(builder, &debug)
+                .with_credentials(&credentials)
+                .send()
+                .unwrap();
+            response.response().error_for_status_ref().unwrap();
+            let page: Vec<serde_json::Value> = response.body().json().unwrap();
+            utxos.extend(page);
+            cursor = response
+                .response()
+                .headers()
+                .get(NEXT_CURSOR_HEADER)
+                .map(|cursor| cursor.to_str().unwrap().to_owned());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        let formatted = output_format
+            .format_json(serde_json::Value::Array(utxos))
+            .unwrap();
         println!("{}", formatted);
     }
 }
@@ -13,6 +13,11 @@ pub struct AddWitness {
     pub common: common::CommonTransaction,
 
     pub witness: PathBuf,
+
+    /// the index of the input the witness is for. If omitted, the witness
+    /// is added to the first input still missing a witness
+    #[structopt(long = "input-index")]
+    pub input_index: Option<usize>,
 }
 
 impl AddWitness {
@@ -21,7 +26,10 @@ impl AddWitness {
 
         let witness = self.witness()?;
 
-        transaction.add_witness(witness)?;
+        match self.input_index {
+            Some(index) => transaction.add_witness_at(index, witness)?,
+            None => transaction.add_witness(witness)?,
+        }
 
         self.common.store(&transaction)?;
         Ok(())
@@ -0,0 +1,28 @@
+use jcli_app::transaction::{common, staging::Staging, Error};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct MergeWitnesses {
+    #[structopt(flatten)]
+    pub common: common::CommonTransaction,
+
+    /// the staging files of the same finalized transaction, with the
+    /// witnesses added by the other signers
+    #[structopt(name = "OTHER_STAGING", parse(from_os_str), raw(required = "true"))]
+    pub others: Vec<PathBuf>,
+}
+
+impl MergeWitnesses {
+    pub fn exec(self) -> Result<(), Error> {
+        let mut transaction = self.common.load()?;
+
+        for other in self.others.iter() {
+            let other = Staging::load(&Some(other))?;
+            transaction.merge_witnesses(&other)?;
+        }
+
+        self.common.store(&transaction)
+    }
+}
@@ -0,0 +1,34 @@
+use chain_impl_mockchain::transaction::InputEnum;
+use jcli_app::transaction::{common, Error};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct MissingWitnesses {
+    #[structopt(flatten)]
+    pub common: common::CommonTransaction,
+}
+
+impl MissingWitnesses {
+    pub fn exec(self) -> Result<(), Error> {
+        let transaction = self.common.load()?;
+        let inputs = transaction.inputs();
+
+        for index in transaction.missing_witnesses() {
+            match inputs[index].to_enum() {
+                InputEnum::UtxoInput(utxo_ptr) => println!(
+                    "{}: {}:{} {}",
+                    index, utxo_ptr.transaction_id, utxo_ptr.output_index, utxo_ptr.value.0
+                ),
+                InputEnum::AccountInput(account, value) => {
+                    let account: chain_crypto::PublicKey<_> = account
+                        .to_single_account()
+                        .ok_or(Error::InfoExpectedSingleAccount)?
+                        .into();
+                    println!("{}: {} {}", index, account, value.0)
+                }
+            }
+        }
+        Ok(())
+    }
+}
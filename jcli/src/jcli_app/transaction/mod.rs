@@ -6,6 +6,8 @@ mod add_witness;
 mod common;
 mod finalize;
 mod info;
+mod merge_witnesses;
+mod missing_witnesses;
 mod mk_witness;
 mod new;
 mod seal;
@@ -35,6 +37,11 @@ pub enum Transaction {
     AddOutput(add_output::AddOutput),
     /// add output to the finalized transaction
     AddWitness(add_witness::AddWitness),
+    /// merge the witnesses of other copies of the same finalized
+    /// transaction, signed offline by other parties
+    MergeWitnesses(merge_witnesses::MergeWitnesses),
+    /// list the inputs still missing a witness
+    MissingWitnesses(missing_witnesses::MissingWitnesses),
     /// set a certificate to the Transaction. If there is already
     /// an extra certificate in the transaction it will be replaced
     /// with the new one.
@@ -61,6 +68,10 @@ custom_error! { pub Error
         = @{{ let _ = source; format_args!("could not open staging transaction file '{}'", path.display()) }},
     StagingFileReadFailed { source: bincode::Error, path: PathBuf }
         = @{{ let _ = source; format_args!("could not read staging transaction file '{}'", path.display()) }},
+    StagingFileLayoutUnknown { source: bincode::Error, path: PathBuf }
+        = @{{ let _ = source; format_args!("'{}' is not a staging transaction file of this jcli or of an older one", path.display()) }},
+    StagingFileVersionUnsupported { version: u32, supported: u32, path: PathBuf }
+        = @{{ format_args!("staging transaction file '{}' has the layout version {}, this jcli reads the version {}", path.display(), version, supported) }},
     StagingFileWriteFailed { source: bincode::Error, path: PathBuf }
         = @{{ let _ = source; format_args!("could not write staging transaction file '{}'", path.display()) }},
     SecretFileFailed { source: key_parser::Error }
@@ -98,6 +109,14 @@ custom_error! { pub Error
 
     TooManyWitnessesToAddWitness { actual: usize, max: usize }
         = "too many witnesses in transaction to add another: {actual}, maximum is {max}",
+    WitnessIndexInvalid { index: usize, max: usize }
+        = "invalid input index {index} for the witness, the transaction has {max} input(s)",
+    WitnessAlreadySet { index: usize }
+        = "input {index} already has a different witness",
+    TxKindToMergeWitnessesInvalid { kind: StagingKind }
+        = "merging witnesses from {kind} transaction is not valid",
+    MergedTxMismatch { expected: String, actual: String }
+        = "cannot merge witnesses of transaction {actual} into transaction {expected}",
     WitnessCountToSealInvalid { actual: usize, expected: usize }
         = "invalid number of witnesses in transaction to seal: {actual}, should be {expected}",
     AccountAddressSingle = "invalid input account, this is a UTxO address",
@@ -135,6 +154,8 @@ impl Transaction {
             Transaction::AddAccount(add_account) => add_account.exec(),
             Transaction::AddOutput(add_output) => add_output.exec(),
             Transaction::AddWitness(add_witness) => add_witness.exec(),
+            Transaction::MergeWitnesses(merge_witnesses) => merge_witnesses.exec(),
+            Transaction::MissingWitnesses(missing_witnesses) => missing_witnesses.exec(),
             Transaction::AddCertificate(add_certificate) => add_certificate.exec(),
             Transaction::Finalize(finalize) => finalize.exec(),
             Transaction::Seal(seal) => seal.exec(),
@@ -12,10 +12,16 @@ use jcli_app::utils::error::CustomErrorFiller;
 use jcli_app::utils::io;
 use jormungandr_lib::interfaces;
 use serde::{Deserialize, Serialize};
+use std::io::Read as _;
 use std::path::Path;
 
 const INPUT_PTR_SIZE: usize = 32;
 
+/// the staging files start with the magic and the version of their layout,
+/// the files written by an older jcli have neither
+const STAGING_MAGIC: &[u8; 8] = b"JCLISTAG";
+const STAGING_VERSION: u32 = 1;
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
 pub enum StagingKind {
     Balancing,
@@ -32,6 +38,19 @@ struct Input {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Staging {
+    kind: StagingKind,
+    inputs: Vec<Input>,
+    outputs: Vec<interfaces::TransactionOutput>,
+    /// one slot per input, filled as the witnesses are added so the
+    /// witnesses of different signers can be merged
+    witnesses: Vec<Option<interfaces::TransactionWitness>>,
+    extra: Option<interfaces::Certificate>,
+}
+
+/// the unversioned layout of the older jcli, the witnesses being added in
+/// the order of the inputs
+#[derive(Deserialize)]
+struct LegacyStaging {
     kind: StagingKind,
     inputs: Vec<Input>,
     outputs: Vec<interfaces::TransactionOutput>,
@@ -39,6 +58,26 @@ pub struct Staging {
     extra: Option<interfaces::Certificate>,
 }
 
+impl From<LegacyStaging> for Staging {
+    fn from(legacy: LegacyStaging) -> Self {
+        let witnesses = match legacy.kind {
+            StagingKind::Balancing => Vec::new(),
+            _ => {
+                let mut witnesses = legacy.witnesses.into_iter().map(Some).collect::<Vec<_>>();
+                witnesses.resize(legacy.inputs.len(), None);
+                witnesses
+            }
+        };
+        Staging {
+            kind: legacy.kind,
+            inputs: legacy.inputs,
+            outputs: legacy.outputs,
+            witnesses,
+            extra: legacy.extra,
+        }
+    }
+}
+
 impl std::fmt::Display for StagingKind {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -60,14 +99,46 @@ impl Staging {
         }
     }
 
+    /// load a staging file, the unversioned files of the older jcli being
+    /// converted to the current layout
     pub fn load<P: AsRef<Path>>(path: &Option<P>) -> Result<Self, Error> {
-        let file = io::open_file_read(path).map_err(|source| Error::StagingFileOpenFailed {
+        let mut file = io::open_file_read(path).map_err(|source| Error::StagingFileOpenFailed {
             source,
             path: io::path_to_path_buf(path),
         })?;
-        bincode::deserialize_from(file).map_err(|source| Error::StagingFileReadFailed {
-            source,
-            path: io::path_to_path_buf(path),
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)
+            .map_err(|source| Error::StagingFileReadFailed {
+                source: source.into(),
+                path: io::path_to_path_buf(path),
+            })?;
+
+        if !bytes.starts_with(STAGING_MAGIC) {
+            return bincode::deserialize::<LegacyStaging>(&bytes)
+                .map(Staging::from)
+                .map_err(|source| Error::StagingFileLayoutUnknown {
+                    source,
+                    path: io::path_to_path_buf(path),
+                });
+        }
+        let bytes = &bytes[STAGING_MAGIC.len()..];
+        let version: u32 =
+            bincode::deserialize(bytes).map_err(|source| Error::StagingFileReadFailed {
+                source,
+                path: io::path_to_path_buf(path),
+            })?;
+        if version != STAGING_VERSION {
+            return Err(Error::StagingFileVersionUnsupported {
+                version,
+                supported: STAGING_VERSION,
+                path: io::path_to_path_buf(path),
+            });
+        }
+        bincode::deserialize(&bytes[std::mem::size_of::<u32>()..]).map_err(|source| {
+            Error::StagingFileReadFailed {
+                source,
+                path: io::path_to_path_buf(path),
+            }
         })
     }
 
@@ -76,10 +147,17 @@ impl Staging {
             source,
             path: io::path_to_path_buf(path),
         })?;
-        bincode::serialize_into(file, self).map_err(|source| Error::StagingFileWriteFailed {
-            source,
-            path: io::path_to_path_buf(path),
-        })
+        self.write_versioned(file)
+            .map_err(|source| Error::StagingFileWriteFailed {
+                source,
+                path: io::path_to_path_buf(path),
+            })
+    }
+
+    fn write_versioned<W: std::io::Write>(&self, mut writer: W) -> bincode::Result<()> {
+        writer.write_all(STAGING_MAGIC)?;
+        bincode::serialize_into(&mut writer, &STAGING_VERSION)?;
+        bincode::serialize_into(&mut writer, self)
     }
 
     pub fn add_input(&mut self, input: chain::transaction::Input) -> Result<(), Error> {
@@ -102,19 +180,68 @@ impl Staging {
         Ok(self.outputs.push(output.into()))
     }
 
+    /// add the witness to the first input still missing a witness
     pub fn add_witness(&mut self, witness: chain::transaction::Witness) -> Result<(), Error> {
+        match self.missing_witnesses().first() {
+            Some(&index) => self.add_witness_at(index, witness),
+            None => Err(Error::TooManyWitnessesToAddWitness {
+                actual: self.witness_count(),
+                max: self.inputs.len(),
+            }),
+        }
+    }
+
+    /// add the witness of the input at the given index. Adding the same
+    /// witness twice is allowed, replacing a witness is not.
+    pub fn add_witness_at(
+        &mut self,
+        index: usize,
+        witness: chain::transaction::Witness,
+    ) -> Result<(), Error> {
         if self.kind != StagingKind::Finalizing {
             return Err(Error::TxKindToAddWitnessInvalid { kind: self.kind });
         }
 
-        if self.inputs.len() <= self.witnesses.len() {
-            return Err(Error::TooManyWitnessesToAddWitness {
-                actual: self.witnesses.len(),
+        let witness = interfaces::TransactionWitness::from(witness);
+        match self.witnesses.get_mut(index) {
+            None => Err(Error::WitnessIndexInvalid {
+                index,
                 max: self.inputs.len(),
+            }),
+            Some(Some(existing)) if existing != &witness => Err(Error::WitnessAlreadySet { index }),
+            Some(slot) => Ok(*slot = Some(witness)),
+        }
+    }
+
+    /// merge the witnesses of another copy of the same transaction, signed
+    /// by other parties
+    pub fn merge_witnesses(&mut self, other: &Staging) -> Result<(), Error> {
+        if other.kind != StagingKind::Finalizing {
+            return Err(Error::TxKindToMergeWitnessesInvalid { kind: other.kind });
+        }
+        if self.id() != other.id() {
+            return Err(Error::MergedTxMismatch {
+                expected: self.id().to_string(),
+                actual: other.id().to_string(),
             });
         }
 
-        Ok(self.witnesses.push(witness.into()))
+        for (index, witness) in other.witnesses.iter().enumerate() {
+            if let Some(witness) = witness {
+                self.add_witness_at(index, witness.clone().into())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the indices of the inputs without witness
+    pub fn missing_witnesses(&self) -> Vec<usize> {
+        self.witnesses
+            .iter()
+            .enumerate()
+            .filter(|(_, witness)| witness.is_none())
+            .map(|(index, _)| index)
+            .collect()
     }
 
     pub fn set_extra(&mut self, extra: chain::certificate::Certificate) -> Result<(), Error> {
@@ -125,7 +252,10 @@ impl Staging {
     }
 
     pub fn witness_count(&self) -> usize {
-        self.witnesses.len()
+        self.witnesses
+            .iter()
+            .filter(|witness| witness.is_some())
+            .count()
     }
 
     pub fn staging_kind_name(&self) -> String {
@@ -182,6 +312,7 @@ impl Staging {
         };
 
         self.kind = StagingKind::Finalizing;
+        self.witnesses = vec![None; self.inputs.len()];
 
         Ok(balance)
     }
@@ -191,9 +322,9 @@ impl Staging {
             return Err(Error::TxKindToSealInvalid { kind: self.kind });
         }
 
-        if self.inputs.len() != self.witnesses.len() {
+        if self.witness_count() != self.inputs.len() {
             return Err(Error::WitnessCountToSealInvalid {
-                actual: self.witnesses.len(),
+                actual: self.witness_count(),
                 expected: self.inputs.len(),
             });
         }
@@ -298,6 +429,10 @@ impl Staging {
         };
 
         for (index, witness) in self.witnesses.iter().enumerate() {
+            let witness = match witness {
+                Some(witness) => witness,
+                None => continue,
+            };
             finalizer
                 .set_witness(index, witness.clone().into())
                 .map_err(|source| Error::AddingWitnessToFinalizedTxFailed {
@@ -329,10 +464,106 @@ impl Staging {
 mod tests {
 
     use super::*;
+    use chain_crypto::SecretKey;
     use chain_impl_mockchain as chain;
-    use chain_impl_mockchain::key::Hash;
+    use chain_impl_mockchain::key::{EitherEd25519SecretKey, Hash};
+    use chain_impl_mockchain::transaction::Witness;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
     use std::str::FromStr;
 
+    fn finalizing_staging(inputs: u8) -> Staging {
+        let mut staging = Staging::new();
+        for i in 0..inputs {
+            staging
+                .add_input(chain::transaction::Input {
+                    input_ptr: [i; INPUT_PTR_SIZE],
+                    index_or_account: 0,
+                    value: Value(100),
+                })
+                .unwrap();
+        }
+        staging.kind = StagingKind::Finalizing;
+        staging.witnesses = vec![None; inputs as usize];
+        staging
+    }
+
+    fn witness(seed: u8, staging: &Staging) -> Witness {
+        let block0_hash =
+            Hash::from_str("c355a02d3b5337ad0e5f5940582675229f25bc03e7feebc3aa929738e1fec35e")
+                .unwrap();
+        let secret_key =
+            EitherEd25519SecretKey::Normal(SecretKey::generate(ChaChaRng::from_seed([seed; 32])));
+        Witness::new_utxo(&block0_hash, &staging.id(), &secret_key)
+    }
+
+    #[test]
+    pub fn test_merge_witnesses_of_other_signers() {
+        let mut staging = finalizing_staging(2);
+        let mut other = finalizing_staging(2);
+        staging.add_witness_at(0, witness(1, &staging)).unwrap();
+        other.add_witness_at(1, witness(2, &other)).unwrap();
+        assert_eq!(staging.missing_witnesses(), vec![1]);
+
+        staging.merge_witnesses(&other).unwrap();
+
+        assert!(staging.missing_witnesses().is_empty());
+        assert!(staging.seal().is_ok());
+    }
+
+    #[test]
+    pub fn test_cannot_merge_conflicting_witnesses() {
+        let mut staging = finalizing_staging(2);
+        let mut other = finalizing_staging(2);
+        staging.add_witness_at(0, witness(1, &staging)).unwrap();
+        other.add_witness_at(0, witness(2, &other)).unwrap();
+
+        assert!(staging.merge_witnesses(&other).is_err());
+    }
+
+    #[test]
+    pub fn test_cannot_merge_witnesses_of_other_transaction() {
+        let mut staging = finalizing_staging(2);
+        let mut other = finalizing_staging(3);
+        other.add_witness_at(2, witness(2, &other)).unwrap();
+
+        assert!(staging.merge_witnesses(&other).is_err());
+    }
+
+    #[test]
+    pub fn test_staging_file_is_read_back() {
+        let tempfile = mktemp::Temp::new_file().unwrap();
+        let path = Some(tempfile.to_path_buf());
+        let mut staging = finalizing_staging(2);
+        staging.add_witness_at(1, witness(1, &staging)).unwrap();
+
+        staging.store(&path).unwrap();
+        let loaded = Staging::load(&path).unwrap();
+
+        assert_eq!(loaded.id(), staging.id());
+        assert_eq!(loaded.missing_witnesses(), vec![0]);
+    }
+
+    #[test]
+    pub fn test_staging_file_of_older_jcli_is_converted() {
+        let tempfile = mktemp::Temp::new_file().unwrap();
+        let path = Some(tempfile.to_path_buf());
+        let staging = finalizing_staging(2);
+        let legacy = (
+            StagingKind::Finalizing,
+            &staging.inputs,
+            &staging.outputs,
+            vec![interfaces::TransactionWitness::from(witness(1, &staging))],
+            None::<interfaces::Certificate>,
+        );
+        std::fs::write(tempfile.as_ref(), bincode::serialize(&legacy).unwrap()).unwrap();
+
+        let loaded = Staging::load(&path).unwrap();
+
+        assert_eq!(loaded.id(), staging.id());
+        assert_eq!(loaded.missing_witnesses(), vec![1]);
+    }
+
     #[test]
     pub fn test_initial_stage_is_balancing() {
         let staging = Staging::new();
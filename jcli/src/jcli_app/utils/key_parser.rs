@@ -1,6 +1,8 @@
-use super::io;
+use super::{error::CustomErrorFiller, io};
 use chain_crypto::bech32::{self, Bech32};
-use chain_crypto::{AsymmetricKey, AsymmetricPublicKey, PublicKey, SecretKey};
+use chain_crypto::{
+    AsymmetricKey, AsymmetricPublicKey, Ed25519, Ed25519Bip32, PublicKey, PublicKeyError, SecretKey,
+};
 use chain_impl_mockchain::key::EitherEd25519SecretKey;
 use std::path::{Path, PathBuf};
 
@@ -11,6 +13,9 @@ custom_error! { pub Error
         = @{{ format_args!("could not decode secret file '{}': {}", path.display(), source) }},
     SecretKeyMalformed { source: bech32::Error }
         = @{{ format_args!("could not decode secretkey: {}", source) }},
+    PublicKeyMalformed { source: bech32::Error, filler: CustomErrorFiller }
+        = @{{ let _ = filler; format_args!("could not decode public key: {}", source) }},
+    PublicKeyInvalid { source: PublicKeyError } = "invalid public key",
 }
 
 pub fn parse_pub_key<A: AsymmetricPublicKey>(
@@ -19,6 +24,18 @@ pub fn parse_pub_key<A: AsymmetricPublicKey>(
     Bech32::try_from_bech32_str(bech32_str)
 }
 
+/// parse an ed25519 public key, the public key of an ed25519bip32 key is
+/// accepted too (without its chain code) so derived keys can be used directly
+pub fn parse_ed25519_pub_key(bech32_str: &str) -> Result<PublicKey<Ed25519>, Error> {
+    match parse_pub_key::<Ed25519Bip32>(bech32_str) {
+        Ok(xpub) => Ok(PublicKey::from_binary(&xpub.as_ref()[..32])?),
+        Err(_) => parse_pub_key(bech32_str).map_err(|source| Error::PublicKeyMalformed {
+            source,
+            filler: CustomErrorFiller,
+        }),
+    }
+}
+
 pub fn read_secret_key_from_file<A: AsymmetricKey, P: AsRef<Path>>(
     path: &Option<P>,
 ) -> Result<SecretKey<A>, Error> {
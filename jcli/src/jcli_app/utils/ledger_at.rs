@@ -0,0 +1,27 @@
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct LedgerAt {
+    /// query the ledger state after the given block instead of the tip
+    #[structopt(long = "at", raw(conflicts_with = "\"at_date\""))]
+    at: Option<String>,
+
+    /// query the ledger state at the given date, `epoch.slot`, instead of
+    /// the tip. This is the state after the last block of the tip's branch
+    /// not after this date
+    #[structopt(long = "at-date")]
+    at_date: Option<String>,
+}
+
+impl LedgerAt {
+    pub fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(at) = &self.at {
+            query.push(("at", at.clone()));
+        }
+        if let Some(at_date) = &self.at_date {
+            query.push(("at_date", at_date.clone()));
+        }
+        query
+    }
+}
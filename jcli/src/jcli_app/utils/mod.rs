@@ -1,7 +1,9 @@
 mod account_id;
 mod debug_flag;
 mod host_addr;
+mod ledger_at;
 mod rest_api;
+mod rest_credentials;
 
 pub mod error;
 pub mod io;
@@ -11,8 +13,10 @@ pub mod output_format;
 pub use self::account_id::AccountId;
 pub use self::debug_flag::DebugFlag;
 pub use self::host_addr::HostAddr;
+pub use self::ledger_at::LedgerAt;
 pub use self::output_format::OutputFormat;
 pub use self::rest_api::{RestApiResponse, RestApiResponseBody, RestApiSender};
+pub use self::rest_credentials::RestCredentials;
 use bech32::Bech32;
 use structopt::StructOpt;
 
@@ -1,12 +1,13 @@
 use hex;
-use jcli_app::utils::DebugFlag;
-use reqwest::{Error, RequestBuilder, Response};
+use jcli_app::utils::{rest_credentials, DebugFlag, RestCredentials};
+use reqwest::{RequestBuilder, Response};
 use std::{fmt, io::Write};
 
 pub struct RestApiSender<'a> {
     builder: RequestBuilder,
     request_body_debug: Option<String>,
     debug_flag: &'a DebugFlag,
+    credentials: Option<&'a RestCredentials>,
 }
 
 pub struct RestApiResponse {
@@ -25,9 +26,16 @@ impl<'a> RestApiSender<'a> {
             builder,
             request_body_debug: None,
             debug_flag,
+            credentials: None,
         }
     }
 
+    pub fn with_credentials(mut self, credentials: &'a RestCredentials) -> Self {
+        self.builder = credentials.apply(self.builder);
+        self.credentials = Some(credentials);
+        self
+    }
+
     pub fn with_binary_body(mut self, body: Vec<u8>) -> Self {
         if self.debug_flag.debug_writer().is_some() {
             self.request_body_debug = Some(hex::encode(&body));
@@ -42,6 +50,14 @@ impl<'a> RestApiSender<'a> {
         self
     }
 
+    pub fn with_json_body<T: serde::Serialize>(mut self, body: &T) -> Self {
+        if self.debug_flag.debug_writer().is_some() {
+            self.request_body_debug = serde_json::to_string_pretty(body).ok();
+        }
+        self.builder = self.builder.json(body);
+        self
+    }
+
     pub fn send(self) -> Result<RestApiResponse, Error> {
         let request = self.builder.build()?;
         if let Some(mut writer) = self.debug_flag.debug_writer() {
@@ -50,9 +66,11 @@ impl<'a> RestApiSender<'a> {
                 writeln!(writer, "Request body:\n{}", body).unwrap();
             }
         }
-        let response = reqwest::Client::new()
-            .execute(request)
-            .and_then(RestApiResponse::new)?;
+        let client = match self.credentials {
+            Some(credentials) => credentials.client()?,
+            None => reqwest::Client::new(),
+        };
+        let response = client.execute(request).and_then(RestApiResponse::new)?;
         if let Some(mut writer) = self.debug_flag.debug_writer() {
             writeln!(writer, "{:#?}", response.response()).unwrap();
             if !response.body().is_empty() {
@@ -64,7 +82,7 @@ impl<'a> RestApiSender<'a> {
 }
 
 impl RestApiResponse {
-    pub fn new(mut response: Response) -> Result<Self, Error> {
+    pub fn new(mut response: Response) -> Result<Self, reqwest::Error> {
         Ok(RestApiResponse {
             body: RestApiResponseBody::new(&mut response)?,
             response,
@@ -81,7 +99,7 @@ impl RestApiResponse {
 }
 
 impl RestApiResponseBody {
-    fn new(response: &mut Response) -> Result<Self, Error> {
+    fn new(response: &mut Response) -> Result<Self, reqwest::Error> {
         match is_body_binary(response) {
             true => {
                 let mut data = Vec::with_capacity(response.content_length().unwrap_or(0) as usize);
@@ -142,3 +160,8 @@ fn is_body_binary(response: &Response) -> bool {
         .and_then(|header_str| header_str.parse::<mime::Mime>().ok())
         == Some(mime::APPLICATION_OCTET_STREAM)
 }
+
+custom_error! { pub Error
+    RequestFailed { source: reqwest::Error } = "request failed: {source}",
+    CredentialsInvalid { source: rest_credentials::Error } = "invalid credentials: {source}",
+}
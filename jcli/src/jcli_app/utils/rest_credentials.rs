@@ -0,0 +1,60 @@
+use reqwest::{Client, Identity, RequestBuilder};
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt)]
+pub struct RestCredentials {
+    /// bearer token to authenticate with on the node REST API
+    #[structopt(long = "token")]
+    token: Option<String>,
+
+    /// PKCS#12 file with the client certificate and its private key, sent
+    /// to nodes requiring client certificates
+    #[structopt(long = "client-pkcs12", parse(from_os_str))]
+    client_pkcs12: Option<PathBuf>,
+
+    /// password of the client PKCS#12 file
+    #[structopt(long = "client-pkcs12-password", default_value = "")]
+    client_pkcs12_password: String,
+}
+
+impl RestCredentials {
+    pub fn apply(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => {
+                builder.header(reqwest::header::AUTHORIZATION, format!("Bearer {}", token))
+            }
+            None => builder,
+        }
+    }
+
+    pub fn client(&self) -> Result<Client, Error> {
+        let path = match &self.client_pkcs12 {
+            Some(path) => path,
+            None => return Ok(Client::new()),
+        };
+        let der = fs::read(path).map_err(|source| Error::ClientPkcs12LoadFailed {
+            source,
+            path: path.clone(),
+        })?;
+        let identity =
+            Identity::from_pkcs12_der(&der, &self.client_pkcs12_password).map_err(|source| {
+                Error::ClientPkcs12Invalid {
+                    source,
+                    path: path.clone(),
+                }
+            })?;
+        Client::builder()
+            .identity(identity)
+            .build()
+            .map_err(|source| Error::ClientBuildFailed { source })
+    }
+}
+
+custom_error! { pub Error
+    ClientPkcs12LoadFailed { source: std::io::Error, path: PathBuf }
+        = @{{ let _ = source; format_args!("could not read client PKCS#12 file '{}'", path.display()) }},
+    ClientPkcs12Invalid { source: reqwest::Error, path: PathBuf }
+        = @{{ let _ = source; format_args!("invalid client PKCS#12 file '{}'", path.display()) }},
+    ClientBuildFailed { source: reqwest::Error } = "could not set up the HTTP client: {source}",
+}
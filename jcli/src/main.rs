@@ -1,8 +1,11 @@
 extern crate bech32;
+extern crate bip39;
 extern crate chain_addr;
 extern crate chain_core;
 extern crate chain_crypto;
 extern crate chain_impl_mockchain;
+extern crate cryptoxide;
+extern crate ed25519_bip32;
 extern crate gtmpl;
 extern crate hex;
 extern crate jormungandr_lib;
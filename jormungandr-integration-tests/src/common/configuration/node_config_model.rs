@@ -3,16 +3,58 @@
 extern crate serde_derive;
 use self::serde_derive::{Deserialize, Serialize};
 use super::file_utils;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Logger {
     pub verbosity: i32,
     pub format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Rest {
     pub listen: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<RestAuthentication>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<RestLimits>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RestLimits {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute_per_ip: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute_per_token: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_body_size: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Mempool {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragment_ttl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_ttl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub garbage_collection_interval: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistence_interval: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fragments_per_block: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestAuthentication {
+    pub tokens: Vec<RestToken>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestToken {
+    pub token: String,
+    pub scopes: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,12 +79,16 @@ pub struct TopicsOfInterests {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NodeConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub storage: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logger: Option<Logger>,
     pub rest: Option<Rest>,
     pub peer_2_peer: Peer2Peer,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mempool: Option<Mempool>,
 }
 
 impl NodeConfig {
@@ -58,13 +104,17 @@ impl NodeConfig {
         let storage_file = file_utils::get_path_in_temp("storage");
 
         NodeConfig {
+            mode: None,
             storage: Some(String::from(storage_file.as_os_str().to_str().unwrap())),
             logger: Some(Logger {
                 verbosity: 1,
                 format: String::from("json"),
+                tasks: None,
             }),
             rest: Some(Rest {
                 listen: format!("127.0.0.1:{}", rest_port.to_string()),
+                authentication: None,
+                limits: None,
             }),
             peer_2_peer: Peer2Peer {
                 trusted_peers: None,
@@ -74,6 +124,7 @@ impl NodeConfig {
                     blocks: String::from("high"),
                 },
             },
+            mempool: None,
         }
     }
 
@@ -86,6 +137,21 @@ impl NodeConfig {
         );
     }
 
+    /// token granting read access to the REST API, if it requires authentication
+    pub fn get_rest_read_token(&self) -> Option<String> {
+        let authentication = self.rest.as_ref()?.authentication.as_ref()?;
+        authentication
+            .tokens
+            .iter()
+            .find(|token| {
+                token
+                    .scopes
+                    .iter()
+                    .any(|scope| scope == "read-only" || scope == "admin")
+            })
+            .map(|token| token.token.clone())
+    }
+
     pub fn get_node_address(&self) -> String {
         let rest = self.rest.as_ref();
         let output = format!("http://{}/api", rest.unwrap().listen);
@@ -87,4 +87,22 @@ impl CertificateCommands {
             .arg(&output_file.as_os_str());
         command
     }
+
+    pub fn get_merge_signatures_command(
+        &self,
+        input_file: &PathBuf,
+        other_files: &[PathBuf],
+        output_file: &PathBuf,
+    ) -> Command {
+        let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+        command
+            .arg("certificate")
+            .arg("merge-signatures")
+            .arg("--input")
+            .arg(&input_file.as_os_str())
+            .arg("--output")
+            .arg(&output_file.as_os_str())
+            .args(other_files);
+        command
+    }
 }
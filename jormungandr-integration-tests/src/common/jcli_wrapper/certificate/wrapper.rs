@@ -87,6 +87,20 @@ impl JCLICertificateWrapper {
         certification
     }
 
+    pub fn assert_merge_signatures(
+        &self,
+        input_file: &PathBuf,
+        other_files: &[PathBuf],
+        output_file: &PathBuf,
+    ) {
+        println!("Running merge certificate signatures...");
+        let output = process_utils::run_process_and_get_output(
+            self.commands
+                .get_merge_signatures_command(&input_file, &other_files, &output_file),
+        );
+        process_assert::assert_process_exited_successfully(output);
+    }
+
     pub fn assert_new_signed_stake_pool_cert(
         &self,
         pool_kes_pk: &str,
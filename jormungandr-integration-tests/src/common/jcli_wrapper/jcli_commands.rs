@@ -45,6 +45,77 @@ pub fn get_genesis_hash_command(path_to_output_block: &PathBuf) -> Command {
     command
 }
 
+/// Get genesis validate command.
+///
+/// # Arguments
+///
+/// * `genesis_file_path` - Path to genesis yaml file or to the block 0
+///
+pub fn get_genesis_validate_command(genesis_file_path: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("genesis")
+        .arg("validate")
+        .arg("--input")
+        .arg(genesis_file_path.as_os_str());
+    command
+}
+
+/// Get genesis diff command.
+///
+/// # Arguments
+///
+/// * `from_file_path` - Path to the reference genesis yaml file or block 0
+/// * `to_file_path` - Path to the compared genesis yaml file or block 0
+///
+pub fn get_genesis_diff_command(from_file_path: &PathBuf, to_file_path: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("genesis")
+        .arg("diff")
+        .arg(from_file_path.as_os_str())
+        .arg(to_file_path.as_os_str());
+    command
+}
+
+/// Get genesis build command.
+///
+/// # Arguments
+///
+/// * `parameters_file_path` - Path to the blockchain parameters yaml file
+/// * `allocations_file_path` - Path to the initial funds csv file
+/// * `leaders_dir_path` - Path to the directory with the consensus leaders keys
+/// * `stake_pools_dir_path` - Path to the directory with the stake pools keys
+/// * `output_genesis_path` - Path to the genesis yaml file to create
+/// * `output_block_path` - Path to the block 0 to create
+///
+pub fn get_genesis_build_command(
+    parameters_file_path: &PathBuf,
+    allocations_file_path: &PathBuf,
+    leaders_dir_path: &PathBuf,
+    stake_pools_dir_path: &PathBuf,
+    output_genesis_path: &PathBuf,
+    output_block_path: &PathBuf,
+) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("genesis")
+        .arg("build")
+        .arg("--parameters")
+        .arg(parameters_file_path.as_os_str())
+        .arg("--allocations")
+        .arg(allocations_file_path.as_os_str())
+        .arg("--leaders")
+        .arg(leaders_dir_path.as_os_str())
+        .arg("--stake-pools")
+        .arg(stake_pools_dir_path.as_os_str())
+        .arg("--output-genesis")
+        .arg(output_genesis_path.as_os_str())
+        .arg("--output-block")
+        .arg(output_block_path.as_os_str());
+    command
+}
+
 /// Get rest stat command.
 pub fn get_rest_stats_command(host: &str) -> Command {
     let mut command = Command::new(configuration::get_jcli_app().as_os_str());
@@ -59,6 +130,98 @@ pub fn get_rest_stats_command(host: &str) -> Command {
     command
 }
 
+/// Get rest node services status command.
+pub fn get_rest_node_services_command(host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("node")
+        .arg("services")
+        .arg("get")
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
+/// Get rest node effective configuration command.
+pub fn get_rest_stake_distribution_command(host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("stake-distribution")
+        .arg("get")
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
+pub fn get_rest_node_config_command(host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("node")
+        .arg("config")
+        .arg("get")
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
+/// Get rest node log levels command.
+pub fn get_rest_node_log_levels_command(host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("node")
+        .arg("log-levels")
+        .arg("get")
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
+/// Set rest node log levels command.
+pub fn get_rest_node_log_levels_set_command(
+    host: &str,
+    tasks: &[(&str, &str)],
+    reset_tasks: &[&str],
+) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("node")
+        .arg("log-levels")
+        .arg("set")
+        .arg("-h")
+        .arg(&host);
+    for (task, level) in tasks {
+        command.arg("--task").arg(format!("{}={}", task, level));
+    }
+    for task in reset_tasks {
+        command.arg("--reset-task").arg(task);
+    }
+    command
+}
+
+/// Get rest node shutdown command.
+pub fn get_rest_node_shutdown_command(host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("node")
+        .arg("shutdown")
+        .arg("post")
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
 /// Get rest stat command.
 pub fn get_rest_account_stats_command(address: &str, host: &str) -> Command {
     let mut command = Command::new(configuration::get_jcli_app().as_os_str());
@@ -73,6 +236,20 @@ pub fn get_rest_account_stats_command(address: &str, host: &str) -> Command {
     command
 }
 
+/// Get rest account get-many command.
+pub fn get_rest_account_get_many_command(addresses: &[&str], host: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("rest")
+        .arg("v0")
+        .arg("account")
+        .arg("get-many")
+        .args(addresses)
+        .arg("-h")
+        .arg(&host);
+    command
+}
+
 /// Get rest block tip command.
 pub fn get_rest_block_tip_command(host: &str) -> Command {
     let mut command = Command::new(configuration::get_jcli_app().as_os_str());
@@ -183,6 +360,38 @@ pub fn get_address_delegation_command(
     command
 }
 
+/// Get utxo get command with filters.
+pub fn get_rest_utxo_get_filtered_command(
+    host: &str,
+    address: Option<&str>,
+    min_value: Option<u64>,
+    page_size: Option<usize>,
+) -> Command {
+    let mut command = get_rest_utxo_get_command(&host);
+    if let Some(address) = address {
+        command.arg("--address").arg(&address);
+    }
+    if let Some(min_value) = min_value {
+        command.arg("--min-value").arg(min_value.to_string());
+    }
+    if let Some(page_size) = page_size {
+        command.arg("--page-size").arg(page_size.to_string());
+    }
+    command
+}
+
+/// Append the block ID of the ledger state to query to a jcli rest command
+pub fn with_ledger_at(mut command: Command, block_id: &str) -> Command {
+    command.arg("--at").arg(block_id);
+    command
+}
+
+/// Append the date of the ledger state to query to a jcli rest command
+pub fn with_ledger_at_date(mut command: Command, date: &str) -> Command {
+    command.arg("--at-date").arg(date);
+    command
+}
+
 /// Get post transaction command.
 pub fn get_post_transaction_command(transaction_hash: &str, host: &str) -> Command {
     let transaction_hash_file_path =
@@ -265,6 +474,79 @@ pub fn get_key_from_bytes_command(input_file: &PathBuf, key_type: &str) -> Comma
     command
 }
 
+/// Get key generate mnemonic command
+pub fn get_key_generate_mnemonic_command(words: usize, seed: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    command
+        .arg("key")
+        .arg("generate-mnemonic")
+        .arg("--words")
+        .arg(words.to_string())
+        .arg("--seed")
+        .arg(&seed);
+    command
+}
+
+/// Get key from mnemonic command
+pub fn get_key_from_mnemonic_command(mnemonic: &str, password: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    let mnemonic_file = file_utils::create_file_in_temp("mnemonic", &mnemonic);
+    command
+        .arg("key")
+        .arg("from-mnemonic")
+        .arg("--input")
+        .arg(&mnemonic_file)
+        .arg("--password")
+        .arg(&password);
+    command
+}
+
+/// Get key derive command
+pub fn get_key_derive_command(private_key: &str, path: &str) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    let secret_file_key = file_utils::create_file_in_temp("secret_file_key", &private_key);
+    command
+        .arg("key")
+        .arg("derive")
+        .arg("--path")
+        .arg(&path)
+        .arg("--input")
+        .arg(&secret_file_key);
+    command
+}
+
+/// Get key sign command
+pub fn get_key_sign_command(private_key: &str, input_file: &PathBuf, hex: bool) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    let secret_file_key = file_utils::create_file_in_temp("secret_file_key", &private_key);
+    command
+        .arg("key")
+        .arg("sign")
+        .arg("--secret-key")
+        .arg(&secret_file_key);
+    if hex {
+        command.arg("--hex");
+    }
+    command.arg(input_file.as_os_str());
+    command
+}
+
+/// Get key verify command
+pub fn get_key_verify_command(public_key: &str, signature: &str, input_file: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+    let public_file_key = file_utils::create_file_in_temp("public_file_key", &public_key);
+    let signature_file = file_utils::create_file_in_temp("signature", &signature);
+    command
+        .arg("key")
+        .arg("verify")
+        .arg("--public-key")
+        .arg(&public_file_key)
+        .arg("--signature")
+        .arg(&signature_file)
+        .arg(input_file.as_os_str());
+    command
+}
+
 pub fn get_rest_message_log_command(host: &str) -> Command {
     let mut command = Command::new(configuration::get_jcli_app().as_os_str());
     command
@@ -276,3 +558,9 @@ pub fn get_rest_message_log_command(host: &str) -> Command {
         .arg(&host);
     command
 }
+
+/// Append the bearer token to a jcli rest command
+pub fn with_rest_token(mut command: Command, token: &str) -> Command {
+    command.arg("--token").arg(token);
+    command
+}
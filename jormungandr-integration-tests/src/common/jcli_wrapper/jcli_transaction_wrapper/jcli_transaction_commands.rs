@@ -150,6 +150,49 @@ impl TransactionCommands {
         command
     }
 
+    pub fn get_add_witness_at_command(
+        &self,
+        witness_file: &PathBuf,
+        input_index: usize,
+        staging_file: &PathBuf,
+    ) -> Command {
+        let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+        command
+            .arg("transaction")
+            .arg("add-witness")
+            .arg(witness_file.as_os_str())
+            .arg("--input-index")
+            .arg(input_index.to_string())
+            .arg("--staging")
+            .arg(staging_file.as_os_str());
+        command
+    }
+
+    pub fn get_merge_witnesses_command(
+        &self,
+        staging_file: &PathBuf,
+        other_staging_files: &[PathBuf],
+    ) -> Command {
+        let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+        command
+            .arg("transaction")
+            .arg("merge-witnesses")
+            .arg("--staging")
+            .arg(staging_file.as_os_str())
+            .args(other_staging_files);
+        command
+    }
+
+    pub fn get_missing_witnesses_command(&self, staging_file: &PathBuf) -> Command {
+        let mut command = Command::new(configuration::get_jcli_app().as_os_str());
+        command
+            .arg("transaction")
+            .arg("missing-witnesses")
+            .arg("--staging")
+            .arg(staging_file.as_os_str());
+        command
+    }
+
     pub fn get_seal_command(&self, staging_file: &PathBuf) -> Command {
         let mut command = Command::new(configuration::get_jcli_app().as_os_str());
         command
@@ -339,6 +339,72 @@ impl JCLITransactionWrapper {
         self
     }
 
+    pub fn assert_add_witness_at<'a>(
+        &'a mut self,
+        witness: &Witness,
+        input_index: usize,
+    ) -> &'a mut JCLITransactionWrapper {
+        let output =
+            process_utils::run_process_and_get_output(self.commands.get_add_witness_at_command(
+                &witness.file,
+                input_index,
+                &self.staging_file_path,
+            ));
+        process_assert::assert_process_exited_successfully(output);
+        self
+    }
+
+    /// copy of the staging transaction, as given to another signer
+    pub fn copy_staging(&self) -> JCLITransactionWrapper {
+        let mut copy = JCLITransactionWrapper::from_genesis(&self.genesis_hash);
+        copy.generate_new_random_staging_file_path();
+        std::fs::copy(&self.staging_file_path, &copy.staging_file_path)
+            .expect("cannot copy staging file");
+        copy
+    }
+
+    pub fn assert_merge_witnesses<'a>(
+        &'a mut self,
+        others: &[&JCLITransactionWrapper],
+    ) -> &'a mut JCLITransactionWrapper {
+        let other_staging_files: Vec<PathBuf> = others
+            .iter()
+            .map(|other| other.staging_file_path.clone())
+            .collect();
+        let output = process_utils::run_process_and_get_output(
+            self.commands
+                .get_merge_witnesses_command(&self.staging_file_path, &other_staging_files),
+        );
+        process_assert::assert_process_exited_successfully(output);
+        self
+    }
+
+    pub fn assert_merge_witnesses_fails(
+        &self,
+        others: &[&JCLITransactionWrapper],
+        expected_msg: &str,
+    ) {
+        let other_staging_files: Vec<PathBuf> = others
+            .iter()
+            .map(|other| other.staging_file_path.clone())
+            .collect();
+        process_assert::assert_process_failed_and_contains_message(
+            self.commands
+                .get_merge_witnesses_command(&self.staging_file_path, &other_staging_files),
+            expected_msg,
+        );
+    }
+
+    pub fn assert_missing_witnesses(&self) -> Vec<String> {
+        let output = process_utils::run_process_and_get_output(
+            self.commands
+                .get_missing_witnesses_command(&self.staging_file_path),
+        );
+        let content = output.as_lossy_string();
+        process_assert::assert_process_exited_successfully(output);
+        content.lines().map(|line| line.to_owned()).collect()
+    }
+
     pub fn assert_seal<'a>(&'a mut self) -> &'a mut JCLITransactionWrapper {
         let output = process_utils::run_process_and_get_output(
             self.commands.get_seal_command(&self.staging_file_path),
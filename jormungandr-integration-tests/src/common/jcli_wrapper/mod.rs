@@ -13,6 +13,7 @@ use super::file_utils;
 use super::process_assert;
 use super::process_utils;
 use super::process_utils::output_extensions::ProcessOutput;
+use serde_yaml::Value;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 
@@ -58,6 +59,72 @@ pub fn assert_genesis_hash_fails(path_to_output_block: &PathBuf, expected_msg: &
     );
 }
 
+pub fn assert_genesis_validate(genesis_file_path: &PathBuf) -> String {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_genesis_validate_command(&genesis_file_path),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    content
+}
+
+pub fn assert_genesis_validate_fails(genesis_yaml: &GenesisYaml, expected_msg: &str) {
+    let input_yaml_file_path = GenesisYaml::serialize(&genesis_yaml);
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_genesis_validate_command(&input_yaml_file_path),
+        expected_msg,
+    );
+}
+
+pub fn assert_genesis_diff(from_file_path: &PathBuf, to_file_path: &PathBuf) -> Vec<String> {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_genesis_diff_command(&from_file_path, &to_file_path),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    content.lines().map(|line| line.to_owned()).collect()
+}
+
+pub fn assert_genesis_build(
+    parameters_file_path: &PathBuf,
+    allocations_file_path: &PathBuf,
+    leaders_dir_path: &PathBuf,
+    stake_pools_dir_path: &PathBuf,
+    output_genesis_path: &PathBuf,
+    output_block_path: &PathBuf,
+) {
+    let output =
+        process_utils::run_process_and_get_output(jcli_commands::get_genesis_build_command(
+            &parameters_file_path,
+            &allocations_file_path,
+            &leaders_dir_path,
+            &stake_pools_dir_path,
+            &output_genesis_path,
+            &output_block_path,
+        ));
+    process_assert::assert_process_exited_successfully(output);
+}
+
+pub fn assert_genesis_build_fails(
+    parameters_file_path: &PathBuf,
+    allocations_file_path: &PathBuf,
+    leaders_dir_path: &PathBuf,
+    stake_pools_dir_path: &PathBuf,
+    expected_msg: &str,
+) {
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_genesis_build_command(
+            &parameters_file_path,
+            &allocations_file_path,
+            &leaders_dir_path,
+            &stake_pools_dir_path,
+            &file_utils::get_path_in_temp("genesis.yaml"),
+            &file_utils::get_path_in_temp("block0.bin"),
+        ),
+        expected_msg,
+    );
+}
+
 pub fn assert_rest_stats(host: &str) -> BTreeMap<String, String> {
     let output =
         process_utils::run_process_and_get_output(jcli_commands::get_rest_stats_command(&host));
@@ -66,6 +133,67 @@ pub fn assert_rest_stats(host: &str) -> BTreeMap<String, String> {
     content
 }
 
+pub fn assert_rest_node_services(host: &str) -> BTreeMap<String, BTreeMap<String, Value>> {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_rest_node_services_command(&host),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_stake_distribution(host: &str) -> Vec<Value> {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_rest_stake_distribution_command(&host),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_node_config(host: &str, token: &str) -> Value {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_rest_token(
+        jcli_commands::get_rest_node_config_command(&host),
+        token,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_node_log_levels(host: &str, token: &str) -> Value {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_rest_token(
+        jcli_commands::get_rest_node_log_levels_command(&host),
+        token,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_node_log_levels_set(
+    host: &str,
+    token: &str,
+    tasks: &[(&str, &str)],
+    reset_tasks: &[&str],
+) -> Value {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_rest_token(
+        jcli_commands::get_rest_node_log_levels_set_command(&host, tasks, reset_tasks),
+        token,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_node_shutdown(host: &str, token: &str) {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_rest_token(
+        jcli_commands::get_rest_node_shutdown_command(&host),
+        token,
+    ));
+    process_assert::assert_process_exited_successfully(output);
+}
+
 pub fn assert_rest_utxo_get(host: &str) -> Vec<UTxOInfo> {
     let output =
         process_utils::run_process_and_get_output(jcli_commands::get_rest_utxo_get_command(&host));
@@ -75,6 +203,40 @@ pub fn assert_rest_utxo_get(host: &str) -> Vec<UTxOInfo> {
     utxos
 }
 
+pub fn assert_rest_utxo_get_filtered(
+    host: &str,
+    address: Option<&str>,
+    min_value: Option<u64>,
+    page_size: Option<usize>,
+) -> Vec<UTxOInfo> {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_rest_utxo_get_filtered_command(&host, address, min_value, page_size),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_utxo_get_at(host: &str, block_id: &str) -> Vec<UTxOInfo> {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_ledger_at(
+        jcli_commands::get_rest_utxo_get_command(&host),
+        &block_id,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
+pub fn assert_rest_utxo_get_at_date(host: &str, date: &str) -> Vec<UTxOInfo> {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_ledger_at_date(
+        jcli_commands::get_rest_utxo_get_command(&host),
+        &date,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    serde_yaml::from_str(&content).unwrap()
+}
+
 pub fn assert_get_address_info(address: &str) -> BTreeMap<String, String> {
     let output = process_utils::run_process_and_get_output(
         jcli_commands::get_address_info_command_default(&address),
@@ -248,6 +410,87 @@ pub fn assert_key_to_bytes_fails(
     );
 }
 
+pub fn assert_key_generate_mnemonic(words: usize, seed: &str) -> String {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_key_generate_mnemonic_command(words, &seed),
+    );
+    let single_line = output.as_single_line();
+    process_assert::assert_process_exited_successfully(output);
+    single_line
+}
+
+pub fn assert_key_from_mnemonic(mnemonic: &str, password: &str) -> String {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_key_from_mnemonic_command(&mnemonic, &password),
+    );
+    let single_line = output.as_single_line();
+    process_assert::assert_process_exited_successfully(output);
+    single_line
+}
+
+pub fn assert_key_from_mnemonic_fails(mnemonic: &str, expected_msg: &str) {
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_key_from_mnemonic_command(&mnemonic, ""),
+        expected_msg,
+    );
+}
+
+pub fn assert_key_derive(private_key: &str, path: &str) -> String {
+    let output = process_utils::run_process_and_get_output(jcli_commands::get_key_derive_command(
+        &private_key,
+        &path,
+    ));
+    let single_line = output.as_single_line();
+    process_assert::assert_process_exited_successfully(output);
+    single_line
+}
+
+pub fn assert_key_derive_fails(private_key: &str, path: &str, expected_msg: &str) {
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_key_derive_command(&private_key, &path),
+        expected_msg,
+    );
+}
+
+pub fn assert_key_sign(private_key: &str, input_file: &PathBuf, hex: bool) -> String {
+    let output = process_utils::run_process_and_get_output(jcli_commands::get_key_sign_command(
+        &private_key,
+        &input_file,
+        hex,
+    ));
+    let single_line = output.as_single_line();
+    process_assert::assert_process_exited_successfully(output);
+    single_line
+}
+
+pub fn assert_key_sign_fails(private_key: &str, input_file: &PathBuf, expected_msg: &str) {
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_key_sign_command(&private_key, &input_file, false),
+        expected_msg,
+    );
+}
+
+pub fn assert_key_verify(public_key: &str, signature: &str, input_file: &PathBuf) {
+    let output = process_utils::run_process_and_get_output(jcli_commands::get_key_verify_command(
+        &public_key,
+        &signature,
+        &input_file,
+    ));
+    process_assert::assert_process_exited_successfully(output);
+}
+
+pub fn assert_key_verify_fails(
+    public_key: &str,
+    signature: &str,
+    input_file: &PathBuf,
+    expected_msg: &str,
+) {
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_key_verify_command(&public_key, &signature, &input_file),
+        expected_msg,
+    );
+}
+
 pub fn assert_rest_get_block_tip(host: &str) -> String {
     let output =
         process_utils::run_process_and_get_output(jcli_commands::get_rest_block_tip_command(&host));
@@ -256,6 +499,16 @@ pub fn assert_rest_get_block_tip(host: &str) -> String {
     single_line
 }
 
+pub fn assert_rest_get_block_tip_with_token(host: &str, token: &str) -> String {
+    let output = process_utils::run_process_and_get_output(jcli_commands::with_rest_token(
+        jcli_commands::get_rest_block_tip_command(&host),
+        &token,
+    ));
+    let single_line = output.as_single_line();
+    process_assert::assert_process_exited_successfully(output);
+    single_line
+}
+
 pub fn assert_rest_account_get_stats(address: &str, host: &str) -> AccountState {
     let output = process_utils::run_process_and_get_output(
         jcli_commands::get_rest_account_stats_command(&address, &host),
@@ -266,6 +519,19 @@ pub fn assert_rest_account_get_stats(address: &str, host: &str) -> AccountState
     serde_yaml::from_str(&content).unwrap()
 }
 
+pub fn assert_rest_account_get_many(
+    addresses: &[&str],
+    host: &str,
+) -> BTreeMap<String, Option<AccountState>> {
+    let output = process_utils::run_process_and_get_output(
+        jcli_commands::get_rest_account_get_many_command(&addresses, &host),
+    );
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+
+    serde_yaml::from_str(&content).unwrap()
+}
+
 pub fn assert_rest_get_block_by_id(block_id: &str, host: &str) -> String {
     let output = process_utils::run_process_and_get_output(
         jcli_commands::get_rest_get_block_command(&block_id, &host),
@@ -82,6 +82,78 @@ pub fn get_start_jormungandr_as_passive_node_command(
     command
 }
 
+pub fn get_validate_config_command(config_path: &PathBuf, genesis_block_path: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("--validate-config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("--genesis-block")
+        .arg(genesis_block_path.as_os_str());
+    command
+}
+
+pub fn get_print_config_command(config_path: &PathBuf, genesis_block_path: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("--print-config")
+        .arg("--config")
+        .arg(config_path.as_os_str())
+        .arg("--genesis-block")
+        .arg(genesis_block_path.as_os_str());
+    command
+}
+
+pub fn get_verify_storage_command(storage: &PathBuf, genesis_block_hash: &str) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("verify-storage")
+        .arg("--storage")
+        .arg(storage.as_os_str())
+        .arg("--genesis-block-hash")
+        .arg(genesis_block_hash);
+    command
+}
+
+pub fn get_export_storage_command(storage: &PathBuf, archive: &PathBuf) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("export-storage")
+        .arg("--storage")
+        .arg(storage.as_os_str())
+        .arg("--output")
+        .arg(archive.as_os_str());
+    command
+}
+
+pub fn get_import_storage_command(
+    storage: &PathBuf,
+    archive: &PathBuf,
+    genesis_block_hash: &str,
+) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("import-storage")
+        .arg("--storage")
+        .arg(storage.as_os_str())
+        .arg("--input")
+        .arg(archive.as_os_str())
+        .arg("--genesis-block-hash")
+        .arg(genesis_block_hash);
+    command
+}
+
+pub fn get_migrate_storage_command(storage: &PathBuf, backend: &str) -> Command {
+    let mut command = Command::new(configuration::get_jormungandr_app().as_os_str());
+    command
+        .arg("migrate-storage")
+        .arg("--storage")
+        .arg(storage.as_os_str())
+        .arg("--to")
+        .arg(backend);
+    command
+}
+
 #[cfg(windows)]
 fn get_stdio_from_log_file(log_file_path: &PathBuf) -> std::process::Stdio {
     use std::os::windows::io::{FromRawHandle, IntoRawHandle};
@@ -24,8 +24,13 @@ fn try_to_start_jormungandr_node(
         .spawn()
         .expect("failed to execute 'start jormungandr node'");
 
+    let mut stats_command =
+        jcli_wrapper::jcli_commands::get_rest_stats_command(&config.get_node_address());
+    if let Some(token) = config.node_config.get_rest_read_token() {
+        stats_command = jcli_wrapper::jcli_commands::with_rest_token(stats_command, &token);
+    }
     let proces_start_result = process_utils::run_process_until_response_matches(
-        jcli_wrapper::jcli_commands::get_rest_stats_command(&config.get_node_address()),
+        stats_command,
         &is_node_up,
         2,
         5,
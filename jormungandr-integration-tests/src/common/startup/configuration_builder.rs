@@ -1,7 +1,9 @@
 use crate::common::configuration::{
     genesis_model::{Fund, GenesisYaml, Initial, LinearFees},
     jormungandr_config::JormungandrConfig,
-    node_config_model::{Logger, NodeConfig, Peer},
+    node_config_model::{
+        Logger, Mempool, NodeConfig, Peer, RestAuthentication, RestLimits, RestToken,
+    },
     secret_model::SecretModel,
 };
 use crate::common::file_utils;
@@ -18,6 +20,10 @@ pub struct ConfigurationBuilder {
     linear_fees: LinearFees,
     certs: Vec<String>,
     consensus_leader_ids: Vec<String>,
+    rest_tokens: Option<Vec<RestToken>>,
+    rest_limits: Option<RestLimits>,
+    mempool: Option<Mempool>,
+    mode: Option<String>,
 }
 
 impl ConfigurationBuilder {
@@ -26,6 +32,10 @@ impl ConfigurationBuilder {
             funds: vec![],
             certs: vec![],
             consensus_leader_ids: vec![],
+            rest_tokens: None,
+            rest_limits: None,
+            mempool: None,
+            mode: None,
             trusted_peers: None,
             block0_hash: None,
             block0_consensus: Some("bft".to_string()),
@@ -97,6 +107,26 @@ impl ConfigurationBuilder {
         self
     }
 
+    pub fn with_rest_tokens<'a>(&'a mut self, rest_tokens: Vec<RestToken>) -> &'a mut Self {
+        self.rest_tokens = Some(rest_tokens);
+        self
+    }
+
+    pub fn with_rest_limits<'a>(&'a mut self, rest_limits: RestLimits) -> &'a mut Self {
+        self.rest_limits = Some(rest_limits);
+        self
+    }
+
+    pub fn with_mempool<'a>(&'a mut self, mempool: Mempool) -> &'a mut Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    pub fn with_mode<'a>(&'a mut self, mode: &str) -> &'a mut Self {
+        self.mode = Some(mode.to_owned());
+        self
+    }
+
     pub fn with_block_hash<'a>(&'a mut self, block0_hash: String) -> &'a mut Self {
         self.block0_hash = Some(block0_hash.clone());
         self
@@ -106,6 +136,14 @@ impl ConfigurationBuilder {
         let mut node_config = NodeConfig::new();
         node_config.peer_2_peer.trusted_peers = self.trusted_peers.clone();
         node_config.logger = self.logger.clone();
+        if let Some(tokens) = &self.rest_tokens {
+            node_config.rest.as_mut().unwrap().authentication = Some(RestAuthentication {
+                tokens: tokens.clone(),
+            });
+        }
+        node_config.rest.as_mut().unwrap().limits = self.rest_limits.clone();
+        node_config.mempool = self.mempool.clone();
+        node_config.mode = self.mode.clone();
         let node_config_path = NodeConfig::serialize(&node_config);
 
         let secret_key = jcli_wrapper::assert_key_generate("ed25519");
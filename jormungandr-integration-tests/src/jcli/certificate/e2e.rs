@@ -1,7 +1,10 @@
 use crate::common::file_assert;
 use crate::common::file_utils;
 use crate::common::jcli_wrapper;
-use crate::common::jcli_wrapper::certificate::wrapper::JCLICertificateWrapper;
+use crate::common::jcli_wrapper::certificate::{
+    commands::CertificateCommands, wrapper::JCLICertificateWrapper,
+};
+use crate::common::process_assert;
 
 #[test]
 pub fn test_create_and_sign_new_stake_delegation() {
@@ -38,3 +41,62 @@ pub fn test_create_and_sign_new_stake_delegation() {
 
     file_assert::assert_file_exists_and_not_empty(&signed_cert);
 }
+
+#[test]
+pub fn test_merge_signatures_of_certificate_signed_offline_by_owners() {
+    let kes_private_key = jcli_wrapper::assert_key_generate("SumEd25519_12");
+    let kes_public_key = jcli_wrapper::assert_key_to_public_default(&kes_private_key);
+    let vrf_private_key = jcli_wrapper::assert_key_generate("Curve25519_2HashDH");
+    let vrf_public_key = jcli_wrapper::assert_key_to_public_default(&vrf_private_key);
+    let alice_key_file = file_utils::create_file_in_temp(
+        "alice.private",
+        &jcli_wrapper::assert_key_generate_default(),
+    );
+    let bob_key_file = file_utils::create_file_in_temp(
+        "bob.private",
+        &jcli_wrapper::assert_key_generate_default(),
+    );
+
+    let certificate_wrapper = JCLICertificateWrapper::new();
+    let certificate = certificate_wrapper.assert_new_stake_pool_registration(
+        &kes_public_key,
+        "13919597664319319060838570079442950054",
+        &vrf_public_key,
+    );
+    let certificate_file = file_utils::create_file_in_temp("certificate", &certificate);
+
+    let alice_cert = file_utils::get_path_in_temp("alice.signcert");
+    certificate_wrapper.assert_sign(&alice_key_file, &certificate_file, &alice_cert);
+    let bob_cert = file_utils::get_path_in_temp("bob.signcert");
+    certificate_wrapper.assert_sign(&bob_key_file, &certificate_file, &bob_cert);
+    let both_cert = file_utils::get_path_in_temp("both.signcert");
+    certificate_wrapper.assert_sign(&bob_key_file, &alice_cert, &both_cert);
+
+    let merged_cert = file_utils::get_path_in_temp("merged.signcert");
+    certificate_wrapper.assert_merge_signatures(
+        &alice_cert,
+        &[bob_cert.clone(), alice_cert.clone()],
+        &merged_cert,
+    );
+    assert_eq!(
+        file_utils::read_file(&merged_cert),
+        file_utils::read_file(&both_cert),
+        "the merged signatures differ from the signatures added one after the other"
+    );
+
+    let other_certificate = certificate_wrapper.assert_new_stake_pool_registration(
+        &kes_public_key,
+        "1",
+        &vrf_public_key,
+    );
+    let other_certificate_file =
+        file_utils::create_file_in_temp("other_certificate", &other_certificate);
+    process_assert::assert_process_failed_and_contains_message(
+        CertificateCommands::new().get_merge_signatures_command(
+            &alice_cert,
+            &[other_certificate_file],
+            &file_utils::get_path_in_temp("mismatch.signcert"),
+        ),
+        "cannot merge the signatures of a different certificate",
+    );
+}
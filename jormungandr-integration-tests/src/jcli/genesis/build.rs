@@ -0,0 +1,147 @@
+use crate::common::configuration::genesis_model::{Fund, GenesisYaml, Initial};
+use crate::common::file_utils;
+use crate::common::jcli_wrapper;
+use crate::common::jcli_wrapper::Discrimination;
+use std::path::PathBuf;
+
+fn create_parameters_file() -> PathBuf {
+    let genesis = GenesisYaml::new();
+    let mut parameters = genesis.blockchain_configuration.clone();
+    parameters.consensus_leader_ids = None;
+    let content = serde_yaml::to_string(&parameters).unwrap();
+    file_utils::create_file_in_temp("parameters.yaml", &content)
+}
+
+fn create_allocations_file(owner_sk: &str) -> PathBuf {
+    let owner_pk = jcli_wrapper::assert_key_to_public_default(&owner_sk);
+    let owner_account = jcli_wrapper::assert_address_account(&owner_pk, Discrimination::Test);
+    let content = match &GenesisYaml::new().initial[0] {
+        Initial::Fund(funds) => funds
+            .iter()
+            .map(|Fund { address, value }| format!("{},{}\n", address, value))
+            .collect::<String>(),
+        _ => unreachable!(),
+    };
+    file_utils::create_file_in_temp(
+        "allocations.csv",
+        &format!("address,value\n{}{},1000\n", content, owner_account),
+    )
+}
+
+fn create_leaders_dir() -> PathBuf {
+    let leaders_dir = file_utils::get_temp_folder();
+    let leader_sk = jcli_wrapper::assert_key_generate("Ed25519Extended");
+    let leader_pk = jcli_wrapper::assert_key_to_public_default(&leader_sk);
+    file_utils::create_file_with_content(&leaders_dir.join("leader_1.sk"), &leader_sk);
+    file_utils::create_file_with_content(&leaders_dir.join("leader_2.pk"), &leader_pk);
+    leaders_dir
+}
+
+fn create_stake_pools_dir(owner_sk: &str) -> PathBuf {
+    let stake_pools_dir = file_utils::get_temp_folder();
+    let stake_pool_dir = stake_pools_dir.join("pool_1");
+    std::fs::create_dir(&stake_pool_dir).unwrap();
+    file_utils::create_file_with_content(&stake_pool_dir.join("owner.sk"), &owner_sk);
+    file_utils::create_file_with_content(
+        &stake_pool_dir.join("kes.sk"),
+        &jcli_wrapper::assert_key_generate("SumEd25519_12"),
+    );
+    file_utils::create_file_with_content(
+        &stake_pool_dir.join("vrf.sk"),
+        &jcli_wrapper::assert_key_generate("Curve25519_2HashDH"),
+    );
+    stake_pools_dir
+}
+
+#[test]
+pub fn test_genesis_build_creates_valid_genesis_and_block0() {
+    let owner_sk = jcli_wrapper::assert_key_generate("Ed25519");
+    let output_genesis = file_utils::get_path_in_temp("genesis.yaml");
+    let output_block = file_utils::get_path_in_temp("block0.bin");
+    jcli_wrapper::assert_genesis_build(
+        &create_parameters_file(),
+        &create_allocations_file(&owner_sk),
+        &create_leaders_dir(),
+        &create_stake_pools_dir(&owner_sk),
+        &output_genesis,
+        &output_block,
+    );
+
+    jcli_wrapper::assert_genesis_validate(&output_genesis);
+    jcli_wrapper::assert_genesis_validate(&output_block);
+    let changes = jcli_wrapper::assert_genesis_diff(&output_genesis, &output_block);
+    assert!(
+        changes.is_empty(),
+        "genesis and block0 differ: {:?}",
+        changes
+    );
+
+    let content = file_utils::read_file(&output_genesis);
+    let genesis: GenesisYaml = serde_yaml::from_str(&content).unwrap();
+    assert_eq!(
+        genesis
+            .blockchain_configuration
+            .consensus_leader_ids
+            .unwrap()
+            .len(),
+        2
+    );
+    let certificates = genesis
+        .initial
+        .iter()
+        .filter(|initial| match initial {
+            Initial::Cert(_) => true,
+            _ => false,
+        })
+        .count();
+    assert_eq!(certificates, 2, "expected registration and delegation");
+}
+
+#[test]
+pub fn test_genesis_build_splits_many_allocations_in_several_funds() {
+    let owner_sk = jcli_wrapper::assert_key_generate("Ed25519");
+    let address = match &GenesisYaml::new().initial[0] {
+        Initial::Fund(funds) => funds[0].address.clone(),
+        _ => unreachable!(),
+    };
+    let allocations_count = 600;
+    let content = (1..=allocations_count)
+        .map(|value| format!("{},{}\n", address, value))
+        .collect::<String>();
+    let allocations = file_utils::create_file_in_temp("allocations.csv", &content);
+    let output_genesis = file_utils::get_path_in_temp("genesis.yaml");
+    let output_block = file_utils::get_path_in_temp("block0.bin");
+    jcli_wrapper::assert_genesis_build(
+        &create_parameters_file(),
+        &allocations,
+        &create_leaders_dir(),
+        &create_stake_pools_dir(&owner_sk),
+        &output_genesis,
+        &output_block,
+    );
+
+    jcli_wrapper::assert_genesis_validate(&output_block);
+    let content = file_utils::read_file(&output_genesis);
+    let genesis: GenesisYaml = serde_yaml::from_str(&content).unwrap();
+    let funds: Vec<usize> = genesis
+        .initial
+        .iter()
+        .filter_map(|initial| match initial {
+            Initial::Fund(funds) => Some(funds.len()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(funds, vec![254, 254, 92]);
+}
+
+#[test]
+pub fn test_genesis_build_fails_with_malformed_allocation() {
+    let allocations = file_utils::create_file_in_temp("allocations.csv", "not_an_address,100\n");
+    jcli_wrapper::assert_genesis_build_fails(
+        &create_parameters_file(),
+        &allocations,
+        &create_leaders_dir(),
+        &create_stake_pools_dir(&jcli_wrapper::assert_key_generate("Ed25519")),
+        "invalid allocation",
+    );
+}
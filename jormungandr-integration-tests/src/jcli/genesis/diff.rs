@@ -0,0 +1,35 @@
+use crate::common::configuration::genesis_model::GenesisYaml;
+use crate::common::file_utils;
+use crate::common::jcli_wrapper;
+
+#[test]
+pub fn test_same_genesis_has_no_differences() {
+    let input_yaml_file_path = GenesisYaml::serialize(&GenesisYaml::new());
+    let path_to_output_block = file_utils::get_path_in_temp("block0.bin");
+    jcli_wrapper::assert_genesis_encode(&input_yaml_file_path, &path_to_output_block);
+
+    let differences =
+        jcli_wrapper::assert_genesis_diff(&input_yaml_file_path, &path_to_output_block);
+    assert!(
+        differences.is_empty(),
+        "unexpected differences: {:?}",
+        differences
+    );
+}
+
+#[test]
+pub fn test_modified_slot_duration_is_reported() {
+    let from = GenesisYaml::new();
+    let mut to = from.clone();
+    to.blockchain_configuration.slot_duration = Some(10);
+
+    let from_path =
+        file_utils::create_file_in_temp("from.yaml", &serde_yaml::to_string(&from).unwrap());
+    let to_path = file_utils::create_file_in_temp("to.yaml", &serde_yaml::to_string(&to).unwrap());
+
+    let differences = jcli_wrapper::assert_genesis_diff(&from_path, &to_path);
+    assert_eq!(
+        differences,
+        vec!["~ blockchain_configuration.slot_duration: 1 -> 10".to_owned()]
+    );
+}
@@ -1,3 +1,6 @@
+pub mod build;
+pub mod diff;
 pub mod encode;
 pub mod hash;
 pub mod init;
+pub mod validate;
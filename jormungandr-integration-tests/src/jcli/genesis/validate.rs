@@ -0,0 +1,45 @@
+use crate::common::configuration::genesis_model::{Fund, GenesisYaml, Initial};
+use crate::common::file_utils;
+use crate::common::jcli_wrapper;
+
+#[test]
+pub fn test_genesis_from_init_is_valid() {
+    let content = jcli_wrapper::assert_genesis_init();
+    let path_to_yaml = file_utils::create_file_in_temp("init_file.yaml", &content);
+    jcli_wrapper::assert_genesis_validate(&path_to_yaml);
+}
+
+#[test]
+pub fn test_encoded_block0_is_valid() {
+    let input_yaml_file_path = GenesisYaml::serialize(&GenesisYaml::new());
+    let path_to_output_block = file_utils::get_path_in_temp("block0.bin");
+    jcli_wrapper::assert_genesis_encode(&input_yaml_file_path, &path_to_output_block);
+    jcli_wrapper::assert_genesis_validate(&path_to_output_block);
+}
+
+#[test]
+pub fn test_genesis_with_bft_consensus_and_no_leaders_is_invalid() {
+    let mut genesis = GenesisYaml::new();
+    genesis.blockchain_configuration.consensus_leader_ids = Some(vec![]);
+    jcli_wrapper::assert_genesis_validate_fails(&genesis, "genesis validation failed");
+}
+
+#[test]
+pub fn test_genesis_with_total_supply_overflow_is_invalid() {
+    let genesis = GenesisYaml::new();
+    let address = match &genesis.initial[0] {
+        Initial::Fund(funds) => funds[0].address.clone(),
+        _ => unreachable!(),
+    };
+    let genesis = GenesisYaml::new_with_funds(&[
+        Fund {
+            address: address.clone(),
+            value: std::u64::MAX.into(),
+        },
+        Fund {
+            address: address,
+            value: 1.into(),
+        },
+    ]);
+    jcli_wrapper::assert_genesis_validate_fails(&genesis, "genesis validation failed");
+}
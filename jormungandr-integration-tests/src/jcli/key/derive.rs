@@ -0,0 +1,46 @@
+use crate::common::jcli_wrapper;
+use crate::common::jcli_wrapper::Discrimination;
+
+const SEED: &str = "73855612722627931e20c850f8ad53eb04c615c7601a95747be073dcada3e135";
+
+#[test]
+pub fn test_key_derivation_is_reproducible() {
+    let root = jcli_wrapper::assert_key_with_seed_generate("Ed25519Bip32", SEED);
+    let first = jcli_wrapper::assert_key_derive(&root, "m/1852'/1815'/0'/0/0");
+    let second = jcli_wrapper::assert_key_derive(&root, "m/1852'/1815'/0'/0/0");
+    assert_eq!(first, second, "same path should give the same key");
+    assert_ne!(first, root, "derived key should differ from the root key");
+}
+
+#[test]
+pub fn test_key_derivation_path_can_be_split() {
+    let root = jcli_wrapper::assert_key_with_seed_generate("Ed25519Bip32", SEED);
+    let account = jcli_wrapper::assert_key_derive(&root, "m/1852'/1815'/0'");
+    assert_eq!(
+        jcli_wrapper::assert_key_derive(&account, "0/1"),
+        jcli_wrapper::assert_key_derive(&root, "m/1852h/1815h/0h/0/1"),
+    );
+}
+
+#[test]
+pub fn test_addresses_from_derived_key() {
+    let mnemonic = jcli_wrapper::assert_key_generate_mnemonic(24, SEED);
+    let root = jcli_wrapper::assert_key_from_mnemonic(&mnemonic, "");
+    let child = jcli_wrapper::assert_key_derive(&root, "m/44'/1815'/0'/0/0");
+    let public_key = jcli_wrapper::assert_key_to_public_default(&child);
+    let account = jcli_wrapper::assert_address_account(&public_key, Discrimination::Test);
+    let single = jcli_wrapper::assert_address_single(&public_key, Discrimination::Test);
+    assert_ne!(account, single);
+}
+
+#[test]
+pub fn test_key_derivation_requires_bip32_key() {
+    let private_key = jcli_wrapper::assert_key_generate("Ed25519Extended");
+    jcli_wrapper::assert_key_derive_fails(&private_key, "m/0", "expected an ed25519bip32");
+}
+
+#[test]
+pub fn test_key_derivation_with_invalid_path() {
+    let root = jcli_wrapper::assert_key_generate("Ed25519Bip32");
+    jcli_wrapper::assert_key_derive_fails(&root, "m/a'/0", "invalid derivation path");
+}
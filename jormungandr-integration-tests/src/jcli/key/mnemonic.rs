@@ -0,0 +1,34 @@
+use crate::common::jcli_wrapper;
+
+const SEED: &str = "73855612722627931e20c850f8ad53eb04c615c7601a95747be073dcada3e135";
+
+#[test]
+pub fn test_mnemonic_generation_with_seed_is_reproducible() {
+    let first = jcli_wrapper::assert_key_generate_mnemonic(15, SEED);
+    let second = jcli_wrapper::assert_key_generate_mnemonic(15, SEED);
+    assert_eq!(first, second, "same seed should give the same mnemonic");
+    assert_eq!(first.split_whitespace().count(), 15);
+}
+
+#[test]
+pub fn test_key_restored_from_mnemonic_is_reproducible() {
+    let mnemonic = jcli_wrapper::assert_key_generate_mnemonic(24, SEED);
+    let first = jcli_wrapper::assert_key_from_mnemonic(&mnemonic, "");
+    let second = jcli_wrapper::assert_key_from_mnemonic(&mnemonic, "");
+    assert_eq!(first, second, "same mnemonic should give the same key");
+    assert!(first.starts_with("ed25519bip32_sk"), "{}", first);
+}
+
+#[test]
+pub fn test_password_changes_key_restored_from_mnemonic() {
+    let mnemonic = jcli_wrapper::assert_key_generate_mnemonic(12, SEED);
+    assert_ne!(
+        jcli_wrapper::assert_key_from_mnemonic(&mnemonic, ""),
+        jcli_wrapper::assert_key_from_mnemonic(&mnemonic, "password"),
+    );
+}
+
+#[test]
+pub fn test_key_from_invalid_mnemonic() {
+    jcli_wrapper::assert_key_from_mnemonic_fails("not a valid mnemonic phrase", "invalid mnemonic");
+}
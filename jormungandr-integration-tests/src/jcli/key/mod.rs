@@ -1,4 +1,7 @@
+pub mod derive;
 pub mod from_bytes;
 pub mod generate;
+pub mod mnemonic;
+pub mod sign;
 pub mod to_bytes;
 pub mod to_public;
@@ -0,0 +1,72 @@
+use crate::common::file_utils;
+use crate::common::jcli_wrapper;
+
+fn assert_sign_and_verify(key_type: &str, hex: bool) {
+    let private_key = jcli_wrapper::assert_key_generate(&key_type);
+    let public_key = jcli_wrapper::assert_key_to_public_default(&private_key);
+    let input_file = file_utils::create_file_in_temp("data", "content to sign");
+    let signature = jcli_wrapper::assert_key_sign(&private_key, &input_file, hex);
+    jcli_wrapper::assert_key_verify(&public_key, &signature, &input_file);
+}
+
+#[test]
+pub fn test_sign_and_verify_ed25519() {
+    assert_sign_and_verify("Ed25519", false);
+}
+
+#[test]
+pub fn test_sign_and_verify_ed25519_extended() {
+    assert_sign_and_verify("Ed25519Extended", false);
+}
+
+#[test]
+pub fn test_sign_and_verify_ed25519_bip32() {
+    assert_sign_and_verify("Ed25519Bip32", false);
+}
+
+#[test]
+pub fn test_sign_and_verify_sum_ed25519_12() {
+    assert_sign_and_verify("SumEd25519_12", false);
+}
+
+#[test]
+pub fn test_sign_and_verify_with_hex_signature() {
+    assert_sign_and_verify("Ed25519", true);
+}
+
+#[test]
+pub fn test_verify_fails_for_modified_content() {
+    let private_key = jcli_wrapper::assert_key_generate("Ed25519");
+    let public_key = jcli_wrapper::assert_key_to_public_default(&private_key);
+    let input_file = file_utils::create_file_in_temp("data", "content to sign");
+    let signature = jcli_wrapper::assert_key_sign(&private_key, &input_file, false);
+    let modified_file = file_utils::create_file_in_temp("data", "modified content");
+    jcli_wrapper::assert_key_verify_fails(
+        &public_key,
+        &signature,
+        &modified_file,
+        "signature verification failed",
+    );
+}
+
+#[test]
+pub fn test_verify_fails_for_other_public_key() {
+    let private_key = jcli_wrapper::assert_key_generate("Ed25519");
+    let other_private_key = jcli_wrapper::assert_key_generate("Ed25519");
+    let other_public_key = jcli_wrapper::assert_key_to_public_default(&other_private_key);
+    let input_file = file_utils::create_file_in_temp("data", "content to sign");
+    let signature = jcli_wrapper::assert_key_sign(&private_key, &input_file, false);
+    jcli_wrapper::assert_key_verify_fails(
+        &other_public_key,
+        &signature,
+        &input_file,
+        "signature verification failed",
+    );
+}
+
+#[test]
+pub fn test_sign_with_vrf_key_is_not_supported() {
+    let private_key = jcli_wrapper::assert_key_generate("Curve25519_2HashDH");
+    let input_file = file_utils::create_file_in_temp("data", "content to sign");
+    jcli_wrapper::assert_key_sign_fails(&private_key, &input_file, "cannot be used for signing");
+}
@@ -0,0 +1,26 @@
+use crate::common::configuration::genesis_model::Fund;
+use crate::common::jcli_wrapper;
+use crate::common::startup;
+
+#[test]
+pub fn test_many_accounts_state_are_read_from_node() {
+    let funded = startup::create_new_account_address();
+    let unknown = startup::create_new_account_address();
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_funds(vec![Fund {
+            address: funded.address.clone(),
+            value: 100.into(),
+        }])
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    let states = jcli_wrapper::assert_rest_account_get_many(
+        &[&funded.address, &unknown.address],
+        &jormungandr_rest_address,
+    );
+    assert_eq!(states.len(), 2);
+    let found: Vec<_> = states.values().filter_map(|state| state.as_ref()).collect();
+    assert_eq!(found.len(), 1, "only the funded account should be found");
+    assert_eq!(found[0].value().to_string(), "100");
+}
@@ -0,0 +1,57 @@
+use crate::common::configuration::node_config_model::RestToken;
+use crate::common::jcli_wrapper;
+use crate::common::jcli_wrapper::jcli_commands;
+use crate::common::process_assert;
+use crate::common::startup;
+
+fn rest_token(token: &str, scopes: &[&str]) -> RestToken {
+    RestToken {
+        token: token.to_owned(),
+        scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+    }
+}
+
+#[test]
+pub fn test_rest_rejects_requests_without_valid_token() {
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![rest_token("reader-token", &["read-only"])])
+        .build();
+    let host = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_rest_block_tip_command(&host),
+        "401",
+    );
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::with_rest_token(
+            jcli_commands::get_rest_block_tip_command(&host),
+            "wrong-token",
+        ),
+        "401",
+    );
+    let block_id = jcli_wrapper::assert_rest_get_block_tip_with_token(&host, "reader-token");
+    assert_ne!(&block_id, "", "empty block hash");
+}
+
+#[test]
+pub fn test_rest_checks_token_scopes() {
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![
+            rest_token("reader-token", &["read-only"]),
+            rest_token("admin-token", &["admin"]),
+        ])
+        .build();
+    let host = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::with_rest_token(
+            jcli_commands::get_post_transaction_command("00", &host),
+            "reader-token",
+        ),
+        "403",
+    );
+    let block_id = jcli_wrapper::assert_rest_get_block_tip_with_token(&host, "admin-token");
+    assert_ne!(&block_id, "", "empty block hash");
+}
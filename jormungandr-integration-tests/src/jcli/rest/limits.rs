@@ -0,0 +1,73 @@
+use crate::common::configuration::node_config_model::{RestLimits, RestToken};
+use crate::common::jcli_wrapper;
+use crate::common::jcli_wrapper::jcli_commands;
+use crate::common::process_assert;
+use crate::common::startup;
+
+#[test]
+pub fn test_rest_rejects_requests_above_token_rate_limit() {
+    let requests_per_minute = 10;
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![
+            RestToken {
+                token: "startup-token".to_owned(),
+                scopes: vec!["read-only".to_owned()],
+            },
+            RestToken {
+                token: "limited-token".to_owned(),
+                scopes: vec!["read-only".to_owned()],
+            },
+        ])
+        .with_rest_limits(RestLimits {
+            requests_per_minute_per_token: Some(requests_per_minute),
+            ..Default::default()
+        })
+        .build();
+    let host = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    for _ in 0..requests_per_minute {
+        jcli_wrapper::assert_rest_get_block_tip_with_token(&host, "limited-token");
+    }
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::with_rest_token(
+            jcli_commands::get_rest_block_tip_command(&host),
+            "limited-token",
+        ),
+        "429",
+    );
+}
+
+#[test]
+pub fn test_rest_counts_unknown_tokens_for_the_client_address() {
+    let requests_per_minute = 5;
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![RestToken {
+            token: "startup-token".to_owned(),
+            scopes: vec!["read-only".to_owned()],
+        }])
+        .with_rest_limits(RestLimits {
+            requests_per_minute_per_token: Some(requests_per_minute),
+            ..Default::default()
+        })
+        .build();
+    let host = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    for i in 0..requests_per_minute {
+        process_assert::assert_process_failed_and_contains_message(
+            jcli_commands::with_rest_token(
+                jcli_commands::get_rest_block_tip_command(&host),
+                &format!("unknown-token-{}", i),
+            ),
+            "401",
+        );
+    }
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::with_rest_token(
+            jcli_commands::get_rest_block_tip_command(&host),
+            "another-unknown-token",
+        ),
+        "429",
+    );
+}
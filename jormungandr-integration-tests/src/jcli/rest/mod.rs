@@ -1,4 +1,10 @@
+pub mod account;
+pub mod auth;
 pub mod block;
 pub mod host;
+pub mod limits;
+pub mod mode;
+pub mod node;
+pub mod stake_distribution;
 pub mod tip;
 pub mod utxo;
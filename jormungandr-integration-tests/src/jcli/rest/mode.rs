@@ -0,0 +1,20 @@
+use crate::common::jcli_wrapper;
+use crate::common::jcli_wrapper::jcli_commands;
+use crate::common::process_assert;
+use crate::common::startup;
+
+#[test]
+pub fn test_mirror_node_serves_blocks_and_refuses_fragments() {
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_mode("mirror")
+        .build();
+    let host = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    let block_id = jcli_wrapper::assert_rest_get_block_tip(&host);
+    assert_ne!(&block_id, "", "empty block hash");
+    process_assert::assert_process_failed_and_contains_message(
+        jcli_commands::get_post_transaction_command("00", &host),
+        "403",
+    );
+}
@@ -0,0 +1,130 @@
+use crate::common::configuration::node_config_model::{Logger, Mempool, RestToken};
+use crate::common::jcli_wrapper;
+use crate::common::startup;
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+pub fn test_node_services_are_running() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+    let services = jcli_wrapper::assert_rest_node_services(&jormungandr_rest_address);
+
+    for name in &["block", "client-query", "fragment", "leadership", "network"] {
+        let status = services
+            .get(*name)
+            .unwrap_or_else(|| panic!("no status for service {}", name));
+        assert_eq!(
+            status.get("state").and_then(|state| state.as_str()),
+            Some("running"),
+            "service {} is not running",
+            name
+        );
+        assert_eq!(
+            status
+                .get("restartCount")
+                .and_then(|restart_count| restart_count.as_u64()),
+            Some(0),
+            "service {} has been restarted",
+            name
+        );
+    }
+}
+
+#[test]
+pub fn test_node_shuts_down_on_admin_request() {
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![RestToken {
+            token: "admin-token".to_owned(),
+            scopes: vec!["admin".to_owned()],
+        }])
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let mut jormungandr = startup::start_jormungandr_node(&mut config);
+    jcli_wrapper::assert_rest_node_shutdown(&jormungandr_rest_address, "admin-token");
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let status = loop {
+        if let Some(status) = jormungandr.child.try_wait().unwrap() {
+            break status;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "node did not shut down within 60 seconds"
+        );
+        thread::sleep(Duration::from_millis(500));
+    };
+    assert!(status.success(), "node exited with {}", status);
+}
+
+#[test]
+pub fn test_node_config_reports_mempool_settings_and_redacts_tokens() {
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![RestToken {
+            token: "read-token".to_owned(),
+            scopes: vec!["read-only".to_owned()],
+        }])
+        .with_mempool(Mempool {
+            fragment_ttl: Some("30m".to_owned()),
+            max_fragments_per_block: Some(100),
+            ..Default::default()
+        })
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+    let node_config =
+        jcli_wrapper::assert_rest_node_config(&jormungandr_rest_address, "read-token");
+
+    let mempool = &node_config["mempool"];
+    assert_eq!(mempool["fragment_ttl"].as_str(), Some("30m"));
+    assert_eq!(mempool["log_ttl"].as_str(), Some("2h"));
+    assert_eq!(mempool["max_fragments_per_block"].as_u64(), Some(100));
+    assert_ne!(
+        node_config["rest"]["authentication"]["tokens"][0]["token"].as_str(),
+        Some("read-token"),
+        "the REST token is not redacted"
+    );
+}
+
+#[test]
+pub fn test_node_log_levels_change_at_runtime() {
+    let mut tasks = BTreeMap::new();
+    tasks.insert("network".to_owned(), "debug".to_owned());
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_logger(Logger {
+            verbosity: 0,
+            format: String::from("json"),
+            tasks: Some(tasks),
+        })
+        .with_rest_tokens(vec![
+            RestToken {
+                token: "read-token".to_owned(),
+                scopes: vec!["read-only".to_owned()],
+            },
+            RestToken {
+                token: "admin-token".to_owned(),
+                scopes: vec!["admin".to_owned()],
+            },
+        ])
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    let levels = jcli_wrapper::assert_rest_node_log_levels(&jormungandr_rest_address, "read-token");
+    assert_eq!(levels["default"].as_str(), Some("info"));
+    assert_eq!(levels["tasks"]["network"].as_str(), Some("debug"));
+
+    let levels = jcli_wrapper::assert_rest_node_log_levels_set(
+        &jormungandr_rest_address,
+        "admin-token",
+        &[("block", "trace")],
+        &["network"],
+    );
+    assert_eq!(levels["tasks"]["block"].as_str(), Some("trace"));
+    assert!(levels["tasks"]["network"].is_null());
+
+    let levels = jcli_wrapper::assert_rest_node_log_levels(&jormungandr_rest_address, "read-token");
+    assert_eq!(levels["tasks"]["block"].as_str(), Some("trace"));
+}
@@ -0,0 +1,22 @@
+use crate::common::jcli_wrapper;
+use crate::common::startup;
+
+#[test]
+pub fn test_stake_distribution_of_first_epoch_is_the_block0_one() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+    let distributions = jcli_wrapper::assert_rest_stake_distribution(&jormungandr_rest_address);
+
+    let first = distributions
+        .first()
+        .expect("no stake distribution reported");
+    assert_eq!(first["epoch"].as_u64(), Some(0));
+    assert!(
+        first["snapshot_epoch"].is_null(),
+        "the leaders of the first epoch are not elected with the block0 stake distribution"
+    );
+    assert!(first["unassigned"].as_u64().is_some());
+    assert!(first["dangling"].as_u64().is_some());
+    assert!(first["pools"].as_mapping().is_some());
+}
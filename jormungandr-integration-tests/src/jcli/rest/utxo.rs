@@ -57,3 +57,76 @@ pub fn test_correct_utxos_are_read_from_node() {
         content[1].associated_fund().to_string()
     );
 }
+
+#[test]
+pub fn test_utxos_are_filtered_and_paginated() {
+    let first_address = startup::create_new_utxo_address().address;
+    let second_address = startup::create_new_utxo_address().address;
+    let funds = vec![
+        Fund {
+            address: first_address.clone(),
+            value: 100.into(),
+        },
+        Fund {
+            address: second_address.clone(),
+            value: 200.into(),
+        },
+        Fund {
+            address: second_address.clone(),
+            value: 300.into(),
+        },
+    ];
+
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_funds(funds.clone())
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    let utxos = jcli_wrapper::assert_rest_utxo_get_filtered(
+        &jormungandr_rest_address,
+        Some(&second_address),
+        None,
+        None,
+    );
+    assert_eq!(utxos.len(), 2);
+    assert!(utxos
+        .iter()
+        .all(|utxo| utxo.address().to_string() == second_address));
+
+    let utxos = jcli_wrapper::assert_rest_utxo_get_filtered(
+        &jormungandr_rest_address,
+        None,
+        Some(250),
+        None,
+    );
+    assert_eq!(utxos.len(), 1);
+    assert_eq!(utxos[0].associated_fund().to_string(), "300");
+
+    let utxos =
+        jcli_wrapper::assert_rest_utxo_get_filtered(&jormungandr_rest_address, None, None, Some(1));
+    assert_eq!(utxos.len(), funds.len(), "all pages should be fetched");
+}
+
+#[test]
+pub fn test_utxos_are_read_at_block0() {
+    let address = startup::create_new_utxo_address().address;
+    let funds = vec![Fund {
+        address: address.clone(),
+        value: 100.into(),
+    }];
+    let mut config = startup::ConfigurationBuilder::new()
+        .with_funds(funds.clone())
+        .build();
+    let jormungandr_rest_address = config.get_node_address();
+    let block0_hash = config.genesis_block_hash.clone();
+    let _jormungandr = startup::start_jormungandr_node(&mut config);
+
+    let utxos = jcli_wrapper::assert_rest_utxo_get_at(&jormungandr_rest_address, &block0_hash);
+    assert_eq!(utxos.len(), funds.len());
+    assert_eq!(utxos[0].address().to_string(), address);
+
+    let utxos = jcli_wrapper::assert_rest_utxo_get_at_date(&jormungandr_rest_address, "0.0");
+    assert_eq!(utxos.len(), funds.len());
+    assert_eq!(utxos[0].address().to_string(), address);
+}
@@ -178,3 +178,54 @@ pub fn test_make_witness_with_wrong_transaction_id_hash_fails() {
         .assert_finalize()
         .assert_make_witness_fails(&witness, "invalid hex encoding for hash value");
 }
+
+#[test]
+pub fn test_witnesses_of_offline_signers_are_merged() {
+    let reciever = startup::create_new_utxo_address();
+
+    let mut transaction_wrapper = JCLITransactionWrapper::new_transaction(FAKE_GENESIS_HASH);
+    transaction_wrapper
+        .assert_add_input(&FAKE_INPUT_TRANSACTION_ID, &0, &100)
+        .assert_add_input(&FAKE_INPUT_TRANSACTION_ID, &1, &100)
+        .assert_add_output(&reciever.address, &200)
+        .assert_finalize();
+    let mut other_signer = transaction_wrapper.copy_staging();
+    assert_eq!(transaction_wrapper.assert_missing_witnesses().len(), 2);
+
+    let witness1 = transaction_wrapper.create_witness_default("utxo");
+    transaction_wrapper
+        .assert_make_witness(&witness1)
+        .assert_add_witness_at(&witness1, 0);
+    let witness2 = other_signer.create_witness_default("utxo");
+    other_signer
+        .assert_make_witness(&witness2)
+        .assert_add_witness_at(&witness2, 1);
+
+    let missing = transaction_wrapper.assert_missing_witnesses();
+    assert_eq!(missing.len(), 1);
+    assert!(missing[0].starts_with("1: "), "{:?}", missing);
+
+    transaction_wrapper
+        .assert_merge_witnesses(&[&other_signer])
+        .assert_seal();
+    assert!(transaction_wrapper.assert_missing_witnesses().is_empty());
+}
+
+#[test]
+pub fn test_witnesses_of_other_transaction_cannot_be_merged() {
+    let reciever = startup::create_new_utxo_address();
+
+    let mut transaction_wrapper = JCLITransactionWrapper::new_transaction(FAKE_GENESIS_HASH);
+    transaction_wrapper
+        .assert_add_input(&FAKE_INPUT_TRANSACTION_ID, &0, &100)
+        .assert_add_output(&reciever.address, &100)
+        .assert_finalize();
+    let mut other_transaction = JCLITransactionWrapper::new_transaction(FAKE_GENESIS_HASH);
+    other_transaction
+        .assert_add_input(&FAKE_INPUT_TRANSACTION_ID, &1, &100)
+        .assert_add_output(&reciever.address, &100)
+        .assert_finalize();
+
+    transaction_wrapper
+        .assert_merge_witnesses_fails(&[&other_transaction], "cannot merge witnesses");
+}
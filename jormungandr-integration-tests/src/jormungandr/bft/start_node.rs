@@ -64,6 +64,7 @@ pub fn test_jormungandr_with_wrong_logger_fails_to_start() {
         .with_logger(Logger {
             format: String::from("xml"),
             verbosity: 1,
+            tasks: None,
         })
         .build();
     startup::assert_start_jormungandr_node_as_passive_fail(
@@ -0,0 +1,98 @@
+use crate::common::configuration::node_config_model::{Mempool, RestLimits, RestToken};
+use crate::common::jormungandr::commands;
+use crate::common::process_assert;
+use crate::common::process_utils::{self, output_extensions::ProcessOutput};
+use crate::common::startup;
+use std::path::PathBuf;
+
+#[test]
+pub fn test_valid_config_passes_validation() {
+    let config = startup::ConfigurationBuilder::new().build();
+    let output = process_utils::run_process_and_get_output(commands::get_validate_config_command(
+        &config.node_config_path,
+        &config.genesis_block_path,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    assert!(
+        content.contains("the node configuration is valid"),
+        "unexpected output: {}",
+        content
+    );
+}
+
+#[test]
+pub fn test_validation_reports_all_errors() {
+    let config = startup::ConfigurationBuilder::new()
+        .with_mempool(Mempool {
+            max_fragments_per_block: Some(0),
+            ..Default::default()
+        })
+        .build();
+    let command = commands::get_validate_config_command(
+        &config.node_config_path,
+        &PathBuf::from("not-a-genesis-block.bin"),
+    );
+    let output = process_utils::run_process_and_get_output(command);
+    let errors = output.err_as_single_line();
+    process_assert::assert_process_failed(output);
+    for expected in &[
+        "`max_fragments_per_block' cannot be zero",
+        "Cannot read the genesis block",
+        "The node configuration has 2 error(s)",
+    ] {
+        assert!(
+            errors.contains(expected),
+            "'{}' does not contain '{}'",
+            errors,
+            expected
+        );
+    }
+}
+
+#[test]
+pub fn test_validation_rejects_zero_rest_limits() {
+    let config = startup::ConfigurationBuilder::new()
+        .with_rest_limits(RestLimits {
+            requests_per_minute_per_ip: Some(0),
+            ..Default::default()
+        })
+        .build();
+    let output = process_utils::run_process_and_get_output(commands::get_validate_config_command(
+        &config.node_config_path,
+        &config.genesis_block_path,
+    ));
+    let errors = output.err_as_single_line();
+    process_assert::assert_process_failed(output);
+    assert!(
+        errors.contains("`requests_per_minute_per_ip' cannot be zero"),
+        "unexpected errors: {}",
+        errors
+    );
+}
+
+#[test]
+pub fn test_printed_config_redacts_rest_tokens() {
+    let config = startup::ConfigurationBuilder::new()
+        .with_rest_tokens(vec![RestToken {
+            token: "secret-admin-token".to_owned(),
+            scopes: vec!["admin".to_owned()],
+        }])
+        .build();
+    let output = process_utils::run_process_and_get_output(commands::get_print_config_command(
+        &config.node_config_path,
+        &config.genesis_block_path,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    let printed: serde_yaml::Value = serde_yaml::from_str(&content).unwrap();
+    assert_eq!(
+        printed["rest"]["authentication"]["tokens"][0]["token"].as_str(),
+        Some("<redacted>")
+    );
+    assert!(!content.contains("secret-admin-token"));
+    assert_eq!(
+        printed["mempool"]["max_fragments_per_block"].as_u64(),
+        Some(250)
+    );
+}
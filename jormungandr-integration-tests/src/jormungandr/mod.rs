@@ -1,2 +1,4 @@
 pub mod bft;
+pub mod config;
 pub mod genesis;
+pub mod storage;
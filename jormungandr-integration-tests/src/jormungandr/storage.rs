@@ -0,0 +1,154 @@
+use crate::common::file_utils;
+use crate::common::jormungandr::commands;
+use crate::common::process_assert;
+use crate::common::process_utils::{self, output_extensions::ProcessOutput};
+use crate::common::startup;
+use std::path::PathBuf;
+
+#[test]
+pub fn test_storage_of_a_stopped_node_is_consistent() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let storage = PathBuf::from(config.node_config.storage.clone().unwrap());
+    let jormungandr = startup::start_jormungandr_node(&mut config);
+    process_utils::sleep(5);
+    std::mem::drop(jormungandr);
+
+    let output = process_utils::run_process_and_get_output(commands::get_verify_storage_command(
+        &storage,
+        &config.genesis_block_hash,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    for expected in &["blocks replayed:", "the storage is consistent"] {
+        assert!(
+            content.contains(expected),
+            "'{}' does not contain '{}'",
+            content,
+            expected
+        );
+    }
+}
+
+#[test]
+pub fn test_storage_of_another_blockchain_is_inconsistent() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let storage = PathBuf::from(config.node_config.storage.clone().unwrap());
+    let jormungandr = startup::start_jormungandr_node(&mut config);
+    std::mem::drop(jormungandr);
+
+    let other_genesis_block_hash = "0".repeat(64);
+    process_assert::assert_process_failed_and_contains_message(
+        commands::get_verify_storage_command(&storage, &other_genesis_block_hash),
+        "does not start with the genesis block",
+    );
+}
+
+#[test]
+pub fn test_verification_of_a_missing_storage_fails() {
+    let config = startup::ConfigurationBuilder::new().build();
+    let storage = file_utils::get_path_in_temp("no-storage");
+    process_assert::assert_process_failed_and_contains_message(
+        commands::get_verify_storage_command(&storage, &config.genesis_block_hash),
+        "does not exist",
+    );
+}
+
+#[test]
+pub fn test_exported_storage_is_imported_into_a_new_storage() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let storage = PathBuf::from(config.node_config.storage.clone().unwrap());
+    let jormungandr = startup::start_jormungandr_node(&mut config);
+    process_utils::sleep(5);
+    std::mem::drop(jormungandr);
+
+    let archive = file_utils::get_path_in_temp("blocks.archive");
+    process_assert::assert_process_exited_successfully(process_utils::run_process_and_get_output(
+        commands::get_export_storage_command(&storage, &archive),
+    ));
+
+    let new_storage = file_utils::get_path_in_temp("imported-storage");
+    let output = process_utils::run_process_and_get_output(commands::get_import_storage_command(
+        &new_storage,
+        &archive,
+        &config.genesis_block_hash,
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    assert!(
+        content.contains("blocks imported"),
+        "unexpected output: {}",
+        content
+    );
+
+    process_assert::assert_process_exited_successfully(process_utils::run_process_and_get_output(
+        commands::get_verify_storage_command(&new_storage, &config.genesis_block_hash),
+    ));
+}
+
+#[test]
+pub fn test_archive_of_another_blockchain_is_not_imported() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let storage = PathBuf::from(config.node_config.storage.clone().unwrap());
+    let jormungandr = startup::start_jormungandr_node(&mut config);
+    std::mem::drop(jormungandr);
+
+    let archive = file_utils::get_path_in_temp("blocks.archive");
+    process_assert::assert_process_exited_successfully(process_utils::run_process_and_get_output(
+        commands::get_export_storage_command(&storage, &archive),
+    ));
+
+    let new_storage = file_utils::get_path_in_temp("imported-storage");
+    let other_genesis_block_hash = "0".repeat(64);
+    process_assert::assert_process_failed_and_contains_message(
+        commands::get_import_storage_command(&new_storage, &archive, &other_genesis_block_hash),
+        "instead of",
+    );
+    assert!(
+        !new_storage.join("blocks.sqlite").exists(),
+        "the storage created for the import has not been removed"
+    );
+}
+
+#[test]
+pub fn test_file_which_is_not_an_archive_is_not_imported() {
+    let config = startup::ConfigurationBuilder::new().build();
+    let new_storage = file_utils::get_path_in_temp("imported-storage");
+    process_assert::assert_process_failed_and_contains_message(
+        commands::get_import_storage_command(
+            &new_storage,
+            &config.genesis_block_path,
+            &config.genesis_block_hash,
+        ),
+        "not a block archive",
+    );
+}
+
+#[test]
+pub fn test_storage_migrated_to_sled_is_consistent() {
+    let mut config = startup::ConfigurationBuilder::new().build();
+    let storage = PathBuf::from(config.node_config.storage.clone().unwrap());
+    let jormungandr = startup::start_jormungandr_node(&mut config);
+    process_utils::sleep(5);
+    std::mem::drop(jormungandr);
+
+    let output = process_utils::run_process_and_get_output(commands::get_migrate_storage_command(
+        &storage, "sled",
+    ));
+    let content = output.as_lossy_string();
+    process_assert::assert_process_exited_successfully(output);
+    assert!(
+        content.contains("blocks migrated from sqlite to sled"),
+        "unexpected output: {}",
+        content
+    );
+    assert!(storage.join("blocks.sled").is_dir());
+    assert!(!storage.join("blocks.sqlite").exists());
+
+    process_assert::assert_process_exited_successfully(process_utils::run_process_and_get_output(
+        commands::get_verify_storage_command(&storage, &config.genesis_block_hash),
+    ));
+    process_assert::assert_process_failed_and_contains_message(
+        commands::get_migrate_storage_command(&storage, "sled"),
+        "already exists",
+    );
+}
@@ -154,6 +154,32 @@ impl BlockchainConfiguration {
         }
     }
 
+    /// the address discrimination (test or production) of the blockchain
+    #[inline]
+    pub fn discrimination(&self) -> Discrimination {
+        self.discrimination
+    }
+
+    /// the consensus in use from the block 0
+    #[inline]
+    pub fn block0_consensus(&self) -> ConsensusVersion {
+        self.block0_consensus
+    }
+
+    /// the list of the consensus leaders of the block 0
+    #[inline]
+    pub fn consensus_leader_ids(&self) -> &[ConsensusLeaderId] {
+        &self.consensus_leader_ids
+    }
+
+    /// add a consensus leader to the block 0, ignoring it if it is
+    /// already part of the consensus leaders
+    pub fn add_consensus_leader_id(&mut self, consensus_leader_id: ConsensusLeaderId) {
+        if !self.consensus_leader_ids.contains(&consensus_leader_id) {
+            self.consensus_leader_ids.push(consensus_leader_id)
+        }
+    }
+
     fn from_config_params(params: ConfigParams) -> Result<Self, FromConfigParamsError> {
         fn param_missing_error(name: &'static str) -> FromConfigParamsError {
             FromConfigParamsError::InitConfigParamMissing { name }
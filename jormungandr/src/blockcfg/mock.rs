@@ -0,0 +1,41 @@
+//! chains of empty blocks for the tests
+
+use super::{Block, BlockBuilder, BlockDate};
+use chain_core::property::{Block as _, ChainLength as _};
+
+/// a block 0 at the date `0.0`
+pub fn block_0() -> Block {
+    let mut builder = BlockBuilder::new();
+    builder.date(BlockDate {
+        epoch: 0,
+        slot_id: 0,
+    });
+    builder.make_genesis_block()
+}
+
+/// the builder of a block following the given parent at the given date,
+/// to sign as the test needs
+pub fn builder_after(parent: &Block, date: BlockDate) -> BlockBuilder {
+    let mut builder = BlockBuilder::new();
+    builder
+        .date(date)
+        .parent(parent.id())
+        .chain_length(parent.chain_length().next());
+    builder
+}
+
+/// an unsigned block following the given parent at the given date
+pub fn block_after(parent: &Block, date: BlockDate) -> Block {
+    builder_after(parent, date).make_genesis_block()
+}
+
+/// a chain of the given number of blocks, the block 0 first, then a block
+/// in each of the following slots of the epoch 0
+pub fn chain(length: u32) -> Vec<Block> {
+    let mut blocks = vec![block_0()];
+    for slot_id in 1..length {
+        let block = block_after(blocks.last().unwrap(), BlockDate { epoch: 0, slot_id });
+        blocks.push(block);
+    }
+    blocks
+}
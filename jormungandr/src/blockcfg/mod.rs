@@ -1,3 +1,6 @@
+#[cfg(test)]
+pub mod mock;
+
 use chain_core::property::HasMessages as _;
 pub use network_core::gossip::Gossip;
 
@@ -15,6 +18,10 @@ pub use chain_impl_mockchain::{
 };
 use std::time::{Duration, SystemTime};
 
+/// the number of blocks a fork may roll back when the block0 does not set
+/// `epoch_stability_depth'
+pub const DEFAULT_EPOCH_STABILITY_DEPTH: u32 = 102_400;
+
 custom_error! {pub Block0Error
     CannotParseEntity{source: config::Error} = "Block0 Initial settings: {source}",
     Malformed{source: Block0Malformed} = "Block0 is invalid or malformed: {source}"
@@ -32,6 +39,7 @@ pub trait Block0DataSource {
     fn slot_duration(&self) -> Result<Duration, Block0Error>;
     fn slots_per_epoch(&self) -> Result<u32, Block0Error>;
     fn start_time(&self) -> Result<SystemTime, Block0Error>;
+    fn epoch_stability_depth(&self) -> Result<u32, Block0Error>;
 }
 
 impl Block0DataSource for Block {
@@ -61,6 +69,15 @@ impl Block0DataSource for Block {
         }
         Err(Block0Malformed::NoStartTime.into())
     }
+
+    fn epoch_stability_depth(&self) -> Result<u32, Block0Error> {
+        for config in initial(self)?.iter() {
+            if let ConfigParam::EpochStabilityDepth(depth) = config {
+                return Ok(*depth);
+            }
+        }
+        Ok(DEFAULT_EPOCH_STABILITY_DEPTH)
+    }
 }
 
 fn initial(block: &Block) -> Result<&ConfigParams, Block0Malformed> {
@@ -0,0 +1,234 @@
+//! Portable archive of the blocks of a storage, to move a blockchain
+//! between storages or to bootstrap a node from a file.
+//!
+//! The archive is written and read as a stream:
+//!
+//! ```text
+//! magic     8 bytes, "JORBLOCK"
+//! manifest  u32 length, big endian, followed by the manifest in JSON
+//! blocks    for every block listed in the manifest, in the same order:
+//!           u32 length, big endian, followed by the serialized block
+//! ```
+
+use crate::{
+    blockcfg::{Block, HeaderHash},
+    blockchain::{
+        verify::{chain_from_block_0, Replay},
+        Inconsistency, LOCAL_BLOCKCHAIN_TIP_TAG,
+    },
+    start_up::NodeStorage,
+};
+use chain_core::property::{Block as _, Deserialize as _, Serialize as _};
+use chain_storage::error::Error as StorageError;
+use jormungandr_lib::crypto::hash::Hash;
+use std::io::{self, Read, Write};
+
+const ARCHIVE_MAGIC: &[u8; 8] = b"JORBLOCK";
+pub const ARCHIVE_VERSION: u32 = 1;
+
+custom_error! {pub Error
+    Io { source: io::Error } = "Cannot read or write the archive: {source}",
+    Storage { source: StorageError } = "Error in the blockchain storage: {source}",
+    Manifest { source: serde_json::Error } = "Invalid archive manifest: {source}",
+    NotAnArchive = "The file is not a block archive",
+    UnsupportedVersion { version: u32 } = "Unsupported archive version {version}",
+    Empty = "The archive holds no block",
+    Truncated = "The archive is truncated",
+    TrailingData = "The archive holds more data than the blocks of its manifest",
+    NoTip = "The storage holds no blockchain tip",
+    UnknownBlock { hash: HeaderHash } = "The block {hash} is not in the storage",
+    NotAnAncestor { from: HeaderHash, to: HeaderHash } = "The block {from} is not an ancestor of the block {to}",
+    Inconsistent { inconsistency: Inconsistency } = "The storage is inconsistent at the {inconsistency}",
+    MalformedBlock { hash: HeaderHash } = "The archived block {hash} cannot be decoded",
+    BlockMismatch { expected: HeaderHash, found: HeaderHash } = "The archive holds the block {found} where its manifest lists {expected}",
+    Invalid { inconsistency: Inconsistency } = "The archive holds an invalid {inconsistency}",
+    Disconnected { hash: HeaderHash, parent: HeaderHash } = "The archived block {hash} does not follow the block {parent}",
+    Block0Mismatch { expected: HeaderHash, found: HeaderHash } = "The archive is of the blockchain of the genesis block {found} instead of {expected}",
+    NotFromBlock0 = "The archive does not start with the genesis block, it can only be imported on top of an existing storage",
+}
+
+/// the first entry of an archive, listing the archived blocks
+#[derive(Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    /// the genesis block of the blockchain the blocks are part of
+    pub block0_id: Hash,
+    /// the hashes of the archived blocks, in chronological order
+    pub blocks: Vec<Hash>,
+}
+
+/// the outcome of an import
+pub struct Imported {
+    pub manifest: Manifest,
+}
+
+/// write the blocks of the tip's branch to the archive, from the block
+/// `from` (the block 0 if not set) to the block `to` (the tip if not set)
+pub fn export<W: Write>(
+    storage: &NodeStorage,
+    from: Option<&HeaderHash>,
+    to: Option<&HeaderHash>,
+    mut output: W,
+) -> Result<Manifest, Error> {
+    let to = match to {
+        Some(to) => to.clone(),
+        None => storage
+            .get_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?
+            .ok_or(Error::NoTip)?,
+    };
+    for hash in from.into_iter().chain(Some(&to)) {
+        if !storage.block_exists(hash)? {
+            return Err(Error::UnknownBlock { hash: hash.clone() });
+        }
+    }
+    let chain = chain_from_block_0(storage, &to, None)
+        .map_err(|inconsistency| Error::Inconsistent { inconsistency })?;
+    let start = match from {
+        None => 0,
+        Some(from) => {
+            chain
+                .iter()
+                .position(|hash| hash == from)
+                .ok_or_else(|| Error::NotAnAncestor {
+                    from: from.clone(),
+                    to: to.clone(),
+                })?
+        }
+    };
+
+    let manifest = Manifest {
+        version: ARCHIVE_VERSION,
+        block0_id: chain[0].clone().into(),
+        blocks: chain[start..].iter().cloned().map(Hash::from).collect(),
+    };
+    output.write_all(ARCHIVE_MAGIC)?;
+    write_entry(&mut output, &serde_json::to_vec(&manifest)?)?;
+    for hash in &chain[start..] {
+        let (block, _) = storage.get_block(hash)?;
+        write_entry(&mut output, &block.serialize_as_vec()?)?;
+    }
+    output.flush()?;
+    Ok(manifest)
+}
+
+/// add the blocks of the archive to the storage, checking they are the
+/// ones of the manifest and that they are chained. An empty storage
+/// needs the archive to start with the block 0, otherwise the archive
+/// must start with the block following the storage's tip.
+///
+/// Every block is verified before it is stored: its leader is checked
+/// and it is applied to the ledger replayed from the block 0, the chain
+/// of the storage's tip being replayed first. The tip of the storage is
+/// only moved to the last archived block once all of them are stored,
+/// a failed import leaves the tip where it was.
+pub fn import<R: Read>(
+    storage: &mut NodeStorage,
+    block_0_id: Option<&HeaderHash>,
+    mut input: R,
+) -> Result<Imported, Error> {
+    let mut magic = [0; 8];
+    input
+        .read_exact(&mut magic)
+        .map_err(|_| Error::NotAnArchive)?;
+    if &magic != ARCHIVE_MAGIC {
+        return Err(Error::NotAnArchive);
+    }
+    let manifest: Manifest = serde_json::from_slice(&read_entry(&mut input)?)?;
+    if manifest.version != ARCHIVE_VERSION {
+        return Err(Error::UnsupportedVersion {
+            version: manifest.version,
+        });
+    }
+    let archive_block0_id = manifest.block0_id.into_hash();
+    if let Some(expected) = block_0_id {
+        if *expected != archive_block0_id {
+            return Err(Error::Block0Mismatch {
+                expected: expected.clone(),
+                found: archive_block0_id,
+            });
+        }
+    }
+    let first = manifest.blocks.first().ok_or(Error::Empty)?.into_hash();
+    let last = manifest.blocks.last().ok_or(Error::Empty)?.into_hash();
+
+    let tip = storage.get_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?;
+    let (mut parent, mut replay) = match &tip {
+        None if first != archive_block0_id => return Err(Error::NotFromBlock0),
+        None => (None, None),
+        Some(tip) => {
+            if !storage.block_exists(&archive_block0_id)? {
+                return Err(Error::UnknownBlock {
+                    hash: archive_block0_id,
+                });
+            }
+            let replay = chain_from_block_0(storage, tip, Some(&archive_block0_id))
+                .and_then(|chain| Replay::from_storage(storage, &chain))
+                .map_err(|inconsistency| Error::Inconsistent { inconsistency })?;
+            (Some(tip.clone()), Some(replay))
+        }
+    };
+
+    for expected in manifest.blocks.iter().map(|hash| hash.into_hash()) {
+        let bytes = read_entry(&mut input)?;
+        let block = Block::deserialize(bytes.as_slice()).map_err(|_| Error::MalformedBlock {
+            hash: expected.clone(),
+        })?;
+        if block.id() != expected {
+            return Err(Error::BlockMismatch {
+                expected,
+                found: block.id(),
+            });
+        }
+        if let Some(parent) = parent {
+            if block.parent_id() != parent {
+                return Err(Error::Disconnected {
+                    hash: block.id(),
+                    parent,
+                });
+            }
+        }
+        match &mut replay {
+            None => replay = Some(Replay::start(&block).map_err(invalid)?),
+            Some(replay) => replay.apply(&block).map_err(invalid)?,
+        }
+        storage.put_block(&block)?;
+        parent = Some(block.id());
+    }
+    if input.read(&mut [0])? != 0 {
+        return Err(Error::TrailingData);
+    }
+
+    storage.put_tag(LOCAL_BLOCKCHAIN_TIP_TAG, &last)?;
+    Ok(Imported { manifest })
+}
+
+fn write_entry<W: Write>(output: &mut W, bytes: &[u8]) -> Result<(), Error> {
+    output.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    output.write_all(bytes)?;
+    Ok(())
+}
+
+/// the entries are read without trusting their length prefix for the
+/// allocation, a corrupted length only leads to a truncated entry
+fn read_entry<R: Read>(input: &mut R) -> Result<Vec<u8>, Error> {
+    let mut length = [0; 4];
+    input.read_exact(&mut length).map_err(truncated)?;
+    let length = u32::from_be_bytes(length) as u64;
+    let mut bytes = Vec::new();
+    input.by_ref().take(length).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return Err(Error::Truncated);
+    }
+    Ok(bytes)
+}
+
+fn invalid(inconsistency: Inconsistency) -> Error {
+    Error::Invalid { inconsistency }
+}
+
+fn truncated(error: io::Error) -> Error {
+    match error.kind() {
+        io::ErrorKind::UnexpectedEof => Error::Truncated,
+        _ => Error::Io { source: error },
+    }
+}
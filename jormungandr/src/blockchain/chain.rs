@@ -1,11 +1,14 @@
 use crate::{
-    blockcfg::{Block, Epoch, Header, HeaderHash, Ledger, Multiverse},
-    blockchain::{Branch, Tip, TipGetError, TipReplaceError},
+    blockcfg::{Block, BlockDate, Epoch, Header, HeaderHash, Ledger, Message, Multiverse},
+    blockchain::{Branch, Tip, TipGetError, TipReplaceError, UnconnectedBlocks},
     leadership::{EpochParameters, Leadership, Leaderships},
     start_up::NodeStorage,
+    storage::{Flusher, Pruner},
     utils::borrow::Borrow,
 };
-use chain_core::property::{Block as _, HasHeader as _, HasMessages as _, Header as _};
+use chain_core::property::{
+    Block as _, HasHeader as _, HasMessages as _, Header as _, Message as _,
+};
 use chain_impl_mockchain::{
     leadership::{self, Verification},
     ledger, multiverse,
@@ -14,13 +17,14 @@ use chain_storage::error::Error as StorageError;
 use chain_storage::store::{self, BlockInfo};
 use chain_time::{SlotDuration, TimeFrame, Timeline};
 use slog::Logger;
-use std::collections::BTreeMap;
-use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::collections::HashSet;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::SystemTime;
 use tokio::sync::mpsc;
 
 pub struct Blockchain {
     /// the storage for the overall blockchains (blocks)
-    pub storage: Arc<RwLock<NodeStorage>>,
+    pub storage: StorageR,
 
     pub multiverse: Multiverse<Ledger>,
 
@@ -33,12 +37,25 @@ pub struct Blockchain {
 
     pub epoch_event: mpsc::Sender<EpochParameters>,
 
-    /// Incoming blocks whose parent does not exist yet. Sorted by
-    /// parent hash to allow quick look up of the children of a
-    /// parent.
-    ///
-    /// FIXME: need some way to GC unconnected blocks after a while.
-    pub unconnected_blocks: BTreeMap<HeaderHash, BTreeMap<HeaderHash, Block>>,
+    /// the maximum number of blocks of the tip's branch a fork may
+    /// roll back, the `epoch_stability_depth' of the block0
+    pub stability_depth: u64,
+
+    pub slots_per_epoch: u32,
+
+    /// Incoming blocks whose parent does not exist yet, processed
+    /// as soon as their parent is acquired.
+    pub unconnected_blocks: UnconnectedBlocks,
+
+    /// deletes the old blocks of a pruned storage when the tip enters a
+    /// new epoch, `None` if the storage keeps every block
+    pub pruner: Option<Pruner>,
+
+    /// makes the writes of the storage durable when the tip is saved,
+    /// `None` if the storage writes them synchronously
+    pub flusher: Option<Flusher>,
+
+    logger: Logger,
 }
 
 #[derive(Clone)]
@@ -49,25 +66,45 @@ impl BlockchainR {
     ///
     /// In the background we are utilising a RwLock. This allows for
     /// multiple Reader to access the blockchain at the same time.
+    ///
+    /// A service panicking while holding the lock poisons it, the lock is
+    /// recovered so the restarted service and the REST API carry on with
+    /// the blockchain as the service left it.
     #[inline]
     pub fn lock_read(&self) -> RwLockReadGuard<Blockchain> {
-        match self.0.read() {
-            Ok(r) => r,
-            Err(err) => panic!("BlockchainR lock is poisoned: {}", err),
-        }
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
     }
 
     /// lock the blockchain for write access purpose.
     ///
     /// In the background we are utilising a RwLock. This will require
     /// that the multiple reads terminate to acquire the lock for
-    /// write purpose (preventing concurrent read)
+    /// write purpose (preventing concurrent read). A poisoned lock is
+    /// recovered as by `lock_read`.
     #[inline]
     pub fn lock_write(&self) -> RwLockWriteGuard<Blockchain> {
-        match self.0.write() {
-            Ok(r) => r,
-            Err(err) => panic!("BlockchainR lock is poisoned: {}", err),
-        }
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// the block storage, shared by the blockchain and the tasks reading it
+/// without holding the blockchain lock
+#[derive(Clone)]
+pub struct StorageR(Arc<RwLock<NodeStorage>>);
+
+impl StorageR {
+    /// lock the storage for reading, a poisoned lock is recovered as by
+    /// `BlockchainR::lock_read`, each write of the storage being complete
+    /// or not done at all
+    #[inline]
+    pub fn read(&self) -> RwLockReadGuard<NodeStorage> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// lock the storage for writing, a poisoned lock is recovered
+    #[inline]
+    pub fn write(&self) -> RwLockWriteGuard<NodeStorage> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
@@ -80,12 +117,99 @@ impl From<Blockchain> for BlockchainR {
 // FIXME: copied from cardano-cli
 pub const LOCAL_BLOCKCHAIN_TIP_TAG: &'static str = "tip";
 
+/// number of slots a block may be ahead of the node's clock, the clocks of
+/// the nodes drifting apart
+const FUTURE_SLOTS_TOLERANCE: u64 = 1;
+
 custom_error! {pub LoadError
     Storage{source: StorageError} = "Error in the blockchain storage: {source}",
     Ledger{source: ledger::Error} = "Invalid blockchain state: {source}",
     Block0 { source: crate::blockcfg::Block0Error } = "Initial setting of the blockchain are invalid",
 }
 
+custom_error! {pub QueryError
+    Storage{source: StorageError} = "Error in the blockchain storage: {source}",
+    TipAccess{source: TipGetError} = "Cannot access the blockchain's TIP",
+    MissingLedgerState{block: HeaderHash} = "No ledger state for the block {block}",
+}
+
+custom_error! {pub LedgerReplayError
+    Storage{source: StorageError} = "Error in the blockchain storage: {source}",
+    Ledger{source: ledger::Error} = "Invalid blockchain state: {source}",
+    TooDeep{block: HeaderHash, max_depth: u64} = "The ledger state of the block {block} is more than {max_depth} blocks after a state held by the node",
+}
+
+/// The ledger state of a block, possibly still to be reconstructed by
+/// applying the blocks following the nearest state held in the multiverse.
+///
+/// The replay does not need the blockchain lock, only the storage.
+pub struct LedgerReplay {
+    base: ReplayBase,
+    /// the blocks to apply on top of `base`, in chronological order
+    blocks: Vec<HeaderHash>,
+    storage: StorageR,
+}
+
+/// the ledger state the blocks of a replay are applied on
+enum ReplayBase {
+    Ledger(Ledger),
+    /// the state created by the block 0, not held in the multiverse
+    Block0(HeaderHash),
+}
+
+impl LedgerReplay {
+    pub fn replay(self) -> Result<Ledger, LedgerReplayError> {
+        let mut state = match self.base {
+            ReplayBase::Ledger(ledger) => ledger,
+            ReplayBase::Block0(block_0_id) => {
+                let (block_0, _) = self.storage.read().get_block(&block_0_id)?;
+                Ledger::new(block_0_id, block_0.messages())?
+            }
+        };
+        for block_hash in self.blocks {
+            let (block, _) = self.storage.read().get_block(&block_hash)?;
+            let parameters = state.get_ledger_parameters();
+            state = state.apply_block(
+                &parameters,
+                block.messages(),
+                &block.header.to_content_eval_context(),
+            )?;
+        }
+        Ok(state)
+    }
+}
+
+/// the ancestor at the given depth of a block, going back with the longest
+/// back links not passing it
+fn ancestor_at_depth(
+    storage: &StorageR,
+    block_info: &BlockInfo<HeaderHash>,
+    depth: u64,
+) -> Result<BlockInfo<HeaderHash>, StorageError> {
+    let mut current = block_info.clone();
+    while current.depth > depth {
+        let remaining = current.depth - depth;
+        let back_link = current
+            .back_links
+            .iter()
+            .filter(|back_link| back_link.distance <= remaining)
+            .max_by_key(|back_link| back_link.distance)
+            .ok_or(StorageError::BlockNotFound)?;
+        current = storage.read().get_block_info(&back_link.block_hash)?;
+    }
+    Ok(current)
+}
+
+/// the parent of a block which is not the block 0
+fn parent_id(block_info: &BlockInfo<HeaderHash>) -> Result<HeaderHash, StorageError> {
+    block_info
+        .back_links
+        .iter()
+        .find(|back_link| back_link.distance == 1)
+        .map(|back_link| back_link.block_hash.clone())
+        .ok_or(StorageError::BlockNotFound)
+}
+
 impl Blockchain {
     pub fn load(
         block_0: Block,
@@ -98,6 +222,8 @@ impl Blockchain {
 
         let start_time = block_0.start_time()?;
         let slot_duration = block_0.slot_duration()?;
+        let stability_depth = block_0.epoch_stability_depth()? as u64;
+        let slots_per_epoch = block_0.slots_per_epoch()?;
 
         let time_frame = TimeFrame::new(
             Timeline::new(start_time),
@@ -115,8 +241,7 @@ impl Blockchain {
                 let mut state = Ledger::new(block_0_id, block_0.messages())?;
 
                 let mut epoch = block_0.date().epoch;
-                let initial_leadership = Leadership::new(epoch, &state);
-                let mut leaderships = Leaderships::new(&block_0.header, initial_leadership);
+                let mut leaderships = Leaderships::new(&block_0.header, &state);
 
                 // FIXME: should restore from serialized chain state once we have it.
                 info!(logger, "restoring state from block0 {}", block_0_id);
@@ -125,6 +250,11 @@ impl Blockchain {
                     let parameters = state.get_ledger_parameters();
                     let block = &storage.get_block(&info.block_hash)?.0;
                     let block_header = &block.header;
+                    if block_header.date().epoch > epoch {
+                        // the state before the block is the one at the end
+                        // of the previous epoch
+                        leaderships.snapshot(epoch, state.clone());
+                    }
                     state = state.apply_block(
                         &parameters,
                         block.messages(),
@@ -133,7 +263,9 @@ impl Blockchain {
                     let gc_root = multiverse.add(info.block_hash.clone(), state.clone());
                     if block_header.date().epoch > epoch {
                         epoch = block_header.date().epoch;
-                        let leadership = Leadership::new(block_header.date().epoch, &state);
+                        leaderships.prune_snapshots(epoch);
+                        let (_, stake_snapshot) = leaderships.stake_snapshot(epoch);
+                        let leadership = Leadership::new(epoch, stake_snapshot);
                         let _gc_root = leaderships.add(
                             block_header.date().epoch,
                             block_header.chain_length(),
@@ -152,9 +284,8 @@ impl Blockchain {
             } else {
                 let state = Ledger::new(block_0.id(), block_0.messages())?;
                 storage.put_block(&block_0)?;
-                let initial_leadership = Leadership::new(block_0.date().epoch, &state);
                 let tip = multiverse.add(block_0.id(), state.clone());
-                let leaderships = Leaderships::new(&block_0.header, initial_leadership);
+                let leaderships = Leaderships::new(&block_0.header, &state);
                 let tip = Tip::new(Branch::new(tip, block_0.header.chain_length(), state));
 
                 (tip, leaderships)
@@ -163,19 +294,24 @@ impl Blockchain {
         multiverse.gc();
 
         Ok(Blockchain {
-            storage: Arc::new(RwLock::new(storage)),
+            storage: StorageR(Arc::new(RwLock::new(storage))),
             multiverse,
             leaderships,
             tip,
-            unconnected_blocks: BTreeMap::default(),
+            unconnected_blocks: UnconnectedBlocks::default(),
+            pruner: None,
+            flusher: None,
             epoch_event,
+            stability_depth,
+            slots_per_epoch,
             time_frame,
+            logger: logger.clone(),
         })
     }
 
-    pub fn initial(&mut self) -> Result<(), StorageError> {
+    pub fn initial(&mut self) -> Result<(), QueryError> {
         let (_block, block_info) = self.get_block_tip()?;
-        let state = self.get_ledger(&block_info.block_hash).unwrap().clone();
+        let state = self.get_tip_ledger(&block_info.block_hash)?.clone();
         let slot = self
             .time_frame
             .slot_at(&std::time::SystemTime::now())
@@ -201,21 +337,144 @@ impl Blockchain {
         self.multiverse.get(hash)
     }
 
+    /// the ledger state of a block of the tip's branch, which is always
+    /// held in the multiverse
+    fn get_tip_ledger(&self, hash: &HeaderHash) -> Result<&Ledger, QueryError> {
+        self.get_ledger(hash)
+            .ok_or_else(|| QueryError::MissingLedgerState {
+                block: hash.clone(),
+            })
+    }
+
+    /// prepare the reconstruction of the ledger state of the given block
+    ///
+    /// if the state is not held in the multiverse anymore, the state will
+    /// be replayed from the nearest ancestor whose state is still held, or
+    /// from the block 0. Only the block infos are read, and at most
+    /// `max_blocks` blocks are walked back.
+    pub fn ledger_replay(
+        &self,
+        hash: &HeaderHash,
+        max_blocks: u64,
+    ) -> Result<LedgerReplay, LedgerReplayError> {
+        let storage = self.storage.read();
+        let mut blocks = Vec::new();
+        let mut current = hash.clone();
+        let base = loop {
+            if let Some(ledger) = self.get_ledger(&current) {
+                break ReplayBase::Ledger(ledger.clone());
+            }
+            let block_info = storage.get_block_info(&current)?;
+            if block_info.depth == 0 {
+                break ReplayBase::Block0(current);
+            }
+            if blocks.len() as u64 >= max_blocks {
+                return Err(LedgerReplayError::TooDeep {
+                    block: hash.clone(),
+                    max_depth: max_blocks,
+                });
+            }
+            blocks.push(current);
+            current = parent_id(&block_info)?;
+        };
+        blocks.reverse();
+        Ok(LedgerReplay {
+            base,
+            blocks,
+            storage: self.storage.clone(),
+        })
+    }
+
+    /// find the last block of the tip's branch whose date is not after the
+    /// given date. Returns `None` if the date is before the block 0.
+    ///
+    /// The whole branch is searched by depth, going back with the back links
+    /// of the block infos and reading the blocks of a binary search only. It
+    /// only needs the storage, the tip being given, and is called without
+    /// holding the blockchain lock.
+    pub fn find_block_at_date(
+        storage: &StorageR,
+        tip: &HeaderHash,
+        date: BlockDate,
+    ) -> Result<Option<HeaderHash>, QueryError> {
+        let is_after_date = |block_hash: &HeaderHash| -> Result<bool, StorageError> {
+            let (block, _) = storage.read().get_block(block_hash)?;
+            Ok(block.date() > date)
+        };
+        // the oldest block known to be after the date and the newest block
+        // known not to be, the blocks between them are still to search
+        let mut after = storage.read().get_block_info(tip)?;
+        if !is_after_date(&after.block_hash)? {
+            return Ok(Some(after.block_hash));
+        }
+        let mut not_after: Option<BlockInfo<HeaderHash>> = None;
+        loop {
+            let from = not_after.as_ref().map_or(0, |info| info.depth + 1);
+            if from >= after.depth {
+                return Ok(not_after.map(|info| info.block_hash));
+            }
+            let middle = from + (after.depth - from) / 2;
+            let info = ancestor_at_depth(storage, &after, middle)?;
+            if is_after_date(&info.block_hash)? {
+                after = info;
+            } else {
+                not_after = Some(info);
+            }
+        }
+    }
+
+    /// the date of the current slot according to the node's clock, `None`
+    /// before the start of the blockchain
+    pub fn current_date(&self) -> Option<BlockDate> {
+        let slot = self.time_frame.slot_at(&SystemTime::now())?;
+        let position = self.leaderships.latest()?.era().from_slot_to_era(slot)?;
+        Some(BlockDate {
+            epoch: position.epoch.0,
+            slot_id: position.slot.0,
+        })
+    }
+
+    /// tell whether the given date is too far ahead of the node's clock
+    /// for a block to be accepted
+    fn is_in_future(&self, date: &BlockDate) -> bool {
+        let slots_per_epoch = self.slots_per_epoch as u64;
+        let slot = |date: &BlockDate| date.epoch as u64 * slots_per_epoch + date.slot_id as u64;
+        match self.current_date() {
+            Some(now) => slot(date) > slot(&now) + FUTURE_SLOTS_TOLERANCE,
+            None => true,
+        }
+    }
+
     /// return the current tip hash and date
     pub fn get_tip(&self) -> Result<HeaderHash, TipGetError> {
         self.tip.hash()
     }
 
-    pub fn get_block_tip(&self) -> Result<(Block, BlockInfo<HeaderHash>), StorageError> {
-        self.get_block(&self.get_tip().unwrap())
+    pub fn get_block_tip(&self) -> Result<(Block, BlockInfo<HeaderHash>), QueryError> {
+        let tip = self.get_tip()?;
+        Ok(self.get_block(&tip)?)
     }
 
     pub fn put_block(&mut self, block: &Block) -> Result<(), StorageError> {
-        self.storage.write().unwrap().put_block(block)
+        self.storage.write().put_block(block)
+    }
+
+    /// write the current tip to the storage and make it durable, so the
+    /// node restarts from it even if the last update of the tip did not
+    /// complete
+    pub fn flush(&mut self) -> Result<(), QueryError> {
+        let tip = self.get_tip()?;
+        self.storage
+            .write()
+            .put_tag(LOCAL_BLOCKCHAIN_TIP_TAG, &tip)?;
+        if let Some(flusher) = &self.flusher {
+            flusher.flush()?;
+        }
+        Ok(())
     }
 
     pub fn put_tip(&mut self, branch: Branch, block: &Block) -> Result<(), HandleBlockError> {
-        let mut storage = self.storage.write().unwrap();
+        let mut storage = self.storage.write();
         storage.put_block(block)?;
         storage.put_tag(LOCAL_BLOCKCHAIN_TIP_TAG, &block.id())?;
         self.tip.replace_with(branch)?;
@@ -226,7 +485,28 @@ impl Blockchain {
         &self,
         hash: &HeaderHash,
     ) -> Result<(Block, BlockInfo<HeaderHash>), StorageError> {
-        self.storage.read().unwrap().get_block(hash)
+        self.storage.read().get_block(hash)
+    }
+
+    /// delete the bodies of the blocks older than the epochs kept by the
+    /// pruning of the storage, if any. The storage is locked for the
+    /// duration, a range of blocks is not pruned while being read.
+    ///
+    /// The tip is already stored: a failure is only logged, the blocks
+    /// are pruned again when the tip enters the next epoch.
+    fn prune(&self, tip: &HeaderHash, tip_epoch: Epoch) {
+        if let Some(pruner) = &self.pruner {
+            let _storage = self.storage.write();
+            if let Err(error) = pruner.prune(tip, tip_epoch) {
+                warn!(
+                    self.logger,
+                    "cannot prune the blocks older than the epoch {} of the tip {}: {}",
+                    tip_epoch,
+                    tip,
+                    error
+                );
+            }
+        }
     }
 
     fn block_exists(&self, block_hash: &HeaderHash) -> Result<bool, StorageError> {
@@ -235,7 +515,7 @@ impl Blockchain {
         // sure that this invariant is preserved everywhere
         // (e.g. loose block GC should delete blocks in reverse
         // order).
-        self.storage.read().unwrap().block_exists(block_hash)
+        self.storage.read().block_exists(block_hash)
     }
 
     /// get the leadership for the given epoch or build a new one
@@ -256,9 +536,13 @@ impl Blockchain {
             .or_else(|| self.build_leadership(epoch, parent_hash).map(Borrow::Owned))
     }
 
+    /// the leaders are elected with the stake distribution of the snapshot
+    /// lagging behind the epoch, see [`STAKE_SNAPSHOT_LAG`]
+    ///
+    /// [`STAKE_SNAPSHOT_LAG`]: ../leadership/constant.STAKE_SNAPSHOT_LAG.html
     pub fn build_leadership(&self, epoch: Epoch, parent_hash: &HeaderHash) -> Option<Leadership> {
         self.get_ledger(parent_hash)
-            .map(|ledger| Leadership::new(epoch, ledger))
+            .map(|_| Leadership::new(epoch, self.leaderships.stake_snapshot(epoch).1))
     }
 
     pub fn get_leadership<'a>(&'a self, epoch: Epoch) -> Option<Borrow<'a, Leadership>> {
@@ -268,15 +552,100 @@ impl Blockchain {
             .map(|leadership| leadership.1.into())
     }
 
+    /// find where the branch of the block `to` forks from the branch of
+    /// the block `from`, walking back the branches in the storage. Only
+    /// the block infos are read, the bodies of the blocks may have been
+    /// pruned.
+    ///
+    /// Returns `None` if more than `max_rollback` blocks of the branch of
+    /// `from` would have to be rolled back to switch to the branch of `to`.
+    pub fn find_fork(
+        &self,
+        from: &HeaderHash,
+        to: &HeaderHash,
+        max_rollback: u64,
+    ) -> Result<Option<Fork>, StorageError> {
+        let storage = self.storage.read();
+        let mut from_info = storage.get_block_info(from)?;
+        let mut to_info = storage.get_block_info(to)?;
+        let mut rolled_back = Vec::new();
+        let mut applied = Vec::new();
+
+        while from_info.block_hash != to_info.block_hash {
+            if from_info.depth >= to_info.depth {
+                if rolled_back.len() as u64 >= max_rollback {
+                    return Ok(None);
+                }
+                rolled_back.push(from_info.block_hash.clone());
+                from_info = storage.get_block_info(&parent_id(&from_info)?)?;
+            } else {
+                applied.push(to_info.block_hash.clone());
+                to_info = storage.get_block_info(&parent_id(&to_info)?)?;
+            }
+        }
+        applied.reverse();
+
+        Ok(Some(Fork {
+            fork_point: from_info.block_hash,
+            rolled_back,
+            applied,
+        }))
+    }
+
+    /// replace the snapshots of the epochs ended after the fork point by
+    /// the ones of the new branch of the tip
+    fn snapshot_fork(&mut self, fork: &Fork) -> Result<(), HandleBlockError> {
+        let (fork_block, _) = self.get_block(&fork.fork_point)?;
+        let mut epoch = fork_block.date().epoch;
+        self.leaderships.rollback_snapshots(epoch);
+        let mut last = fork.fork_point.clone();
+        for hash in fork.applied.iter() {
+            let (block, _) = self.get_block(hash)?;
+            if block.date().epoch > epoch {
+                // the last block of the epoch on the new branch
+                let state = self.get_tip_ledger(&last)?.clone();
+                self.leaderships.snapshot(epoch, state);
+                epoch = block.date().epoch;
+            }
+            last = hash.clone();
+        }
+        Ok(())
+    }
+
+    /// the fragments of the blocks rolled back by the given fork which
+    /// are not in the blocks of the new branch
+    fn abandoned_fragments(&self, fork: &Fork) -> Result<Vec<Message>, StorageError> {
+        let mut kept = HashSet::new();
+        for hash in fork.applied.iter() {
+            let (block, _) = self.get_block(hash)?;
+            kept.extend(block.messages().map(|message| message.id()));
+        }
+
+        let mut abandoned = Vec::new();
+        for hash in fork.rolled_back.iter().rev() {
+            let (block, _) = self.get_block(hash)?;
+            abandoned.extend(
+                block
+                    .messages()
+                    .filter(|message| !kept.contains(&message.id()))
+                    .cloned(),
+            );
+        }
+        Ok(abandoned)
+    }
+
     /// Retrieves a list of checkpoint block hashes to pass to another
     /// peer for recovery of missing chain blocks. The checkpoints
     /// traverse back in the history with exponentially receding offsets.
-    pub fn get_checkpoints(&self) -> Result<Vec<HeaderHash>, StorageError> {
-        let tip = self.get_tip().unwrap();
-        let storage = self.storage.read().unwrap();
+    pub fn get_checkpoints(&self) -> Result<Vec<HeaderHash>, QueryError> {
+        let tip = self.get_tip()?;
+        let storage = self.storage.read();
         let tip_info = storage.get_block_info(&tip)?;
+        if tip_info.depth == 0 {
+            // the block 0 is the only checkpoint
+            return Ok(vec![tip]);
+        }
         let mut checkpoints = Vec::new();
-        assert!(tip_info.depth > 0);
         store::for_path_to_nth_ancestor(&*storage, &tip, tip_info.depth - 1, |block_info| {
             checkpoints.push(block_info.block_hash.clone());
         })?;
@@ -288,6 +657,36 @@ custom_error! {pub HandleBlockError
     Storage{source: StorageError} = "Error in the blockchain storage",
     Ledger{source: ledger::Error} = "Invalid blockchain state",
     InternalTip { source: TipReplaceError } = "Cannot update the blockchain's TIP",
+    TipAccess { source: TipGetError } = "Cannot access the blockchain's TIP",
+    Query { source: QueryError } = "{source}",
+    MissingLedgerState { block: HeaderHash } = "No ledger state for the block {block}",
+}
+
+/// where two branches of the blockchain fork
+pub struct Fork {
+    /// the last block common to both branches
+    pub fork_point: HeaderHash,
+    /// the blocks of the first branch after the fork point, the last
+    /// one first
+    pub rolled_back: Vec<HeaderHash>,
+    /// the blocks of the second branch after the fork point, in
+    /// chronological order
+    pub applied: Vec<HeaderHash>,
+}
+
+/// the tip has moved to another branch, abandoning the last blocks of
+/// the branch it was on
+pub struct Reorg {
+    /// the tip before the change of branch
+    pub from: HeaderHash,
+    /// the tip after the change of branch
+    pub to: HeaderHash,
+    pub fork_point: HeaderHash,
+    /// the number of blocks abandoned
+    pub rolled_back: usize,
+    /// the fragments of the abandoned blocks which are not in the new
+    /// branch, oldest first. They need to go back in the MemPool.
+    pub abandoned_fragments: Vec<Message>,
 }
 
 pub enum HandledBlock {
@@ -303,13 +702,25 @@ pub enum HandledBlock {
 
     /// the block as been acquired, disseminate to the connected
     /// network that a block has been processed
-    Acquired { header: Header },
+    ///
+    /// `connected` lists the cached blocks which were waiting for this
+    /// one and have been acquired along with it, parents first.
+    ///
+    /// `reorg` is set if the tip has moved to another branch.
+    Acquired {
+        header: Header,
+        connected: Vec<Header>,
+        reorg: Option<Reorg>,
+    },
 }
 
 custom_error! {pub RejectionReason
     AlreadyPresent = "Block already present in the blockchain",
     BeyondStabilityDepth = "the block is beyond the stability depth, we reject it",
     Consensus { error: leadership::Error } = "{error}",
+    InFuture = "the block's date is too far ahead of the node's clock",
+    NoLedgerState = "the ledger state of the block's parent is not held anymore",
+    InvalidContent { error: ledger::Error } = "the block cannot be applied to the ledger: {error}",
 }
 
 pub enum BlockHeaderTriage {
@@ -328,9 +739,8 @@ pub fn handle_end_of_epoch_event(
     epoch: Epoch,
 ) -> Result<(), HandleBlockError> {
     let (tip, tip_info) = blockchain.get_block_tip()?;
-    let state = blockchain.get_ledger(&tip_info.block_hash).unwrap();
-
-    // TODO: get the ledger state from 2 epochs ago
+    let state = blockchain.get_tip_ledger(&tip_info.block_hash)?;
+    let (_, stake_snapshot) = blockchain.leaderships.stake_snapshot(epoch + 1);
 
     blockchain
         .epoch_event
@@ -342,7 +752,7 @@ pub fn handle_end_of_epoch_event(
             ledger_parameters: state.get_ledger_parameters(),
 
             time_frame: blockchain.time_frame.clone(),
-            ledger_reference: state.clone(),
+            ledger_reference: stake_snapshot.clone(),
         })
         .unwrap_or_else(|_| ());
     Ok(())
@@ -360,31 +770,107 @@ pub fn handle_block(
             // in the node blockchain
             // we need to signal the network more blocks are required
 
-            blockchain
-                .unconnected_blocks
-                .entry(block.parent_id())
-                .or_insert(BTreeMap::new())
-                .insert(block.id(), block);
+            blockchain.unconnected_blocks.insert(block);
             Ok(HandledBlock::MissingBranchToBlock { to })
         }
         BlockHeaderTriage::ProcessBlockToState => {
-            //
-            process_block(blockchain, block)
+            let previous_tip = blockchain.get_tip()?;
+            let header = match process_block(blockchain, block) {
+                Err(HandleBlockError::Ledger { source }) => {
+                    return Ok(HandledBlock::Rejected {
+                        reason: RejectionReason::InvalidContent { error: source },
+                    });
+                }
+                result => result?,
+            };
+            let connected = process_unconnected_descendants(blockchain, header.id())?;
+            let reorg = find_reorg(blockchain, previous_tip)?;
+            Ok(HandledBlock::Acquired {
+                header,
+                connected,
+                reorg,
+            })
         }
     }
 }
 
-fn process_block(
+/// check whether the tip has left the branch of the previous tip, the
+/// snapshots of the epochs ended after the fork point being replaced by
+/// the ones of the new branch
+fn find_reorg(
     blockchain: &mut Blockchain,
-    block: Block,
-) -> Result<HandledBlock, HandleBlockError> {
-    let (block_tip, _block_tip_info) = blockchain.get_block(&block.parent_id())?;
+    previous_tip: HeaderHash,
+) -> Result<Option<Reorg>, HandleBlockError> {
+    let tip = blockchain.get_tip()?;
+    if tip == previous_tip {
+        return Ok(None);
+    }
+    // the blocks are only accepted on a branch forking within the stability
+    // depth, there is no need to bound the search here
+    let fork = match blockchain.find_fork(&previous_tip, &tip, u64::max_value())? {
+        Some(fork) => fork,
+        None => return Ok(None),
+    };
+    if fork.rolled_back.is_empty() {
+        return Ok(None);
+    }
+    blockchain.snapshot_fork(&fork)?;
+    let abandoned_fragments = blockchain.abandoned_fragments(&fork)?;
+    Ok(Some(Reorg {
+        from: previous_tip,
+        to: tip,
+        fork_point: fork.fork_point,
+        rolled_back: fork.rolled_back.len(),
+        abandoned_fragments,
+    }))
+}
 
-    let tip_chain_length = block_tip.chain_length();
-    let parent_epoch = block_tip.date().epoch;
+/// process the cached blocks which were waiting for the given block,
+/// then the ones waiting for them and so on. Returns the headers of the
+/// blocks acquired this way.
+fn process_unconnected_descendants(
+    blockchain: &mut Blockchain,
+    acquired: HeaderHash,
+) -> Result<Vec<Header>, HandleBlockError> {
+    let mut connected = Vec::new();
+    let mut parents = vec![acquired];
+    while let Some(parent) = parents.pop() {
+        for block in blockchain.unconnected_blocks.take_children(&parent) {
+            match header_triage(blockchain, &block.header(), true)? {
+                BlockHeaderTriage::NotOfInterest { .. } => {}
+                BlockHeaderTriage::MissingParentOrBranch { .. } => {
+                    blockchain.unconnected_blocks.insert(block);
+                }
+                BlockHeaderTriage::ProcessBlockToState => match process_block(blockchain, block) {
+                    // an invalid block is dropped, its own descendants
+                    // expiring from the cache
+                    Err(HandleBlockError::Ledger { .. }) => {}
+                    result => {
+                        let header = result?;
+                        parents.push(header.id());
+                        connected.push(header);
+                    }
+                },
+            }
+        }
+    }
+    Ok(connected)
+}
+
+fn process_block(blockchain: &mut Blockchain, block: Block) -> Result<Header, HandleBlockError> {
+    let (parent, _parent_info) = blockchain.get_block(&block.parent_id())?;
+
+    let tip_chain_length = blockchain.tip.chain_length()?;
+    let parent_epoch = parent.date().epoch;
+    let new_chain_length = block.chain_length();
 
+    let parent_state = blockchain
+        .get_ledger(&block.parent_id())
+        .ok_or_else(|| HandleBlockError::MissingLedgerState {
+            block: block.parent_id(),
+        })?
+        .clone();
     let state = {
-        let parent_state = blockchain.get_ledger(&block.parent_id()).unwrap();
         let current_parameters = parent_state.get_ledger_parameters();
         parent_state.apply_block(
             &current_parameters,
@@ -394,14 +880,19 @@ fn process_block(
     };
 
     if block.header.date().epoch > parent_epoch {
-        let leadership = Leadership::new(
-            block.header.date().epoch,
-            blockchain.get_ledger(&block.parent_id()).unwrap(),
-        );
+        // the parent is the last block of its epoch on the branch, only the
+        // snapshots of the tip's branch are kept, the ones of a branch
+        // becoming the tip being taken on the reorganisation
+        if new_chain_length > tip_chain_length {
+            blockchain.leaderships.snapshot(parent_epoch, parent_state);
+        }
+        let epoch = block.header.date().epoch;
+        let (_, stake_snapshot) = blockchain.leaderships.stake_snapshot(epoch);
+        let leadership = Leadership::new(epoch, stake_snapshot);
         let _gc_root = blockchain.leaderships.add(
             block.header.date().epoch,
             block.header.chain_length(),
-            block_tip.id(),
+            parent.id(),
             leadership,
         );
     }
@@ -410,23 +901,27 @@ fn process_block(
     // corresponding states, but to prevent a DoS, we may
     // want to store only sufficiently long chains.
 
-    let new_chain_length = block.chain_length();
-
     let branch = Branch::new(
         blockchain.multiverse.add(block.id(), state.clone()),
         new_chain_length,
         state,
     );
 
+    // the longest branch is the tip, the branches forking deeper than the
+    // stability depth having been rejected by the header triage
     if new_chain_length > tip_chain_length {
         blockchain.put_tip(branch, &block)?;
+        if block.header.date().epoch > parent_epoch {
+            blockchain
+                .leaderships
+                .prune_snapshots(block.header.date().epoch);
+            blockchain.prune(&block.id(), block.header.date().epoch);
+        }
     } else {
         blockchain.put_block(&block)?;
     }
 
-    Ok(HandledBlock::Acquired {
-        header: block.header(),
-    })
+    Ok(block.header())
 }
 
 pub fn header_triage(
@@ -444,7 +939,11 @@ pub fn header_triage(
         });
     }
 
-    let (block_tip, _) = blockchain.get_block_tip()?;
+    if blockchain.is_in_future(&block_date) {
+        return Ok(BlockHeaderTriage::NotOfInterest {
+            reason: RejectionReason::InFuture,
+        });
+    }
 
     if let Some(leadership) = blockchain.get_leadership_or_build(block_date.epoch, &parent_id) {
         match leadership.verify(header) {
@@ -455,31 +954,257 @@ pub fn header_triage(
                 });
             }
         }
-    } else {
-        // Error No leadership found for the epoch
-        //
-        // That the leadership is missing may not be a problem, we might simply
-        // need to try to retrieve it (this could be linked with the missing
-        // parent or branch (`BlockHeaderTriage::MissingParentOrBranch`)
-        unimplemented!()
-    }
-
-    // TODO: this is a wrong check, we need to get something more
-    //       dynamic than this dummy comparison
-    // hint: it might be worth utilising the Clock to know exactly
-    // how many blocks there is between the 2 given dates
-    // then to use the stability depth to compare if the block
-    // is not too far from the blockchain
-    //
-    if is_tip_candidate && block_date.epoch < block_tip.date().epoch.checked_sub(2).unwrap_or(0) {
+    } else if blockchain.block_exists(&parent_id)? {
+        // the parent is there but its state has been dropped, the block
+        // is on a branch too old to build the epoch's leadership
         return Ok(BlockHeaderTriage::NotOfInterest {
-            reason: RejectionReason::BeyondStabilityDepth,
+            reason: RejectionReason::NoLedgerState,
         });
+    } else {
+        // No leadership for the epoch and no parent to build it from:
+        // the ancestors of the block need to be retrieved first, the
+        // header will be verified once they are
+        return Ok(BlockHeaderTriage::MissingParentOrBranch { to: parent_id });
     }
 
     if !blockchain.block_exists(&parent_id)? {
         return Ok(BlockHeaderTriage::MissingParentOrBranch { to: parent_id });
     }
 
+    // a block becoming the tip may not roll back more than the stability
+    // depth of the tip's branch
+    if is_tip_candidate {
+        let tip = blockchain.get_tip()?;
+        let fork = blockchain.find_fork(&tip, &parent_id, blockchain.stability_depth)?;
+        if fork.is_none() {
+            return Ok(BlockHeaderTriage::NotOfInterest {
+                reason: RejectionReason::BeyondStabilityDepth,
+            });
+        }
+    }
+
     Ok(BlockHeaderTriage::ProcessBlockToState)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockcfg::{mock, BlockBuilder};
+    use chain_core::property::{Block as _, ChainLength as _, Header as _};
+    use chain_crypto::{bech32::Bech32 as _, Blake2b256, Ed25519, SecretKey};
+    use chain_storage::memory::MemoryBlockStore;
+    use jormungandr_lib::interfaces::Block0Configuration;
+    use quickcheck::{Arbitrary, Gen, TestResult};
+    use rand::SeedableRng as _;
+    use rand_chacha::ChaChaRng;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const SLOTS_PER_EPOCH: u32 = 10;
+    /// the blockchain started 10 epochs ago, the slots lasting 1 second
+    const ELAPSED_EPOCHS: u32 = 10;
+
+    fn leader() -> SecretKey<Ed25519> {
+        SecretKey::generate(&mut ChaChaRng::from_seed([0; 32]))
+    }
+
+    fn not_a_leader() -> SecretKey<Ed25519> {
+        SecretKey::generate(&mut ChaChaRng::from_seed([1; 32]))
+    }
+
+    fn blockchain(leader: &SecretKey<Ed25519>) -> (Blockchain, Block) {
+        let block0_date =
+            SystemTime::now() - Duration::from_secs((SLOTS_PER_EPOCH * ELAPSED_EPOCHS) as u64);
+        let block0_configuration: Block0Configuration = serde_yaml::from_str(&format!(
+            r#"
+blockchain_configuration:
+  block0_date: {}
+  discrimination: test
+  block0_consensus: bft
+  slots_per_epoch: {}
+  slot_duration: 1
+  consensus_leader_ids:
+    - {}
+  linear_fees:
+    constant: 0
+    coefficient: 0
+    certificate: 0
+"#,
+            block0_date.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            SLOTS_PER_EPOCH,
+            leader.to_public().to_bech32_str(),
+        ))
+        .unwrap();
+        let block_0 = block0_configuration.to_block();
+        let (epoch_event, _) = mpsc::channel(1);
+        let logger = Logger::root(slog::Discard, o!());
+        let blockchain = Blockchain::load(
+            block_0.clone(),
+            Box::new(MemoryBlockStore::new()),
+            epoch_event,
+            &logger,
+        )
+        .unwrap();
+        (blockchain, block_0)
+    }
+
+    fn block_after(leader: &SecretKey<Ed25519>, parent: &Block, date: BlockDate) -> Block {
+        mock::builder_after(parent, date).make_bft_block(leader)
+    }
+
+    fn date(epoch: u32, slot_id: u32) -> BlockDate {
+        BlockDate { epoch, slot_id }
+    }
+
+    /// a block as a peer may send it, possibly malformed
+    #[derive(Clone, Debug)]
+    struct PeerBlock {
+        date: BlockDate,
+        /// the block 0 if not set, an unknown block otherwise
+        parent: Option<u64>,
+        signed_by_leader: bool,
+        valid_chain_length: bool,
+    }
+
+    impl Arbitrary for PeerBlock {
+        fn arbitrary<G>(g: &mut G) -> Self
+        where
+            G: Gen,
+        {
+            PeerBlock {
+                date: date(Arbitrary::arbitrary(g), Arbitrary::arbitrary(g)),
+                parent: Arbitrary::arbitrary(g),
+                signed_by_leader: Arbitrary::arbitrary(g),
+                valid_chain_length: Arbitrary::arbitrary(g),
+            }
+        }
+    }
+
+    impl PeerBlock {
+        fn make(&self, block_0: &Block) -> Block {
+            let parent = match self.parent {
+                None => block_0.id(),
+                Some(seed) => HeaderHash::from(Blake2b256::new(&seed.to_le_bytes())),
+            };
+            let chain_length = if self.valid_chain_length {
+                block_0.chain_length().next()
+            } else {
+                block_0.chain_length()
+            };
+            let signer = if self.signed_by_leader {
+                leader()
+            } else {
+                not_a_leader()
+            };
+            let mut builder = BlockBuilder::new();
+            builder
+                .date(self.date)
+                .parent(parent)
+                .chain_length(chain_length);
+            builder.make_bft_block(&signer)
+        }
+    }
+
+    quickcheck! {
+        fn block_from_a_peer_is_handled_without_error(peer_block: PeerBlock) -> TestResult {
+            let (mut blockchain, block_0) = blockchain(&leader());
+            let block = peer_block.make(&block_0);
+
+            if let Err(err) = header_triage(&blockchain, &block.header, false) {
+                return TestResult::error(format!("header triage failed: {}", err));
+            }
+            match handle_block(&mut blockchain, block, true) {
+                Err(err) => TestResult::error(format!("block handling failed: {}", err)),
+                Ok(_) => TestResult::passed(),
+            }
+        }
+    }
+
+    #[test]
+    fn checkpoints_of_the_block_0() {
+        let (blockchain, block_0) = blockchain(&leader());
+
+        assert_eq!(blockchain.get_checkpoints().unwrap(), vec![block_0.id()]);
+    }
+
+    #[test]
+    fn header_from_a_future_epoch_is_rejected() {
+        let leader = leader();
+        let (mut blockchain, block_0) = blockchain(&leader);
+        let block = block_after(&leader, &block_0, date(ELAPSED_EPOCHS + 2, 0));
+
+        match handle_block(&mut blockchain, block, true).unwrap() {
+            HandledBlock::Rejected {
+                reason: RejectionReason::InFuture,
+            } => {}
+            HandledBlock::Rejected { reason } => panic!("unexpected rejection: {}", reason),
+            _ => panic!("a block from a future epoch has been accepted"),
+        }
+    }
+
+    #[test]
+    fn header_from_an_epoch_without_leadership_waits_for_its_ancestors() {
+        let leader = leader();
+        let (mut blockchain, block_0) = blockchain(&leader);
+        let parent = block_after(&leader, &block_0, date(3, 0));
+        let block = block_after(&leader, &parent, date(5, 0));
+
+        match handle_block(&mut blockchain, block.clone(), true).unwrap() {
+            HandledBlock::MissingBranchToBlock { to } => assert_eq!(to, parent.id()),
+            _ => panic!("the block is expected to miss its parent"),
+        }
+        assert_eq!(blockchain.unconnected_blocks.len(), 1);
+
+        match handle_block(&mut blockchain, parent, true).unwrap() {
+            HandledBlock::Acquired { connected, .. } => {
+                assert_eq!(connected.len(), 1);
+                assert_eq!(connected[0].id(), block.id());
+            }
+            _ => panic!("the parent is expected to be acquired"),
+        }
+        assert!(blockchain.unconnected_blocks.is_empty());
+        assert_eq!(blockchain.get_tip().unwrap(), block.id());
+    }
+
+    #[test]
+    fn block_at_date_is_the_last_one_not_after_the_date() {
+        let leader = leader();
+        let (mut blockchain, block_0) = blockchain(&leader);
+        let mut blocks = vec![block_0];
+        for block_date in &[date(0, 1), date(0, 3), date(1, 0), date(1, 5)] {
+            let block = block_after(&leader, blocks.last().unwrap(), *block_date);
+            handle_block(&mut blockchain, block.clone(), true).unwrap();
+            blocks.push(block);
+        }
+        let tip = blockchain.get_tip().unwrap();
+        let storage = blockchain.storage.clone();
+        let find = |block_date| Blockchain::find_block_at_date(&storage, &tip, block_date).unwrap();
+
+        assert_eq!(find(date(0, 0)), Some(blocks[0].id()));
+        assert_eq!(find(date(0, 2)), Some(blocks[1].id()));
+        assert_eq!(find(date(0, 3)), Some(blocks[2].id()));
+        assert_eq!(find(date(1, 4)), Some(blocks[3].id()));
+        assert_eq!(find(date(2, 0)), Some(blocks[4].id()));
+    }
+
+    #[test]
+    fn snapshots_of_the_abandoned_branch_are_dropped_on_reorg() {
+        let leader = leader();
+        let (mut blockchain, block_0) = blockchain(&leader);
+        let a1 = block_after(&leader, &block_0, date(0, 5));
+        let a2 = block_after(&leader, &a1, date(1, 0));
+        let a3 = block_after(&leader, &a2, date(2, 0));
+        for block in &[&a1, &a2, &a3] {
+            handle_block(&mut blockchain, (*block).clone(), true).unwrap();
+        }
+        assert_eq!(blockchain.leaderships.stake_snapshot(3).0, Some(1));
+
+        // a longer branch forking in the epoch 1 and not leaving it
+        let b3 = block_after(&leader, &a2, date(1, 3));
+        let b4 = block_after(&leader, &b3, date(1, 4));
+        for block in &[&b3, &b4] {
+            handle_block(&mut blockchain, (*block).clone(), true).unwrap();
+        }
+        assert_eq!(blockchain.get_tip().unwrap(), b4.id());
+        assert_eq!(blockchain.leaderships.stake_snapshot(3).0, Some(0));
+    }
+}
@@ -0,0 +1,31 @@
+//! Copy of the blockchain of a storage into an empty storage, to change
+//! the database of a storage directory.
+
+use crate::{
+    blockchain::{verify::chain_from_block_0, Inconsistency, LOCAL_BLOCKCHAIN_TIP_TAG},
+    start_up::NodeStorage,
+};
+use chain_storage::error::Error as StorageError;
+
+custom_error! {pub Error
+    Storage { source: StorageError } = "Error in the blockchain storage: {source}",
+    NoTip = "The storage to migrate holds no blockchain tip",
+    Inconsistent { inconsistency: Inconsistency } = "The storage to migrate is inconsistent at the {inconsistency}",
+}
+
+/// copy the blocks of the tip's branch, from the block 0, and set the
+/// tip of the new storage. The blocks of the other branches are not
+/// copied. Returns the number of blocks copied.
+pub fn migrate(from: &NodeStorage, to: &mut NodeStorage) -> Result<usize, Error> {
+    let tip = from
+        .get_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?
+        .ok_or(Error::NoTip)?;
+    let chain = chain_from_block_0(from, &tip, None)
+        .map_err(|inconsistency| Error::Inconsistent { inconsistency })?;
+    for hash in chain.iter() {
+        let (block, _) = from.get_block(hash)?;
+        to.put_block(&block)?;
+    }
+    to.put_tag(LOCAL_BLOCKCHAIN_TIP_TAG, &tip)?;
+    Ok(chain.len())
+}
@@ -1,11 +1,25 @@
+mod archive;
 mod branch;
 mod chain;
+mod migrate;
 mod process;
 mod tip;
+mod unconnected;
+mod verify;
 
+pub use self::archive::{
+    export, import, Error as ArchiveError, Imported, Manifest, ARCHIVE_VERSION,
+};
 pub use self::branch::Branch;
 pub use self::chain::{
-    handle_block, Blockchain, BlockchainR, HandleBlockError, HandledBlock, LoadError,
+    handle_block, Blockchain, BlockchainR, Fork, HandleBlockError, HandledBlock, LedgerReplay,
+    LedgerReplayError, LoadError, QueryError, Reorg, StorageR, LOCAL_BLOCKCHAIN_TIP_TAG,
 };
+pub use self::migrate::{migrate, Error as MigrateError};
 pub use self::process::handle_input;
 pub use self::tip::{Tip, TipGetError, TipReplaceError};
+pub use self::unconnected::UnconnectedBlocks;
+pub use self::verify::{
+    verify_storage, EpochStatistics, Error as VerifyError, Inconsistency, InconsistencyKind,
+    Report as VerifyReport,
+};
@@ -1,24 +1,28 @@
-use crate::blockchain::chain::{self, BlockHeaderTriage, BlockchainR, HandledBlock};
-use crate::intercom::{BlockMsg, NetworkMsg, PropagateMsg};
+use crate::blockcfg::Header;
+use crate::blockchain::chain::{self, BlockHeaderTriage, BlockchainR, HandledBlock, Reorg};
+use crate::intercom::{BlockMsg, NetworkMsg, PropagateMsg, TransactionMsg};
 use crate::stats_counter::StatsCounter;
 use crate::utils::{
     async_msg::MessageBox,
     task::{Input, TokioServiceInfo},
 };
 
-use chain_core::property::Header as _;
+use chain_core::property::{Block as _, Header as _};
+use slog::Logger;
 
 pub fn handle_input(
     info: &TokioServiceInfo,
     blockchain: &BlockchainR,
-    _stats_counter: &StatsCounter,
+    stats_counter: &StatsCounter,
     network_msg_box: &mut MessageBox<NetworkMsg>,
+    fragment_msg_box: &mut Option<MessageBox<TransactionMsg>>,
     input: Input<BlockMsg>,
 ) -> Result<(), ()> {
     let bquery = match input {
         Input::Shutdown => {
-            // TODO: is there some work to do here to clean up the
-            //       the state and make sure all state is saved properly
+            // the blocks are stored as they are processed, the tip is
+            // flushed by the node once this service has stopped
+            info!(info.logger(), "all the queued blocks have been processed");
             return Ok(());
         }
         Input::Input(msg) => msg,
@@ -29,14 +33,29 @@ pub fn handle_input(
     match bquery {
         BlockMsg::LeadershipExpectEndOfEpoch(epoch) => {
             let blockchain = blockchain.lock_read();
-            chain::handle_end_of_epoch_event(&blockchain, epoch)
-                .map_err(|e| crit!(logger, "end of epoch processing failed: {:?}", e))?;
+            if let Err(err) = chain::handle_end_of_epoch_event(&blockchain, epoch) {
+                crit!(logger,
+                    "end of epoch processing failed";
+                    "epoch" => epoch,
+                    "reason" => err.to_string(),
+                );
+            }
         }
         BlockMsg::LeadershipBlock(block) => {
+            let block_id = block.id();
             let mut blockchain = blockchain.lock_write();
-            match chain::handle_block(&mut blockchain, block, true)
-                .map_err(|e| crit!(logger, "block processing failed: {:?}", e))?
-            {
+            let handled = match chain::handle_block(&mut blockchain, block, true) {
+                Ok(handled) => handled,
+                Err(err) => {
+                    crit!(logger,
+                        "node's created block processing failed";
+                        "id" => block_id.to_string(),
+                        "reason" => err.to_string(),
+                    );
+                    return Ok(());
+                }
+            };
+            match handled {
                 HandledBlock::Rejected { reason } => {
                     warn!(logger,
                         "rejecting node's created block" ;
@@ -57,24 +76,40 @@ pub fn handle_input(
                         "the block cannot be added, missing intermediate blocks to {}", to
                     );
                 }
-                HandledBlock::Acquired { header } => {
+                HandledBlock::Acquired {
+                    header,
+                    connected,
+                    reorg,
+                } => {
                     info!(logger,
                         "block added successfully to Node's blockchain";
                         "id" => header.id().to_string(),
                         "date" => header.date().to_string()
                     );
                     debug!(logger, "Header: {:?}", header);
-                    network_msg_box
-                        .try_send(NetworkMsg::Propagate(PropagateMsg::Block(header)))
-                        .unwrap_or_else(|err| {
-                            error!(logger, "cannot propagate block to network: {}", err)
-                        });
+                    propagate_block(&logger, network_msg_box, header);
+                    propagate_connected_blocks(&logger, network_msg_box, connected);
+                    if let Some(reorg) = reorg {
+                        handle_reorg(&logger, stats_counter, fragment_msg_box, reorg);
+                    }
                 }
             }
         }
         BlockMsg::NetworkBlock(block) => {
+            let block_id = block.id();
             let mut blockchain = blockchain.lock_write();
-            match chain::handle_block(&mut blockchain, block, true).unwrap() {
+            let handled = match chain::handle_block(&mut blockchain, block, true) {
+                Ok(handled) => handled,
+                Err(err) => {
+                    error!(logger,
+                        "block from the network processing failed";
+                        "id" => block_id.to_string(),
+                        "reason" => err.to_string(),
+                    );
+                    return Ok(());
+                }
+            };
+            match handled {
                 HandledBlock::Rejected { reason } => {
                     // TODO: drop the network peer that has sent
                     // an invalid block.
@@ -93,7 +128,11 @@ pub fn handle_input(
                         "disconnected block received, missing intermediate blocks to {}", to
                     );
                 }
-                HandledBlock::Acquired { header } => {
+                HandledBlock::Acquired {
+                    header,
+                    connected,
+                    reorg,
+                } => {
                     info!(logger,
                         "block added successfully to Node's blockchain";
                         "id" => header.id().to_string(),
@@ -101,17 +140,29 @@ pub fn handle_input(
                     );
                     debug!(logger, "Header: {:?}", header);
                     // Propagate the block to other nodes
-                    network_msg_box
-                        .try_send(NetworkMsg::Propagate(PropagateMsg::Block(header)))
-                        .unwrap_or_else(|err| {
-                            error!(logger, "cannot propagate block to network: {}", err)
-                        });
+                    propagate_block(&logger, network_msg_box, header);
+                    propagate_connected_blocks(&logger, network_msg_box, connected);
+                    if let Some(reorg) = reorg {
+                        handle_reorg(&logger, stats_counter, fragment_msg_box, reorg);
+                    }
                 }
             }
         }
         BlockMsg::AnnouncedBlock(header, node_id) => {
             let blockchain = blockchain.lock_read();
-            match chain::header_triage(&blockchain, &header, false).unwrap() {
+            let triage = match chain::header_triage(&blockchain, &header, false) {
+                Ok(triage) => triage,
+                Err(err) => {
+                    error!(logger,
+                        "block announcement processing failed";
+                        "id" => header.id().to_string(),
+                        "node_id" => node_id.to_string(),
+                        "reason" => err.to_string(),
+                    );
+                    return Ok(());
+                }
+            };
+            match triage {
                 BlockHeaderTriage::NotOfInterest { reason } => {
                     info!(logger, "rejecting block announcement: {:?}", reason);
                 }
@@ -122,7 +173,16 @@ pub fn handle_input(
                         logger,
                         "received a loose block ({}), missing parent(s) block(s)", to
                     );
-                    let from = blockchain.get_checkpoints().unwrap();
+                    let from = match blockchain.get_checkpoints() {
+                        Ok(from) => from,
+                        Err(err) => {
+                            error!(logger,
+                                "cannot compute the checkpoints to pull the missing headers";
+                                "reason" => err.to_string(),
+                            );
+                            return Ok(());
+                        }
+                    };
                     network_msg_box
                         .try_send(NetworkMsg::PullHeaders { node_id, from, to })
                         .unwrap_or_else(|err| {
@@ -146,3 +206,57 @@ pub fn handle_input(
 
     Ok(())
 }
+
+/// the cached blocks acquired because their parent arrived are announced
+/// like any other acquired block
+fn propagate_connected_blocks(
+    logger: &Logger,
+    network_msg_box: &mut MessageBox<NetworkMsg>,
+    connected: Vec<Header>,
+) {
+    for header in connected {
+        info!(logger,
+            "cached block added to Node's blockchain, its parent has been acquired";
+            "id" => header.id().to_string(),
+            "date" => header.date().to_string()
+        );
+        propagate_block(logger, network_msg_box, header);
+    }
+}
+
+fn propagate_block(logger: &Logger, network_msg_box: &mut MessageBox<NetworkMsg>, header: Header) {
+    network_msg_box
+        .try_send(NetworkMsg::Propagate(PropagateMsg::Block(header)))
+        .unwrap_or_else(|err| error!(logger, "cannot propagate block to network: {}", err));
+}
+
+/// the fragments of the abandoned blocks go back in the MemPool so they
+/// can be included in the blocks of the new branch, if the node has one
+fn handle_reorg(
+    logger: &Logger,
+    stats_counter: &StatsCounter,
+    fragment_msg_box: &mut Option<MessageBox<TransactionMsg>>,
+    reorg: Reorg,
+) {
+    stats_counter.add_reorg_cnt(1);
+    warn!(logger,
+        "the tip moved to another branch";
+        "from" => reorg.from.to_string(),
+        "to" => reorg.to.to_string(),
+        "fork_point" => reorg.fork_point.to_string(),
+        "rolled_back_blocks" => reorg.rolled_back,
+        "abandoned_fragments" => reorg.abandoned_fragments.len(),
+    );
+    let fragment_msg_box = match fragment_msg_box {
+        Some(fragment_msg_box) if !reorg.abandoned_fragments.is_empty() => fragment_msg_box,
+        _ => return,
+    };
+    fragment_msg_box
+        .try_send(TransactionMsg::PutBackFragments(reorg.abandoned_fragments))
+        .unwrap_or_else(|err| {
+            error!(
+                logger,
+                "cannot put back the abandoned fragments in the MemPool: {}", err
+            )
+        });
+}
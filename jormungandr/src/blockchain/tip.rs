@@ -69,6 +69,19 @@ impl Tip {
         Ok(self.branch.read()?.chain_length().clone())
     }
 
+    /// get the branch of the Tip, to read its hash, chain length and
+    /// ledger consistently while the Tip may move to another branch
+    ///
+    /// # Error
+    ///
+    /// This function might return an error if the underlying lock is
+    /// poisoned.
+    ///
+    #[inline]
+    pub fn branch(&self) -> Result<Branch, TipGetError> {
+        Ok(self.branch.read()?.clone())
+    }
+
     /// get the ledger of the Tip
     ///
     /// # Error
@@ -0,0 +1,245 @@
+use crate::blockcfg::{Block, HeaderHash};
+use chain_core::property::Block as _;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// maximum number of unconnected blocks kept in the cache
+pub const DEFAULT_MAX_UNCONNECTED_BLOCKS: usize = 1024;
+/// maximum time an unconnected block is kept waiting for its parent: 1h
+pub const DEFAULT_UNCONNECTED_BLOCK_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Incoming blocks whose parent does not exist yet. Sorted by
+/// parent hash to allow quick look up of the children of a
+/// parent.
+///
+/// The cache is bounded: when it is full the oldest blocks are dropped
+/// to make room for the new ones, and the blocks that have been waiting
+/// for their parent for too long are dropped.
+pub struct UnconnectedBlocks {
+    by_parent: BTreeMap<HeaderHash, BTreeMap<HeaderHash, Block>>,
+    /// the blocks in order of arrival, the oldest first
+    arrivals: VecDeque<Arrival>,
+    count: usize,
+    max_count: usize,
+    max_age: Duration,
+}
+
+struct Arrival {
+    at: Instant,
+    parent: HeaderHash,
+    id: HeaderHash,
+}
+
+impl Default for UnconnectedBlocks {
+    fn default() -> Self {
+        UnconnectedBlocks::new(
+            DEFAULT_MAX_UNCONNECTED_BLOCKS,
+            DEFAULT_UNCONNECTED_BLOCK_MAX_AGE,
+        )
+    }
+}
+
+impl UnconnectedBlocks {
+    pub fn new(max_count: usize, max_age: Duration) -> Self {
+        UnconnectedBlocks {
+            by_parent: BTreeMap::new(),
+            arrivals: VecDeque::new(),
+            count: 0,
+            max_count,
+            max_age,
+        }
+    }
+
+    /// number of blocks held in the cache
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// keep the given block until its parent is acquired, evicting the
+    /// expired blocks and, if the cache is full, the oldest ones
+    pub fn insert(&mut self, block: Block) {
+        let now = Instant::now();
+        self.purge(now);
+
+        let parent = block.parent_id();
+        let id = block.id();
+        if self
+            .by_parent
+            .get(&parent)
+            .map_or(false, |children| children.contains_key(&id))
+        {
+            return;
+        }
+        if self.max_count == 0 {
+            return;
+        }
+        while self.count >= self.max_count {
+            match self.arrivals.pop_front() {
+                Some(oldest) => self.remove(&oldest.parent, &oldest.id),
+                None => break,
+            }
+        }
+
+        self.by_parent
+            .entry(parent.clone())
+            .or_insert_with(BTreeMap::new)
+            .insert(id.clone(), block);
+        self.arrivals.push_back(Arrival {
+            at: now,
+            parent,
+            id,
+        });
+        self.count += 1;
+    }
+
+    /// take out of the cache the blocks waiting for the given parent
+    pub fn take_children(&mut self, parent: &HeaderHash) -> Vec<Block> {
+        self.purge(Instant::now());
+        match self.by_parent.remove(parent) {
+            None => Vec::new(),
+            Some(children) => {
+                self.arrivals.retain(|arrival| &arrival.parent != parent);
+                self.count -= children.len();
+                children.into_iter().map(|(_, block)| block).collect()
+            }
+        }
+    }
+
+    /// drop the blocks which have been waiting for longer than the
+    /// maximum age
+    fn purge(&mut self, now: Instant) {
+        while let Some(arrival) = self.arrivals.front() {
+            if now.duration_since(arrival.at) < self.max_age {
+                break;
+            }
+            let arrival = self.arrivals.pop_front().unwrap();
+            self.remove(&arrival.parent, &arrival.id);
+        }
+    }
+
+    fn remove(&mut self, parent: &HeaderHash, id: &HeaderHash) {
+        let now_empty = match self.by_parent.get_mut(parent) {
+            None => return,
+            Some(children) => {
+                if children.remove(id).is_some() {
+                    self.count -= 1;
+                }
+                children.is_empty()
+            }
+        };
+        if now_empty {
+            self.by_parent.remove(parent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockcfg::{mock, BlockDate};
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn ids(blocks: &[Block]) -> Vec<HeaderHash> {
+        blocks.iter().map(|block| block.id()).collect()
+    }
+
+    #[test]
+    fn oldest_blocks_are_evicted_when_full() {
+        let blocks = mock::chain(4);
+        let mut unconnected = UnconnectedBlocks::new(2, HOUR);
+        for block in &blocks[1..] {
+            unconnected.insert(block.clone());
+        }
+        assert_eq!(unconnected.len(), 2);
+        assert!(unconnected.take_children(&blocks[0].id()).is_empty());
+        assert_eq!(
+            ids(&unconnected.take_children(&blocks[1].id())),
+            vec![blocks[2].id()]
+        );
+        assert_eq!(
+            ids(&unconnected.take_children(&blocks[2].id())),
+            vec![blocks[3].id()]
+        );
+        assert!(unconnected.is_empty());
+    }
+
+    #[test]
+    fn expired_blocks_are_dropped() {
+        let blocks = mock::chain(3);
+        let mut unconnected = UnconnectedBlocks::new(10, Duration::from_secs(0));
+        unconnected.insert(blocks[1].clone());
+        // the first block expires as the second one is inserted
+        unconnected.insert(blocks[2].clone());
+        assert_eq!(unconnected.len(), 1);
+        assert!(unconnected.take_children(&blocks[1].id()).is_empty());
+        assert!(unconnected.is_empty());
+        assert!(unconnected.arrivals.is_empty());
+    }
+
+    #[test]
+    fn duplicate_block_is_kept_once() {
+        let blocks = mock::chain(2);
+        let mut unconnected = UnconnectedBlocks::new(10, HOUR);
+        unconnected.insert(blocks[1].clone());
+        unconnected.insert(blocks[1].clone());
+        assert_eq!(unconnected.len(), 1);
+        assert_eq!(unconnected.arrivals.len(), 1);
+        assert_eq!(
+            ids(&unconnected.take_children(&blocks[0].id())),
+            vec![blocks[1].id()]
+        );
+    }
+
+    #[test]
+    fn taken_children_are_forgotten() {
+        let blocks = mock::chain(3);
+        let sibling = mock::block_after(
+            &blocks[0],
+            BlockDate {
+                epoch: 0,
+                slot_id: 5,
+            },
+        );
+        let mut unconnected = UnconnectedBlocks::new(3, HOUR);
+        unconnected.insert(blocks[1].clone());
+        unconnected.insert(blocks[2].clone());
+        unconnected.insert(sibling.clone());
+
+        let mut children = ids(&unconnected.take_children(&blocks[0].id()));
+        children.sort();
+        let mut expected = vec![blocks[1].id(), sibling.id()];
+        expected.sort();
+        assert_eq!(children, expected);
+        assert_eq!(unconnected.len(), 1);
+        assert_eq!(unconnected.arrivals.len(), 1);
+
+        // the room of the taken children is available again, without
+        // evicting the block still waiting for its parent
+        let nephew = mock::block_after(
+            &sibling,
+            BlockDate {
+                epoch: 0,
+                slot_id: 6,
+            },
+        );
+        let grandnephew = mock::block_after(
+            &nephew,
+            BlockDate {
+                epoch: 0,
+                slot_id: 7,
+            },
+        );
+        unconnected.insert(nephew);
+        unconnected.insert(grandnephew);
+        assert_eq!(unconnected.len(), 3);
+        assert_eq!(
+            ids(&unconnected.take_children(&blocks[1].id())),
+            vec![blocks[2].id()]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Offline verification of a block storage: the chain of the tip is
+//! replayed from the block 0, the leader of every block is verified and
+//! the block is applied to the ledger, without starting the node.
+
+use crate::{
+    blockcfg::{Block, BlockDate, Epoch, HeaderHash, Ledger},
+    blockchain::LOCAL_BLOCKCHAIN_TIP_TAG,
+    leadership::{Leadership, Leaderships},
+    start_up::NodeStorage,
+};
+use chain_core::property::{Block as _, HasMessages as _};
+use chain_impl_mockchain::{
+    leadership::{self, Verification},
+    ledger,
+};
+use chain_storage::{error::Error as StorageError, store::BlockInfo};
+use std::fmt::{self, Display};
+
+custom_error! {pub Error
+    Storage { source: StorageError } = "Cannot read the storage: {source}",
+    NoTip = "The storage holds no blockchain tip",
+}
+
+custom_error! {pub InconsistencyKind
+    Unreadable { source: StorageError } = "the block cannot be read from the storage: {source}",
+    Depth { expected: u64, found: u64 } = "the block is at depth {found} instead of {expected}",
+    Block0Mismatch { block0_id: HeaderHash } = "the blockchain does not start with the genesis block {block0_id}",
+    Leadership { error: leadership::Error } = "the leader of the block is invalid: {error}",
+    Ledger { error: ledger::Error } = "the block cannot be applied to the ledger: {error}",
+}
+
+/// the first block of the chain of the tip found inconsistent
+#[derive(Debug)]
+pub struct Inconsistency {
+    pub block: HeaderHash,
+    /// the date of the block, unknown if it cannot be read
+    pub date: Option<BlockDate>,
+    pub kind: InconsistencyKind,
+}
+
+/// the blocks of an epoch on the chain of the tip
+pub struct EpochStatistics {
+    pub epoch: Epoch,
+    pub blocks: u64,
+    pub fragments: u64,
+}
+
+pub struct Report {
+    pub tip: HeaderHash,
+    /// the statistics of the blocks replayed before the inconsistency,
+    /// if any, in chronological order
+    pub epochs: Vec<EpochStatistics>,
+    pub inconsistency: Option<Inconsistency>,
+}
+
+/// verify the chain of the tip of the storage, up to the first
+/// inconsistency. If set, the chain must start with the given block 0.
+pub fn verify_storage(
+    storage: &NodeStorage,
+    block_0_id: Option<&HeaderHash>,
+) -> Result<Report, Error> {
+    let tip = storage
+        .get_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?
+        .ok_or(Error::NoTip)?;
+    let mut report = Report {
+        tip: tip.clone(),
+        epochs: Vec::new(),
+        inconsistency: None,
+    };
+    let replayed = chain_from_block_0(storage, &tip, block_0_id)
+        .and_then(|chain| replay(storage, &chain, &mut report));
+    report.inconsistency = replayed.err();
+    Ok(report)
+}
+
+/// the hashes of the blocks from the block 0 to the tip, checking each
+/// block is stored one level above its parent
+pub(super) fn chain_from_block_0(
+    storage: &NodeStorage,
+    tip: &HeaderHash,
+    block_0_id: Option<&HeaderHash>,
+) -> Result<Vec<HeaderHash>, Inconsistency> {
+    let mut chain = Vec::new();
+    let mut current = tip.clone();
+    let mut expected_depth = None;
+    loop {
+        let (block, info) = read_block(storage, &current)?;
+        if let Some(expected) = expected_depth {
+            if info.depth != expected {
+                let kind = InconsistencyKind::Depth {
+                    expected,
+                    found: info.depth,
+                };
+                return Err(Inconsistency::new(&block, kind));
+            }
+        }
+        chain.push(current);
+        if info.depth == 0 {
+            match block_0_id {
+                Some(block0_id) if *block0_id != block.id() => {
+                    let kind = InconsistencyKind::Block0Mismatch {
+                        block0_id: block0_id.clone(),
+                    };
+                    return Err(Inconsistency::new(&block, kind));
+                }
+                _ => break,
+            }
+        }
+        expected_depth = Some(info.depth - 1);
+        current = block.parent_id();
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// the ledger of a chain being replayed block after block, the leaders
+/// of each epoch being elected the same way the node elects them
+pub(super) struct Replay {
+    state: Ledger,
+    leaderships: Leaderships,
+    epoch: Epoch,
+    leadership: Leadership,
+}
+
+impl Replay {
+    pub(super) fn start(block_0: &Block) -> Result<Self, Inconsistency> {
+        let state = Ledger::new(block_0.id(), block_0.messages())
+            .map_err(|error| Inconsistency::new(block_0, InconsistencyKind::Ledger { error }))?;
+        let epoch = block_0.date().epoch;
+        Ok(Replay {
+            leaderships: Leaderships::new(&block_0.header, &state),
+            leadership: Leadership::new(epoch, &state),
+            state,
+            epoch,
+        })
+    }
+
+    /// verify the leader of the block following the last replayed one
+    /// and apply the block to the ledger
+    pub(super) fn apply(&mut self, block: &Block) -> Result<(), Inconsistency> {
+        if block.date().epoch > self.epoch {
+            // the state before the block is the one at the end of the
+            // previous epoch
+            self.leaderships.snapshot(self.epoch, self.state.clone());
+            self.epoch = block.date().epoch;
+            self.leaderships.prune_snapshots(self.epoch);
+            self.leadership =
+                Leadership::new(self.epoch, self.leaderships.stake_snapshot(self.epoch).1);
+        }
+        if let Verification::Failure(error) = self.leadership.verify(&block.header) {
+            let kind = InconsistencyKind::Leadership { error };
+            return Err(Inconsistency::new(block, kind));
+        }
+        let parameters = self.state.get_ledger_parameters();
+        self.state = self
+            .state
+            .apply_block(
+                &parameters,
+                block.messages(),
+                &block.header.to_content_eval_context(),
+            )
+            .map_err(|error| Inconsistency::new(block, InconsistencyKind::Ledger { error }))?;
+        Ok(())
+    }
+
+    /// replay the stored blocks of the chain, the block 0 first
+    pub(super) fn from_storage(
+        storage: &NodeStorage,
+        chain: &[HeaderHash],
+    ) -> Result<Self, Inconsistency> {
+        Self::replay(storage, chain, |_| ())
+    }
+
+    fn replay<F>(
+        storage: &NodeStorage,
+        chain: &[HeaderHash],
+        mut on_block: F,
+    ) -> Result<Self, Inconsistency>
+    where
+        F: FnMut(&Block),
+    {
+        let (block_0, _) = read_block(storage, &chain[0])?;
+        let mut replay = Replay::start(&block_0)?;
+        on_block(&block_0);
+        for hash in &chain[1..] {
+            let (block, _) = read_block(storage, hash)?;
+            replay.apply(&block)?;
+            on_block(&block);
+        }
+        Ok(replay)
+    }
+}
+
+fn replay(
+    storage: &NodeStorage,
+    chain: &[HeaderHash],
+    report: &mut Report,
+) -> Result<(), Inconsistency> {
+    Replay::replay(storage, chain, |block| report.record(block)).map(|_| ())
+}
+
+fn read_block(
+    storage: &NodeStorage,
+    hash: &HeaderHash,
+) -> Result<(Block, BlockInfo<HeaderHash>), Inconsistency> {
+    storage.get_block(hash).map_err(|source| Inconsistency {
+        block: hash.clone(),
+        date: None,
+        kind: InconsistencyKind::Unreadable { source },
+    })
+}
+
+impl Inconsistency {
+    fn new(block: &Block, kind: InconsistencyKind) -> Self {
+        Inconsistency {
+            block: block.id(),
+            date: Some(block.date()),
+            kind,
+        }
+    }
+}
+
+impl Report {
+    fn record(&mut self, block: &Block) {
+        let epoch = block.date().epoch;
+        let fragments = block.messages().count() as u64;
+        match self.epochs.last_mut() {
+            Some(statistics) if statistics.epoch == epoch => {
+                statistics.blocks += 1;
+                statistics.fragments += fragments;
+            }
+            _ => self.epochs.push(EpochStatistics {
+                epoch,
+                blocks: 1,
+                fragments,
+            }),
+        }
+    }
+
+    /// the number of blocks replayed, the block 0 included
+    pub fn blocks(&self) -> u64 {
+        self.epochs.iter().map(|statistics| statistics.blocks).sum()
+    }
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.date {
+            Some(date) => write!(f, "block {} ({}): {}", self.block, date, self.kind),
+            None => write!(f, "block {}: {}", self.block, self.kind),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "tip: {}", self.tip)?;
+        writeln!(f, "blocks replayed: {}", self.blocks())?;
+        writeln!(f, "{:>8} {:>10} {:>10}", "epoch", "blocks", "fragments")?;
+        for statistics in self.epochs.iter() {
+            writeln!(
+                f,
+                "{:>8} {:>10} {:>10}",
+                statistics.epoch, statistics.blocks, statistics.fragments
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use crate::blockcfg::{Block, Header, HeaderHash};
 use crate::blockchain::BlockchainR;
 use crate::intercom::{do_stream_reply, ClientMsg, Error, ReplyStreamHandle};
+use crate::start_up::NodeStorage;
 use crate::utils::task::{Input, ThreadServiceInfo};
 use chain_core::property::{Block as _, HasHeader as _};
-use chain_storage::store;
+use chain_storage::{error::Error as StorageError, store};
 
 pub fn handle_input(_info: &ThreadServiceInfo, blockchain: &BlockchainR, input: Input<ClientMsg>) {
     let cquery = match input {
@@ -35,8 +36,8 @@ pub fn handle_input(_info: &ThreadServiceInfo, blockchain: &BlockchainR, input:
 
 fn handle_get_block_tip(blockchain: &BlockchainR) -> Result<Header, Error> {
     let blockchain = blockchain.lock_read();
-    let tip = blockchain.get_tip().unwrap();
-    let storage = blockchain.storage.read().unwrap();
+    let tip = blockchain.get_tip()?;
+    let storage = blockchain.storage.read();
     match storage.get_block(&tip) {
         Err(err) => Err(Error::failed(format!(
             "Cannot read block '{}': {}",
@@ -61,7 +62,7 @@ fn handle_get_headers_range(
     let mut checkpoints = checkpoints
         .iter()
         .filter_map(
-            |checkpoint| match blockchain.storage.read().unwrap().get_block(&checkpoint) {
+            |checkpoint| match blockchain.storage.read().get_block(&checkpoint) {
                 Err(_) => None,
                 Ok((blk, _)) => Some((blk.date(), checkpoint)),
             },
@@ -81,12 +82,12 @@ fn handle_get_headers_range(
 
         /* Send headers up to the maximum. */
         let mut header_count = 0usize;
-        let storage = blockchain.storage.read().unwrap();
+        let storage = blockchain.storage.read();
         for x in store::iterate_range(&*storage, &from, &to)? {
             match x {
                 Err(err) => return Err(Error::from(err)),
                 Ok(info) => {
-                    let (block, _) = storage.get_block(&info.block_hash)?;
+                    let block = get_block(&storage, &info.block_hash)?;
                     reply.send(block.header());
                     header_count += 1;
                     if header_count >= MAX_HEADERS {
@@ -108,13 +109,13 @@ fn handle_get_blocks_range(
 ) -> Result<(), Error> {
     // FIXME: remove double locking
     let blockchain = blockchain.lock_read();
-    let storage = blockchain.storage.read().unwrap();
+    let storage = blockchain.storage.read();
 
     // FIXME: include the from block
 
     for x in store::iterate_range(&*storage, &from, &to)? {
         let info = x?;
-        let (blk, _) = storage.get_block(&info.block_hash)?;
+        let blk = get_block(&storage, &info.block_hash)?;
         reply.send(blk);
     }
 
@@ -129,7 +130,7 @@ fn handle_get_blocks(
     let blockchain = blockchain.lock_read();
 
     for id in ids.into_iter() {
-        let (blk, _) = blockchain.storage.read().unwrap().get_block(&id)?;
+        let blk = get_block(&blockchain.storage.read(), &id)?;
         reply.send(blk);
     }
 
@@ -144,7 +145,7 @@ fn handle_get_headers(
     let blockchain = blockchain.lock_read();
 
     for id in ids.into_iter() {
-        let (blk, _) = blockchain.storage.read().unwrap().get_block(&id)?;
+        let blk = get_block(&blockchain.storage.read(), &id)?;
         reply.send(blk.header());
     }
 
@@ -166,18 +167,26 @@ fn handle_pull_blocks_to_tip(
     }
     let from = from.remove(0);
 
-    let tip = blockchain.get_tip().unwrap();
+    let tip = blockchain.get_tip()?;
 
-    let storage = blockchain.storage.read().unwrap();
+    let storage = blockchain.storage.read();
     for x in store::iterate_range(&*storage, &from, &tip)? {
         let info = x?;
-        let (blk, _) = blockchain
-            .storage
-            .read()
-            .unwrap()
-            .get_block(&info.block_hash)?;
+        let blk = get_block(&storage, &info.block_hash)?;
         reply.send(blk);
     }
 
     Ok(())
 }
+
+/// read a block of the storage, the block whose body has been deleted by
+/// the pruning of the storage being reported as such
+fn get_block(storage: &NodeStorage, id: &HeaderHash) -> Result<Block, Error> {
+    match storage.get_block(id) {
+        Ok((blk, _)) => Ok(blk),
+        Err(StorageError::BlockNotFound) if storage.block_exists(id)? => Err(Error::not_found(
+            format!("the block {} has been pruned from the storage", id),
+        )),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -1,11 +1,13 @@
 mod entry;
 mod logs;
+mod persistence;
 mod pool;
 mod process;
 pub mod selection;
 
 pub use self::entry::PoolEntry;
 pub use self::logs::Logs;
+pub use self::persistence::{Error as PersistenceError, Persistence, Restored};
 pub use self::pool::Pool;
 pub use self::process::Process;
 
@@ -0,0 +1,159 @@
+//! Persistence of the MemPool in the storage directory, so the pending
+//! fragments are not lost when the node restarts.
+
+use crate::{
+    blockcfg::{HeaderContentEvalContext, Ledger, LedgerParameters},
+    blockchain::{Blockchain, QueryError},
+    fragment::Fragment,
+};
+use bincode;
+use chain_core::property::{Block as _, Deserialize as _, Message as _, Serialize as _};
+use jormungandr_lib::interfaces::{FragmentLog, FragmentStatus};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// name of the file, in the storage directory, holding the MemPool
+const MEMPOOL_FILE: &str = "mempool.bin";
+
+custom_error! {pub Error
+    Io { source: io::Error } = "cannot access the MemPool file",
+    Encoding { source: bincode::Error } = "cannot encode or decode the MemPool file",
+    FragmentInvalid = "the MemPool file contains an invalid fragment",
+    Blockchain { source: QueryError } = "cannot read the blockchain tip",
+    Tip = "cannot access the blockchain tip",
+    Pool = "cannot access the MemPool"
+}
+
+/// where and how often the MemPool is saved
+#[derive(Clone)]
+pub struct Persistence {
+    path: PathBuf,
+    interval: Duration,
+}
+
+/// the fragments and logs restored from a saved MemPool
+pub struct Restored {
+    /// the fragments still valid on top of the tip, oldest first
+    pub fragments: Vec<Fragment>,
+    /// the logs of all the saved fragments, the fragments no longer valid
+    /// being marked as rejected
+    pub logs: Vec<FragmentLog>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Saved {
+    /// the pending fragments, serialized, oldest first
+    fragments: Vec<Vec<u8>>,
+    logs: Vec<FragmentLog>,
+}
+
+impl Persistence {
+    /// save the MemPool in the given storage directory, every `interval`
+    /// and when the node shuts down
+    pub fn new(storage_dir: &Path, interval: Duration) -> Self {
+        Persistence {
+            path: storage_dir.join(MEMPOOL_FILE),
+            interval,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// write the given fragments and logs, replacing the previously saved
+    /// ones only once they are completely written
+    pub fn save(&self, fragments: &[Fragment], logs: Vec<FragmentLog>) -> Result<(), Error> {
+        let fragments = fragments
+            .iter()
+            .map(|fragment| fragment.serialize_as_vec())
+            .collect::<Result<Vec<_>, _>>()?;
+        let saved = Saved { fragments, logs };
+
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            bincode::serialize_into(&mut writer, &saved)?;
+            writer.flush()?;
+        }
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// load the saved MemPool, if any, and check the pending fragments are
+    /// still valid on top of the tip of the blockchain
+    pub fn restore(&self, blockchain: &Blockchain) -> Result<Option<Restored>, Error> {
+        let saved: Saved = match File::open(&self.path) {
+            Ok(file) => bincode::deserialize_from(BufReader::new(file))?,
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let fragments = saved
+            .fragments
+            .iter()
+            .map(|bytes| Fragment::deserialize(bytes.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::FragmentInvalid)?;
+
+        // the fragments are checked as if they were included in the block
+        // following the tip
+        let (tip, _) = blockchain.get_block_tip()?;
+        let ledger = blockchain.tip.ledger().map_err(|_| Error::Tip)?;
+        let metadata = HeaderContentEvalContext {
+            block_date: tip.date(),
+            chain_length: tip.header.chain_length().next(),
+            nonce: None,
+        };
+        let ledger_params = ledger.get_ledger_parameters();
+
+        Ok(Some(revalidate(
+            fragments,
+            saved.logs,
+            ledger,
+            &ledger_params,
+            &metadata,
+        )))
+    }
+}
+
+/// apply the fragments one after the other on top of the ledger, the logs
+/// of the fragments which cannot be applied anymore are marked rejected
+fn revalidate(
+    fragments: Vec<Fragment>,
+    logs: Vec<FragmentLog>,
+    mut ledger: Ledger,
+    ledger_params: &LedgerParameters,
+    metadata: &HeaderContentEvalContext,
+) -> Restored {
+    let mut logs: HashMap<_, _> = logs
+        .into_iter()
+        .map(|log| (log.fragment_id().clone(), log))
+        .collect();
+    let mut valid = Vec::new();
+
+    for fragment in fragments {
+        let log = match logs.get_mut(&fragment.id().into()) {
+            Some(log) => log,
+            None => continue,
+        };
+        match ledger.apply_fragment(ledger_params, &fragment, metadata) {
+            Ok(new_ledger) => {
+                ledger = new_ledger;
+                valid.push(fragment);
+            }
+            Err(error) => log.modify(FragmentStatus::Rejected {
+                reason: format!("no longer valid after the node restarted: {}", error),
+            }),
+        }
+    }
+
+    Restored {
+        fragments: valid,
+        logs: logs.into_iter().map(|(_, log)| log).collect(),
+    }
+}
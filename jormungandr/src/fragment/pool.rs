@@ -1,8 +1,11 @@
 use crate::{
     blockcfg::{HeaderContentEvalContext, Ledger, LedgerParameters},
-    fragment::{selection::FragmentSelectionAlgorithm, Fragment, Logs},
+    fragment::{
+        selection::FragmentSelectionAlgorithm, Fragment, Logs, Persistence, PersistenceError,
+        Restored,
+    },
 };
-use jormungandr_lib::interfaces::{FragmentLog, FragmentOrigin};
+use jormungandr_lib::interfaces::{FragmentLog, FragmentOrigin, FragmentStatus};
 use std::time::Duration;
 use tokio::{prelude::*, sync::lock::Lock, timer};
 
@@ -53,6 +56,77 @@ impl Pool {
             })
     }
 
+    /// put back in the MemPool the fragments and the logs restored from
+    /// a previous run of the node
+    pub fn restore(&mut self, restored: Restored) -> impl Future<Item = (), Error = ()> {
+        let Restored { fragments, logs } = restored;
+        let mut lock = self.pool.clone();
+        let mut pool_logs = self.logs.clone();
+
+        future::poll_fn(move || Ok(lock.poll_lock()))
+            .and_then(move |mut guard| {
+                for fragment in fragments {
+                    guard.insert(fragment);
+                }
+                future::ok(())
+            })
+            .and_then(move |()| stream::iter_ok(logs).for_each(move |log| pool_logs.insert(log)))
+    }
+
+    /// put back in the MemPool the fragments of the blocks abandoned when
+    /// the tip moved to another branch, their logs being pending again
+    pub fn put_back(&mut self, fragments: Vec<Fragment>) -> impl Future<Item = (), Error = ()> {
+        use chain_core::property::Message as _;
+
+        let mut lock = self.pool.clone();
+        let logs = self.logs.clone();
+
+        future::poll_fn(move || Ok(lock.poll_lock()))
+            .and_then(move |pool| logs.inner().map(|logs| (pool, logs)))
+            .and_then(move |(mut pool, mut logs)| {
+                for fragment in fragments {
+                    let id = fragment.id();
+                    if logs.exists(Some(id.clone().into()))[0] {
+                        logs.modify(&id.clone().into(), FragmentStatus::Pending);
+                    } else {
+                        logs.insert(FragmentLog::new(id.clone().into(), FragmentOrigin::Network));
+                    }
+                    if !pool.entries.contains_key(&id) {
+                        pool.insert(fragment);
+                    }
+                }
+                future::ok(())
+            })
+    }
+
+    /// the fragments pending in the MemPool, oldest first
+    pub fn fragments(&self) -> impl Future<Item = Vec<Fragment>, Error = ()> {
+        let mut lock = self.pool.clone();
+
+        future::poll_fn(move || Ok(lock.poll_lock())).and_then(|guard| {
+            let fragments = guard
+                .entries_by_time
+                .iter()
+                .filter_map(|id| guard.entries.get(id))
+                .map(|(_, fragment, _)| fragment.clone())
+                .collect();
+            future::ok(fragments)
+        })
+    }
+
+    /// save the pending fragments and the logs with the given persistence
+    pub fn persist(
+        &self,
+        persistence: &Persistence,
+    ) -> impl Future<Item = (), Error = PersistenceError> {
+        let persistence = persistence.clone();
+
+        self.fragments()
+            .join(self.logs.logs())
+            .map_err(|()| PersistenceError::Pool)
+            .and_then(move |(fragments, logs)| persistence.save(&fragments, logs))
+    }
+
     pub fn poll_purge(&mut self) -> impl Future<Item = (), Error = timer::Error> {
         let mut lock = self.pool.clone();
         let purge_logs = self.logs.poll_purge();
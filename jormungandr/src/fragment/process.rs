@@ -1,5 +1,5 @@
 use crate::{
-    fragment::{Logs, Pool},
+    fragment::{Logs, Persistence, Pool, Restored},
     intercom::TransactionMsg,
     stats_counter::StatsCounter,
     utils::{async_msg::MessageQueue, task::TokioServiceInfo},
@@ -18,6 +18,8 @@ pub struct Process {
     pool: Pool,
     logs: Logs,
     garbage_collection_interval: Duration,
+    persistence: Option<Persistence>,
+    restored: Option<Restored>,
 }
 
 impl Process {
@@ -31,9 +33,24 @@ impl Process {
             pool: Pool::new(pool_ttl, logs.clone()),
             logs,
             garbage_collection_interval,
+            persistence: None,
+            restored: None,
         }
     }
 
+    /// save the MemPool regularly with the given persistence, the fragments
+    /// and logs restored from the previous run being put back in the pool
+    /// when the process starts
+    pub fn with_persistence(
+        mut self,
+        persistence: Persistence,
+        restored: Option<Restored>,
+    ) -> Self {
+        self.persistence = Some(persistence);
+        self.restored = restored;
+        self
+    }
+
     pub fn logs(&self) -> &Logs {
         &self.logs
     }
@@ -48,54 +65,99 @@ impl Process {
         input: MessageQueue<TransactionMsg>,
     ) -> impl Future<Item = (), Error = ()> {
         service_info.spawn(self.start_pool_garbage_collector(service_info.logger().clone()));
+        if let Some(persistence) = self.persistence.as_ref() {
+            service_info.spawn(
+                self.start_pool_persistence(persistence.clone(), service_info.logger().clone()),
+            );
+        }
+
+        let restore = match self.restored {
+            Some(restored) => {
+                info!(
+                    service_info.logger(),
+                    "restoring {} fragments in the MemPool",
+                    restored.fragments.len()
+                );
+                A(self.pool.clone().restore(restored))
+            }
+            None => B(future::ok(())),
+        };
 
         let pool = self.pool.clone();
         let pool_copy = self.pool;
 
-        input.for_each(move |input| {
-            match input {
-                TransactionMsg::ProposeTransaction(txids, reply) => {
-                    let logs = pool.logs().clone();
-
-                    A(A(logs.exists(txids).and_then(|rep| {
-                        reply.reply_ok(rep);
-                        future::ok(())
-                    })))
-                }
-                TransactionMsg::SendTransaction(origin, txs) => {
-                    // TODO? stats_counter.add_tx_recv_cnt(txs.len());
+        restore.and_then(move |()| {
+            input.for_each(move |input| {
+                match input {
+                    TransactionMsg::ProposeTransaction(txids, reply) => {
+                        let logs = pool.logs().clone();
 
-                    // Note that we cannot use apply_block here, since we don't have a valid context to which to apply
-                    // those blocks. one valid tx in a given context, could be invalid in another. for example
-                    // fee calculations, existence utxo / account solvency.
+                        A(A(logs.exists(txids).and_then(|rep| {
+                            reply.reply_ok(rep);
+                            future::ok(())
+                        })))
+                    }
+                    TransactionMsg::SendTransaction(origin, txs) => {
+                        // TODO? stats_counter.add_tx_recv_cnt(txs.len());
 
-                    // FIXME/TODO check that the txs are valid within themselves with basic requirements (e.g. inputs >= outputs).
-                    // we also want to keep a basic capability to filter away repetitive queries or definitely discarded txid.
+                        // Note that we cannot use apply_block here, since we don't have a valid context to which to apply
+                        // those blocks. one valid tx in a given context, could be invalid in another. for example
+                        // fee calculations, existence utxo / account solvency.
 
-                    // This interface only makes sense for messages coming from arbitrary users (like transaction, certificates),
-                    // for other message we don't want to receive them through this interface, and possibly
-                    // put them in another pool.
+                        // FIXME/TODO check that the txs are valid within themselves with basic requirements (e.g. inputs >= outputs).
+                        // we also want to keep a basic capability to filter away repetitive queries or definitely discarded txid.
 
-                    let mut pool_copy = pool_copy.clone();
-                    let stats_counter = stats_counter.clone();
+                        // This interface only makes sense for messages coming from arbitrary users (like transaction, certificates),
+                        // for other message we don't want to receive them through this interface, and possibly
+                        // put them in another pool.
 
-                    A(B(stream::iter_ok(txs).for_each(move |tx| {
+                        let mut pool_copy = pool_copy.clone();
                         let stats_counter = stats_counter.clone();
-                        pool_copy.insert(origin, tx).map(move |inserted| {
-                            if inserted {
-                                stats_counter.add_tx_recv_cnt(1)
-                            }
-                        })
-                    })))
-                }
-                TransactionMsg::GetTransactions(_txids, _handler) => {
-                    // this function is no yet implemented, this is not handled in the
-                    B(future::ok(unimplemented!()))
+
+                        A(B(A(stream::iter_ok(txs).for_each(move |tx| {
+                            let stats_counter = stats_counter.clone();
+                            pool_copy.insert(origin, tx).map(move |inserted| {
+                                if inserted {
+                                    stats_counter.add_tx_recv_cnt(1)
+                                }
+                            })
+                        }))))
+                    }
+                    TransactionMsg::PutBackFragments(fragments) => {
+                        A(B(B(pool_copy.clone().put_back(fragments))))
+                    }
+                    TransactionMsg::GetTransactions(_txids, _handler) => {
+                        // this function is no yet implemented, this is not handled in the
+                        B(future::ok(unimplemented!()))
+                    }
                 }
-            }
+            })
         })
     }
 
+    fn start_pool_persistence(
+        &self,
+        persistence: Persistence,
+        logger: Logger,
+    ) -> impl Future<Item = (), Error = ()> {
+        let pool = self.pool().clone();
+        let error_logger = logger.clone();
+        Interval::new_interval(persistence.interval())
+            .map_err(move |error| {
+                error!(error_logger, "Cannot schedule the MemPool saving" ; "reason" => error.to_string());
+            })
+            .for_each(move |_instant| {
+                let logger = logger.clone();
+                debug!(logger, "save the MemPool in the storage");
+                pool.persist(&persistence).then(move |result| {
+                    if let Err(error) = result {
+                        warn!(logger, "Cannot save the MemPool" ; "reason" => error.to_string());
+                    }
+                    Ok(())
+                })
+            })
+    }
+
     fn start_pool_garbage_collector(&self, logger: Logger) -> impl Future<Item = (), Error = ()> {
         let mut pool = self.pool().clone();
         let garbage_collection_interval = self.garbage_collection_interval;
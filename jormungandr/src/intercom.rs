@@ -1,4 +1,5 @@
 use crate::blockcfg::{Block, Epoch, Header, HeaderHash, Message, MessageId};
+use crate::blockchain::TipGetError;
 use crate::network::p2p::topology::NodeId;
 use futures::prelude::*;
 use futures::sync::{mpsc, oneshot};
@@ -76,6 +77,15 @@ impl From<chain_storage::error::Error> for Error {
     }
 }
 
+impl From<TipGetError> for Error {
+    fn from(err: TipGetError) -> Self {
+        Error {
+            code: core_error::Code::Internal,
+            cause: err.into(),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         Display::fmt(&self.cause, f)
@@ -230,6 +240,9 @@ pub enum TransactionMsg {
     ProposeTransaction(Vec<MessageId>, ReplyHandle<Vec<bool>>),
     SendTransaction(FragmentOrigin, Vec<Message>),
     GetTransactions(Vec<MessageId>, ReplyStreamHandle<Message>),
+    /// the fragments of blocks abandoned when the tip moved to another
+    /// branch, pending again
+    PutBackFragments(Vec<Message>),
 }
 
 /// Client messages, mainly requests from connected peers to our node.
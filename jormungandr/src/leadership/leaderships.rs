@@ -1,10 +1,16 @@
-use crate::blockcfg::{ChainLength, Epoch, Header, HeaderHash};
+use crate::blockcfg::{ChainLength, Epoch, Header, HeaderHash, Ledger};
 use chain_core::property::Header as _;
 use chain_impl_mockchain::multiverse::{GCRoot, Multiverse};
 use std::collections::{BTreeMap, HashSet};
 
 pub use chain_impl_mockchain::leadership::Leadership;
 
+/// number of epochs between the stake distribution snapshot and the epoch
+/// whose leaders are elected with it: the leaders of the epoch `N` are
+/// elected with the stake distribution at the end of the epoch `N - 2`,
+/// which all the branches within the stability depth agree on.
+pub const STAKE_SNAPSHOT_LAG: Epoch = 2;
+
 /// structure containing the leaderships at different
 /// time of the blockchain.
 pub struct Leaderships {
@@ -12,14 +18,27 @@ pub struct Leaderships {
 
     anchors: BTreeMap<Epoch, HashSet<HeaderHash>>,
 
+    /// the ledger state after the block0, used to elect the leaders of the
+    /// epochs without a snapshot old enough
+    initial_snapshot: Ledger,
+
+    /// the ledger states at the end of the epochs of the tip's branch
+    snapshots: BTreeMap<Epoch, Ledger>,
+
+    /// the snapshots of the epochs before this one are not needed anymore
+    /// to elect the leaders and have been dropped, if any
+    snapshots_from: Option<Epoch>,
+
     pub tip: GCRoot,
 }
 
 impl Leaderships {
-    /// Leadership object need to be construction with initial data
-    pub fn new(block_0_header: &Header, initial: Leadership) -> Self {
+    /// Leadership object need to be construction with initial data: the
+    /// ledger state after the block0
+    pub fn new(block_0_header: &Header, initial_snapshot: &Ledger) -> Self {
         let mut multiverse = Multiverse::new();
         let mut anchors = BTreeMap::new();
+        let initial = Leadership::new(block_0_header.date().epoch, initial_snapshot);
 
         let gc_root =
             multiverse.insert(block_0_header.chain_length(), block_0_header.id(), initial);
@@ -32,6 +51,9 @@ impl Leaderships {
         Leaderships {
             multiverse: multiverse,
             anchors: anchors,
+            initial_snapshot: initial_snapshot.clone(),
+            snapshots: BTreeMap::new(),
+            snapshots_from: None,
             tip: gc_root,
         }
     }
@@ -60,4 +82,60 @@ impl Leaderships {
             .insert(*gc_root);
         gc_root
     }
+
+    /// the leadership of the latest epoch a leadership has been built for
+    pub fn latest(&self) -> Option<&Leadership> {
+        self.anchors
+            .values()
+            .next_back()
+            .and_then(|set| set.iter().next())
+            .and_then(|hash| self.multiverse.get(hash))
+    }
+
+    /// keep the ledger state of the last block of the given epoch
+    pub fn snapshot(&mut self, epoch: Epoch, ledger: Ledger) {
+        self.snapshots.insert(epoch, ledger);
+    }
+
+    /// drop the snapshots of the given epoch and of the following ones,
+    /// the tip having moved to a branch forking in this epoch
+    pub fn rollback_snapshots(&mut self, epoch: Epoch) {
+        self.snapshots.split_off(&epoch);
+    }
+
+    /// drop the snapshots which will not elect the leaders of the epoch
+    /// of the tip or of the following ones
+    pub fn prune_snapshots(&mut self, tip_epoch: Epoch) {
+        let oldest_needed = tip_epoch
+            .checked_sub(STAKE_SNAPSHOT_LAG)
+            .and_then(|last| self.snapshots.range(..=last).next_back())
+            .map(|(epoch, _)| *epoch);
+        if let Some(oldest_needed) = oldest_needed {
+            let kept = self.snapshots.split_off(&oldest_needed);
+            if !self.snapshots.is_empty() {
+                self.snapshots_from = Some(oldest_needed);
+            }
+            self.snapshots = kept;
+        }
+    }
+
+    /// the ledger state whose stake distribution elects the leaders of the
+    /// given epoch, along with the epoch it is the end of (`None` for the
+    /// state after the block0)
+    pub fn stake_snapshot(&self, epoch: Epoch) -> (Option<Epoch>, &Ledger) {
+        epoch
+            .checked_sub(STAKE_SNAPSHOT_LAG)
+            .and_then(|last| self.snapshots.range(..=last).next_back())
+            .map(|(epoch, ledger)| (Some(*epoch), ledger))
+            .unwrap_or((None, &self.initial_snapshot))
+    }
+
+    /// the epochs a leadership has been built for whose stake snapshot is
+    /// still kept, in order
+    pub fn epochs<'a>(&'a self) -> impl Iterator<Item = Epoch> + 'a {
+        let first = self
+            .snapshots_from
+            .map_or(0, |snapshots_from| snapshots_from + STAKE_SNAPSHOT_LAG);
+        self.anchors.range(first..).map(|(epoch, _)| *epoch)
+    }
 }
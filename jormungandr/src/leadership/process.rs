@@ -34,6 +34,7 @@ pub struct Process {
     blockchain_tip: Tip,
 
     block_message_box: MessageBox<BlockMsg>,
+    max_fragments_per_block: usize,
 
     epoch_broadcaster: watch::Sender<Option<TaskParameters>>,
     epoch_receiver: watch::Receiver<Option<TaskParameters>>,
@@ -48,6 +49,7 @@ impl Process {
         fragment_pool: Pool,
         blockchain_tip: Tip,
         block_message_box: MessageBox<BlockMsg>,
+        max_fragments_per_block: usize,
     ) -> Self {
         let (epoch_broadcaster, epoch_receiver) = watch::channel(None);
 
@@ -58,6 +60,7 @@ impl Process {
             fragment_pool,
             blockchain_tip,
             block_message_box,
+            max_fragments_per_block,
             epoch_broadcaster,
             epoch_receiver,
         }
@@ -114,6 +117,7 @@ impl Process {
             fragment_pool,
             epoch_receiver,
             block_message,
+            self.max_fragments_per_block,
         );
 
         self.service_info.spawn(task.start())
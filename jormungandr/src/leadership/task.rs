@@ -38,6 +38,7 @@ pub struct Task {
     epoch_receiver: watch::Receiver<Option<TaskParameters>>,
     fragment_pool: Pool,
     block_message: MessageBox<BlockMsg>,
+    max_fragments_per_block: usize,
 }
 
 impl Task {
@@ -50,6 +51,7 @@ impl Task {
         fragment_pool: Pool,
         epoch_receiver: watch::Receiver<Option<TaskParameters>>,
         block_message: MessageBox<BlockMsg>,
+        max_fragments_per_block: usize,
     ) -> Self {
         let logger = Logger::root(
             logger,
@@ -67,6 +69,7 @@ impl Task {
             fragment_pool,
             epoch_receiver,
             block_message,
+            max_fragments_per_block,
         }
     }
 
@@ -78,6 +81,7 @@ impl Task {
         let blockchain_tip = self.blockchain_tip;
         let fragment_pool = self.fragment_pool;
         let block_message = self.block_message;
+        let max_fragments_per_block = self.max_fragments_per_block;
 
         self.epoch_receiver
             .map_err(|error| TaskError::LeadershipReceiver {
@@ -94,6 +98,7 @@ impl Task {
                     blockchain_tip.clone(),
                     fragment_pool.clone(),
                     task_parameters,
+                    max_fragments_per_block,
                 )
                 .map_err(|error| {
                     TaskError::LeadershipHandle { source: error }
@@ -116,6 +121,7 @@ fn handle_leadership(
     blockchain_tip: Tip,
     mut fragment_pool: Pool,
     task_parameters: TaskParameters,
+    max_fragments_per_block: usize,
 ) -> impl Future<Item = (), Error = HandleLeadershipError> {
     let schedule = LeaderSchedule::new(logger.clone(), &leader_id, &enclave, &task_parameters);
 
@@ -129,13 +135,17 @@ fn handle_leadership(
                 "scheduled_at_date" => format!("{}", scheduled_event.leader_output.date),
             );
 
+            // the block extends the tip's branch at the time of the event,
+            // following the tip when it moves to another branch
+            let branch = blockchain_tip.branch().unwrap();
             let block = prepare_block(
                 &mut fragment_pool,
-                blockchain_tip.ledger().unwrap().clone(),
+                branch.ledger().clone(),
                 &task_parameters.leadership,
                 scheduled_event.leader_output.date,
-                blockchain_tip.chain_length().unwrap().next(),
-                blockchain_tip.hash().unwrap(),
+                branch.chain_length().next(),
+                branch.hash(),
+                max_fragments_per_block,
             );
 
             let block = enclave.create_block(block, scheduled_event.leader_output);
@@ -155,10 +165,11 @@ fn prepare_block(
     date: BlockDate,
     chain_length: ChainLength,
     parent_id: HeaderHash,
+    max_fragments_per_block: usize,
 ) -> BlockBuilder {
     use crate::fragment::selection::{FragmentSelectionAlgorithm as _, OldestFirst};
 
-    let selection_algorithm = OldestFirst::new(250 /* TODO!! */);
+    let selection_algorithm = OldestFirst::new(max_fragments_per_block);
     let metadata = HeaderContentEvalContext {
         block_date: date,
         chain_length,
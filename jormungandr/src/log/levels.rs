@@ -0,0 +1,178 @@
+use crate::log::KEY_TASK;
+use slog::{Drain, Key, Level, OwnedKVList, Record, Serializer, KV};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+/// The log levels of the node: a default one and overrides for some tasks.
+///
+/// This object is safe to clone, the levels are shared by the logger
+/// filtering the records and the REST API changing them at runtime.
+#[derive(Clone)]
+pub struct LogLevels(Arc<RwLock<Levels>>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Levels {
+    pub default: Level,
+    pub tasks: BTreeMap<String, Level>,
+}
+
+impl LogLevels {
+    pub fn new(default: Level, tasks: BTreeMap<String, Level>) -> Self {
+        LogLevels(Arc::new(RwLock::new(Levels { default, tasks })))
+    }
+
+    pub fn get(&self) -> Levels {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn set_default(&self, level: Level) {
+        self.0.write().unwrap().default = level;
+    }
+
+    /// override the level of the given task, or use the default level
+    /// again for this task if `level` is `None`
+    pub fn set_task(&self, task: String, level: Option<Level>) {
+        let mut levels = self.0.write().unwrap();
+        match level {
+            Some(level) => levels.tasks.insert(task, level),
+            None => levels.tasks.remove(&task),
+        };
+    }
+}
+
+/// Drain filtering the records with the level of the task which logged
+/// them, the default level applying to the records without a task or
+/// logged by a task without an override.
+pub struct TaskLevelFilter<D> {
+    drain: D,
+    levels: LogLevels,
+}
+
+impl<D> TaskLevelFilter<D> {
+    pub fn new(drain: D, levels: LogLevels) -> Self {
+        TaskLevelFilter { drain, levels }
+    }
+}
+
+impl<D: Drain> Drain for TaskLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let enabled = {
+            let levels = self.levels.0.read().unwrap();
+            let mut task_level = TaskLevel {
+                tasks: &levels.tasks,
+                level: None,
+            };
+            // the tasks are found from the innermost to the outermost, the
+            // first one with an override decides of the level. Looking for
+            // them cannot fail, the serializer never returns an error.
+            if !levels.tasks.is_empty() {
+                let _ = values.serialize(record, &mut task_level);
+            }
+            record
+                .level()
+                .is_at_least(task_level.level.unwrap_or(levels.default))
+        };
+        if enabled {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// serializer looking for the level override of the tasks of a record
+struct TaskLevel<'a> {
+    tasks: &'a BTreeMap<String, Level>,
+    level: Option<Level>,
+}
+
+impl<'a> Serializer for TaskLevel<'a> {
+    fn emit_str(&mut self, key: Key, value: &str) -> slog::Result {
+        if self.level.is_none() && key == KEY_TASK {
+            self.level = self.tasks.get(value).cloned();
+        }
+        Ok(())
+    }
+
+    fn emit_arguments(&mut self, _key: Key, _value: &fmt::Arguments) -> slog::Result {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::{Logger, Never};
+    use std::sync::Mutex;
+
+    /// drain keeping the messages of the records it receives
+    #[derive(Clone, Default)]
+    struct Messages(Arc<Mutex<Vec<String>>>);
+
+    impl Drain for Messages {
+        type Ok = ();
+        type Err = Never;
+
+        fn log(&self, record: &Record, _: &OwnedKVList) -> Result<(), Never> {
+            self.0.lock().unwrap().push(record.msg().to_string());
+            Ok(())
+        }
+    }
+
+    fn logger(levels: &LogLevels) -> (Logger, Messages) {
+        let messages = Messages::default();
+        let drain = TaskLevelFilter::new(messages.clone(), levels.clone()).fuse();
+        (Logger::root(drain, o!()), messages)
+    }
+
+    #[test]
+    fn task_override_replaces_the_default_level() {
+        let mut tasks = BTreeMap::new();
+        tasks.insert("network".to_owned(), Level::Debug);
+        let levels = LogLevels::new(Level::Info, tasks);
+        let (logger, messages) = logger(&levels);
+        let network = logger.new(o!(KEY_TASK => "network"));
+        let block = logger.new(o!(KEY_TASK => "block"));
+
+        debug!(network, "network debug");
+        trace!(network, "network trace");
+        debug!(block, "block debug");
+        info!(block, "block info");
+        debug!(logger, "no task debug");
+
+        levels.set_task("network".to_owned(), None);
+        debug!(network, "network debug without override");
+
+        assert_eq!(
+            *messages.0.lock().unwrap(),
+            vec!["network debug".to_owned(), "block info".to_owned()]
+        );
+    }
+
+    #[test]
+    fn innermost_task_with_an_override_wins() {
+        let mut tasks = BTreeMap::new();
+        tasks.insert("network".to_owned(), Level::Trace);
+        tasks.insert("server".to_owned(), Level::Warning);
+        let levels = LogLevels::new(Level::Info, tasks);
+        let (logger, messages) = logger(&levels);
+        let network = logger.new(o!(KEY_TASK => "network"));
+        let server = network.new(o!(KEY_TASK => "server"));
+        let client = network.new(o!(KEY_TASK => "client"));
+
+        info!(server, "server info");
+        warn!(server, "server warning");
+        trace!(client, "client trace");
+
+        assert_eq!(
+            *messages.0.lock().unwrap(),
+            vec!["server warning".to_owned(), "client trace".to_owned()]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 mod asyncable_drain;
+mod levels;
 
 pub use self::asyncable_drain::AsyncableDrain;
+pub use self::levels::{Levels, LogLevels, TaskLevelFilter};
 
 pub const KEY_TASK: &str = "task";
 pub const KEY_SUB_TASK: &str = "sub_task";
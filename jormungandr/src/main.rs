@@ -22,16 +22,21 @@ extern crate hex;
 extern crate http;
 extern crate hyper;
 extern crate jormungandr_lib;
+#[cfg(test)]
+extern crate mktemp;
 extern crate native_tls;
 extern crate network_core;
 extern crate network_grpc;
+extern crate openssl;
 extern crate poldercast;
 extern crate rand_chacha;
 extern crate tokio;
+extern crate tokio_signal;
 #[macro_use]
 extern crate custom_error;
 
 #[cfg(test)]
+#[macro_use]
 extern crate quickcheck;
 extern crate rand;
 extern crate regex;
@@ -41,7 +46,8 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 extern crate serde_yaml;
-#[macro_use(o, debug, info, warn, error, crit)]
+extern crate sled;
+#[macro_use(o, trace, debug, info, warn, error, crit)]
 extern crate slog;
 extern crate slog_async;
 #[cfg(feature = "gelf")]
@@ -62,10 +68,17 @@ use crate::{
     blockchain::BlockchainR,
     secure::enclave::Enclave,
     settings::start::Settings,
-    utils::{async_msg, task::Services},
+    utils::{
+        async_msg, signal,
+        task::{RestartPolicy, Services},
+    },
+};
+use futures::{sync::oneshot, Future};
+use settings::{
+    start::{NodeMode, RawSettings},
+    Command, CommandLine, ExportStorageArguments, ImportStorageArguments, MigrateStorageArguments,
+    VerifyStorageArguments,
 };
-use futures::Future;
-use settings::{start::RawSettings, CommandLine};
 use slog::Logger;
 use std::sync::{Arc, Mutex};
 
@@ -83,12 +96,28 @@ pub mod settings;
 pub mod start_up;
 pub mod state;
 mod stats_counter;
+pub mod storage;
 pub mod utils;
 
 use stats_counter::StatsCounter;
 
 fn start() -> Result<(), start_up::Error> {
-    let initialized_node = initialize_node()?;
+    let command_line = CommandLine::load();
+    match &command_line.command {
+        Some(Command::VerifyStorage(arguments)) => return verify_storage(arguments),
+        Some(Command::ExportStorage(arguments)) => return export_storage(arguments),
+        Some(Command::ImportStorage(arguments)) => return import_storage(arguments),
+        Some(Command::MigrateStorage(arguments)) => return migrate_storage(arguments),
+        None => {}
+    }
+    if command_line.validate_config {
+        return validate_config(command_line);
+    }
+    if command_line.print_config {
+        return print_config(command_line);
+    }
+
+    let initialized_node = initialize_node(command_line)?;
 
     let bootstrapped_node = bootstrap(initialized_node)?;
 
@@ -100,67 +129,128 @@ pub struct BootstrappedNode {
     blockchain: BlockchainR,
     new_epoch_notifier: tokio::sync::mpsc::Receiver<self::leadership::EpochParameters>,
     logger: Logger,
+    log_levels: log::LogLevels,
 }
 
 const FRAGMENT_TASK_QUEUE_LEN: usize = 1024;
 const NETWORK_TASK_QUEUE_LEN: usize = 32;
+/// number of times the services processing inputs are restarted
+/// after a panic before the node stops
+const SERVICE_MAX_RESTARTS: u32 = 5;
 
 fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::Error> {
     let mut services = Services::new(bootstrapped_node.logger.clone());
+    signal::handle_shutdown_signals(
+        services.shutdown_trigger(),
+        bootstrapped_node.logger.new(o!(log::KEY_TASK => "signals")),
+    );
 
     // initialize the network propagation channel
-    let (mut network_msgbox, network_queue) = async_msg::channel(NETWORK_TASK_QUEUE_LEN);
+    let (network_msgbox, network_queue) = async_msg::channel(NETWORK_TASK_QUEUE_LEN);
     let (fragment_msgbox, fragment_queue) = async_msg::channel(FRAGMENT_TASK_QUEUE_LEN);
     let new_epoch_notifier = bootstrapped_node.new_epoch_notifier;
 
+    let network_queue_depth = network_queue.depth();
+    let (network_shutdown, network_shutdown_signal) = oneshot::channel();
+    let fragment_queue_depth = fragment_queue.depth();
+
+    // a mirror node never creates blocks, whatever its secrets, and has no
+    // MemPool: the fragments it receives are dropped
+    let is_mirror = bootstrapped_node.settings.mode == NodeMode::Mirror;
+
     let stats_counter = StatsCounter::default();
+    let running_config = Arc::new(bootstrapped_node.settings.running_config());
+    let mempool = bootstrapped_node.settings.mempool.clone();
+
+    let mempool_persistence = bootstrapped_node
+        .settings
+        .storage
+        .as_ref()
+        .filter(|_| !is_mirror)
+        .map(|dir| fragment::Persistence::new(dir, *mempool.persistence_interval.as_ref()));
 
     let (fragment_pool, pool_logs) = {
         let stats_counter = stats_counter.clone();
-        use std::time::Duration;
-        // TODO: get the TTL and from the settings
         let process = fragment::Process::new(
-            // TTL of a fragment in the MemPool: 1h
-            Duration::from_secs(3600),
-            // TTL of a MemPool log: 2h
-            Duration::from_secs(3600 * 2),
-            // Interval between GC pauses: 15min
-            Duration::from_secs(3600 / 4),
+            *mempool.fragment_ttl.as_ref(),
+            *mempool.log_ttl.as_ref(),
+            *mempool.garbage_collection_interval.as_ref(),
         );
+        let process = match mempool_persistence.as_ref() {
+            Some(persistence) => {
+                let restored = persistence
+                    .restore(&bootstrapped_node.blockchain.lock_read())
+                    .unwrap_or_else(|error| {
+                        warn!(
+                            bootstrapped_node.logger,
+                            "cannot restore the MemPool, starting with an empty one: {}", error
+                        );
+                        None
+                    });
+                process.with_persistence(persistence.clone(), restored)
+            }
+            None => process,
+        };
 
         let pool = process.pool().clone();
         let logs = process.logs().clone();
 
-        services.spawn_future("fragment", move |info| {
-            process.start(info, stats_counter, fragment_queue)
-        });
+        if !is_mirror {
+            services.spawn_future("fragment", move |info| {
+                process.start(info, stats_counter, fragment_queue)
+            });
+            services.watch_queue("fragment", fragment_queue_depth);
+        }
         (pool, logs)
     };
 
+    // the inputs of the fragment service, the REST server rejects the
+    // fragments itself on a mirror node
+    let node_fragment_msgbox = if is_mirror {
+        None
+    } else {
+        Some(fragment_msgbox.clone())
+    };
+
     let block_task = {
         let blockchain = bootstrapped_node.blockchain.clone();
         let stats_counter = stats_counter.clone();
-        services.spawn_future_with_inputs("block", move |info, input| {
-            blockchain::handle_input(
-                info,
-                &blockchain,
-                &stats_counter,
-                &mut network_msgbox,
-                input,
-            )
+        let fragment_msgbox = node_fragment_msgbox.clone();
+        let policy = RestartPolicy::Restart {
+            max_restarts: SERVICE_MAX_RESTARTS,
+        };
+        services.spawn_future_with_inputs("block", policy, move || {
+            let blockchain = blockchain.clone();
+            let stats_counter = stats_counter.clone();
+            let mut network_msgbox = network_msgbox.clone();
+            let mut fragment_msgbox = fragment_msgbox.clone();
+            move |info, input| {
+                blockchain::handle_input(
+                    info,
+                    &blockchain,
+                    &stats_counter,
+                    &mut network_msgbox,
+                    &mut fragment_msgbox,
+                    input,
+                )
+            }
         })
     };
 
     let client_task = {
         let blockchain = bootstrapped_node.blockchain.clone();
-        services.spawn_with_inputs("client-query", move |info, input| {
-            client::handle_input(info, &blockchain, input)
+        let policy = RestartPolicy::Restart {
+            max_restarts: SERVICE_MAX_RESTARTS,
+        };
+        services.spawn_with_inputs("client-query", policy, move || {
+            let blockchain = blockchain.clone();
+            move |info, input| client::handle_input(info, &blockchain, input)
         })
     };
 
     {
         let client_msgbox = client_task.clone();
-        let fragment_msgbox = fragment_msgbox.clone();
+        let fragment_msgbox = node_fragment_msgbox;
         let block_msgbox = block_task.clone();
         let config = bootstrapped_node.settings.network.clone();
         let channels = network::Channels {
@@ -170,14 +260,22 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
         };
 
         services.spawn("network", move |info| {
-            network::run(config, network_queue, channels, info.into_logger());
+            network::run(
+                config,
+                network_queue,
+                channels,
+                network_shutdown_signal,
+                info.into_logger(),
+            );
         });
+        services.watch_queue("network", network_queue_depth);
     }
 
     let leader_secrets: Result<Vec<Leader>, start_up::Error> = bootstrapped_node
         .settings
         .leadership
         .iter()
+        .filter(|_| !is_mirror)
         .map(|secret_path| {
             let secret = secure::NodeSecret::load_from_file(secret_path.as_path())?;
             Ok(Leader {
@@ -189,12 +287,13 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
     let leader_secrets = leader_secrets?;
     let enclave = Enclave::from_vec(leader_secrets);
 
-    {
+    if !is_mirror {
         let fragment_pool = fragment_pool.clone();
         let block_task = block_task.clone();
         let blockchain = bootstrapped_node.blockchain.clone();
 
         let enclave = enclave.clone();
+        let max_fragments_per_block = mempool.max_fragments_per_block;
 
         services.spawn_future("leadership", move |info| {
             let process = self::leadership::Process::new(
@@ -202,6 +301,7 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
                 fragment_pool,
                 blockchain.lock_read().tip.clone(),
                 block_task,
+                max_fragments_per_block,
             );
 
             process.start(enclave, new_epoch_notifier)
@@ -215,18 +315,50 @@ fn start_services(bootstrapped_node: BootstrappedNode) -> Result<(), start_up::E
                 blockchain: bootstrapped_node.blockchain.clone(),
                 transaction_task: Arc::new(Mutex::new(fragment_msgbox)),
                 logs: Arc::new(Mutex::new(pool_logs)),
+                services: services.status(),
+                shutdown: services.shutdown_trigger(),
+                config: running_config,
+                log_levels: bootstrapped_node.log_levels,
             };
             Some(rest::start_rest_server(&rest, context)?)
         }
         None => None,
     };
 
-    services.wait_all();
+    let shutdown_logger = bootstrapped_node
+        .logger
+        .new(o!(log::KEY_TASK => "shutdown"));
+    let reason = services.wait();
 
     if let Some(server) = rest_server {
-        server.stop().wait().unwrap()
+        if let Err(error) = server.stop().wait() {
+            warn!(shutdown_logger, "cannot stop the REST server: {}", error);
+        }
+    }
+
+    let reason = reason.map_err(|source| start_up::Error::Service { source })?;
+    info!(shutdown_logger, "shutting down: {}", reason);
+
+    // no more blocks are created once the leadership is stopped, the blocks
+    // already in the queue are processed before the tip is saved
+    services.stop("leadership");
+    services.drain("block");
+    let flushed = bootstrapped_node.blockchain.lock_write().flush();
+    if let Some(persistence) = mempool_persistence {
+        if let Err(error) = fragment_pool.persist(&persistence).wait() {
+            warn!(shutdown_logger, "cannot save the MemPool: {}", error);
+        }
     }
 
+    let _ = network_shutdown.send(());
+    services.stop("network");
+    services.stop_all();
+
+    flushed.map_err(|source| start_up::Error::StorageFlush {
+        source,
+        reason: start_up::ErrorKind::Tip,
+    })?;
+    info!(shutdown_logger, "shutdown complete");
     Ok(())
 }
 
@@ -247,14 +379,23 @@ fn bootstrap(initialized_node: InitializedNode) -> Result<BootstrappedNode, star
         settings,
         block0,
         storage,
+        pruner,
+        flusher,
         logger,
+        log_levels,
     } = initialized_node;
     let bootstrap_logger = logger.new(o!(log::KEY_TASK => "bootstrap"));
 
     let (new_epoch_announcements, new_epoch_notifier) = tokio::sync::mpsc::channel(100);
 
-    let blockchain =
-        start_up::load_blockchain(block0, storage, new_epoch_announcements, &bootstrap_logger)?;
+    let blockchain = start_up::load_blockchain(
+        block0,
+        storage,
+        pruner,
+        flusher,
+        new_epoch_announcements,
+        &bootstrap_logger,
+    )?;
 
     network::bootstrap(&settings.network, blockchain.clone(), &bootstrap_logger);
 
@@ -263,6 +404,7 @@ fn bootstrap(initialized_node: InitializedNode) -> Result<BootstrappedNode, star
         blockchain,
         new_epoch_notifier,
         logger,
+        log_levels,
     })
 }
 
@@ -270,17 +412,19 @@ pub struct InitializedNode {
     pub settings: Settings,
     pub block0: blockcfg::Block,
     pub storage: start_up::NodeStorage,
+    pub pruner: Option<storage::Pruner>,
+    pub flusher: Option<storage::Flusher>,
     pub logger: Logger,
+    pub log_levels: log::LogLevels,
 }
 
-fn initialize_node() -> Result<InitializedNode, start_up::Error> {
-    let command_line = CommandLine::load();
+fn initialize_node(command_line: CommandLine) -> Result<InitializedNode, start_up::Error> {
     let raw_settings = RawSettings::load(command_line)?;
-    let logger = raw_settings.to_logger()?;
+    let (logger, log_levels) = raw_settings.to_logger()?;
 
     let init_logger = logger.new(o!(log::KEY_TASK => "init"));
     let settings = raw_settings.try_into_settings(&init_logger)?;
-    let storage = start_up::prepare_storage(&settings, &init_logger)?;
+    let (storage, pruner, flusher) = start_up::prepare_storage(&settings, &init_logger)?;
 
     // TODO: load network module here too (if needed)
 
@@ -294,18 +438,169 @@ fn initialize_node() -> Result<InitializedNode, start_up::Error> {
         settings,
         block0,
         storage,
+        pruner,
+        flusher,
         logger,
+        log_levels,
     })
 }
 
-fn main() {
-    use std::error::Error;
+/// check the whole configuration of the node without starting it, every
+/// error found is printed
+fn validate_config(command_line: CommandLine) -> Result<(), start_up::Error> {
+    let raw_settings = RawSettings::load(command_line)?;
+    let (logger, _) = raw_settings.to_logger()?;
+    let init_logger = logger.new(o!(log::KEY_TASK => "init"));
 
-    if let Err(error) = start() {
-        eprintln!("{}", error);
-        if let Some(source) = error.source() {
-            eprintln!("{}", source);
+    let mut errors: Vec<start_up::Error> = raw_settings
+        .validate()
+        .into_iter()
+        .map(|error| error.into())
+        .collect();
+    // the settings are only checked further if they can be built
+    if errors.is_empty() {
+        let settings = raw_settings.try_into_settings(&init_logger)?;
+        errors.extend(start_up::validate(&settings, &init_logger));
+    }
+
+    if errors.is_empty() {
+        println!("the node configuration is valid");
+        return Ok(());
+    }
+    for error in &errors {
+        print_error(error);
+    }
+    Err(start_up::Error::InvalidConfiguration {
+        count: errors.len(),
+    })
+}
+
+/// print the effective settings of the node, as YAML, without starting it
+fn print_config(command_line: CommandLine) -> Result<(), start_up::Error> {
+    let raw_settings = RawSettings::load(command_line)?;
+    let (logger, _) = raw_settings.to_logger()?;
+    let init_logger = logger.new(o!(log::KEY_TASK => "init"));
+    let settings = raw_settings.try_into_settings(&init_logger)?;
+
+    let config = serde_yaml::to_string(&settings.running_config())
+        .map_err(|source| start_up::Error::PrintConfiguration { source })?;
+    println!("{}", config);
+    Ok(())
+}
+
+fn verify_storage(arguments: &VerifyStorageArguments) -> Result<(), start_up::Error> {
+    let storage = start_up::open_storage(&arguments.storage)?;
+    let report = blockchain::verify_storage(&storage, arguments.block_0_hash.as_ref())?;
+
+    print!("{}", report);
+    match report.inconsistency {
+        None => {
+            println!("the storage is consistent");
+            Ok(())
         }
+        Some(inconsistency) => Err(start_up::Error::InconsistentStorage { inconsistency }),
+    }
+}
+
+fn export_storage(arguments: &ExportStorageArguments) -> Result<(), start_up::Error> {
+    let storage = start_up::open_storage(&arguments.storage)?;
+    let archive =
+        std::fs::File::create(&arguments.output).map_err(|source| start_up::Error::IO {
+            source,
+            reason: start_up::ErrorKind::Archive,
+        })?;
+
+    let manifest = blockchain::export(
+        &storage,
+        arguments.from.as_ref(),
+        arguments.to.as_ref(),
+        std::io::BufWriter::new(archive),
+    )?;
+    println!("{} blocks exported", manifest.blocks.len());
+    Ok(())
+}
+
+fn import_storage(arguments: &ImportStorageArguments) -> Result<(), start_up::Error> {
+    let archive = std::fs::File::open(&arguments.input).map_err(|source| start_up::Error::IO {
+        source,
+        reason: start_up::ErrorKind::Archive,
+    })?;
+    let existing_backend = start_up::storage_backend(&arguments.storage);
+    let backend = existing_backend.or(arguments.backend).unwrap_or_default();
+    let mut storage = start_up::create_storage(&arguments.storage, backend)?;
+    let block_0_id = arguments.block_0_hash.as_ref();
+
+    let input = std::io::BufReader::new(archive);
+
+    // a failed import leaves the tip of the storage where it was, a
+    // storage created for the import is removed
+    let error = match blockchain::import(&mut storage, block_0_id, input) {
+        Err(source) => start_up::Error::Archive { source },
+        Ok(imported) => {
+            println!("{} blocks imported", imported.manifest.blocks.len());
+            return Ok(());
+        }
+    };
+    std::mem::drop(storage);
+    if existing_backend.is_none() {
+        let _ = start_up::remove_storage(&arguments.storage, backend);
+    }
+    Err(error)
+}
+
+fn migrate_storage(arguments: &MigrateStorageArguments) -> Result<(), start_up::Error> {
+    let dir = &arguments.storage;
+    let to = start_up::storage_path(dir, arguments.to);
+    if to.exists() {
+        return Err(start_up::Error::StorageExists {
+            path: to.display().to_string(),
+        });
+    }
+    let backend =
+        start_up::storage_backend(dir).ok_or_else(|| start_up::Error::StorageNotFound {
+            path: dir.display().to_string(),
+        })?;
+    let from = start_up::storage_path(dir, backend);
+    let storage = start_up::create_storage(dir, backend)?;
+    let mut migrated_storage = start_up::create_storage(dir, arguments.to)?;
+
+    // a failed migration leaves the storage as it was
+    let migrated = blockchain::migrate(&storage, &mut migrated_storage);
+    std::mem::drop(migrated_storage);
+    std::mem::drop(storage);
+    let blocks = match migrated {
+        Ok(blocks) => blocks,
+        Err(source) => {
+            let _ = start_up::remove_storage(dir, arguments.to);
+            return Err(start_up::Error::StorageMigration { source });
+        }
+    };
+
+    let kept = from.with_extension(format!("{}.migrated", backend));
+    std::fs::rename(&from, &kept).map_err(|source| start_up::Error::IO {
+        source,
+        reason: start_up::ErrorKind::Storage,
+    })?;
+    println!(
+        "{} blocks migrated from {} to {}, the previous database is kept in '{}'",
+        blocks,
+        backend,
+        arguments.to,
+        kept.display()
+    );
+    Ok(())
+}
+
+fn print_error(error: &dyn std::error::Error) {
+    eprintln!("{}", error);
+    if let Some(source) = error.source() {
+        eprintln!("{}", source);
+    }
+}
+
+fn main() {
+    if let Err(error) = start() {
+        print_error(&error);
 
         // TODO: https://github.com/rust-lang/rust/issues/43301
         //
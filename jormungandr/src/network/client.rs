@@ -131,10 +131,13 @@ where
     fn process_block_event(&mut self, event: BlockEvent<S::Block>) {
         match event {
             BlockEvent::Announce(header) => {
+                let logger = &self.logger;
                 self.channels
                     .block_box
                     .try_send(BlockMsg::AnnouncedBlock(header, self.remote_node_id))
-                    .unwrap();
+                    .unwrap_or_else(|err| {
+                        error!(logger, "cannot send the block announcement: {}", err)
+                    });
             }
             BlockEvent::Solicit(block_ids) => {
                 let (reply_handle, stream) = intercom::stream_reply::<
@@ -199,6 +202,7 @@ where
                 let node_id = self.remote_node_id.clone();
                 let err_logger = self.logger.clone();
                 let and_then_logger = self.logger.clone();
+                let send_logger = self.logger.clone();
                 tokio::spawn(
                     self.service
                         .pull_headers(&from, &to)
@@ -216,7 +220,12 @@ where
                                 .for_each(move |header| {
                                     block_box
                                         .try_send(BlockMsg::AnnouncedBlock(header, node_id))
-                                        .unwrap();
+                                        .unwrap_or_else(|err| {
+                                            error!(
+                                                send_logger,
+                                                "cannot send the pulled header: {}", err
+                                            )
+                                        });
                                     Ok(())
                                 })
                                 .map_err(move |e| {
@@ -242,6 +251,7 @@ where
         let mut block_box = self.channels.block_box.clone();
         let err_logger = self.logger.clone();
         let and_then_logger = self.logger.clone();
+        let send_logger = self.logger.clone();
         tokio::spawn(
             self.service
                 .get_blocks(block_ids)
@@ -251,7 +261,11 @@ where
                 .and_then(move |blocks| {
                     blocks
                         .for_each(move |block| {
-                            block_box.try_send(BlockMsg::NetworkBlock(block)).unwrap();
+                            block_box
+                                .try_send(BlockMsg::NetworkBlock(block))
+                                .unwrap_or_else(|err| {
+                                    error!(send_logger, "cannot send the solicited block: {}", err)
+                                });
                             Ok(())
                         })
                         .map_err(move |e| {
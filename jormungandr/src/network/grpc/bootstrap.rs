@@ -11,6 +11,13 @@ use tokio::prelude::*;
 use tokio::runtime::current_thread;
 
 pub fn bootstrap_from_peer(peer: Peer, blockchain: BlockchainR, logger: &Logger) {
+    let tip = match blockchain.lock_read().get_tip() {
+        Ok(tip) => tip,
+        Err(e) => {
+            error!(logger, "cannot bootstrap from a peer: {}", e);
+            return;
+        }
+    };
     info!(logger, "connecting to bootstrap peer {}", peer.connection);
     let bootstrap = connect(peer.address(), None)
         .map_err(|e| {
@@ -22,7 +29,6 @@ pub fn bootstrap_from_peer(peer: Peer, blockchain: BlockchainR, logger: &Logger)
             })
         })
         .and_then(|mut client| {
-            let tip = blockchain.lock_read().get_tip().unwrap();
             client
                 .pull_blocks_to_tip(&[tip])
                 .map_err(|e| {
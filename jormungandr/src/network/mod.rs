@@ -18,19 +18,24 @@ use self::p2p::{
 use crate::blockcfg::{Block, HeaderHash};
 use crate::blockchain::BlockchainR;
 use crate::intercom::{BlockMsg, ClientMsg, NetworkMsg, PropagateMsg, TransactionMsg};
-use crate::settings::start::network::{Configuration, Peer, Protocol};
+use crate::settings::start::{
+    network::{Configuration, Peer, Protocol},
+    NodeMode,
+};
 use crate::utils::{
     async_msg::{MessageBox, MessageQueue},
     task::TaskMessageBox,
 };
 use futures::prelude::*;
 use futures::stream;
+use futures::sync::oneshot;
 use network_core::{
     error as core_error,
     gossip::{Gossip, Node},
 };
 use slog::Logger;
 use std::{error::Error, iter, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::runtime::Runtime;
 use tokio::timer::Interval;
 
 type Connection = SocketAddr;
@@ -40,7 +45,8 @@ pub enum BlockConfig {}
 /// all the different channels the network may need to talk to
 pub struct Channels {
     pub client_box: TaskMessageBox<ClientMsg>,
-    pub transaction_box: MessageBox<TransactionMsg>,
+    /// `None` on a mirror node, which drops the fragments it receives
+    pub transaction_box: Option<MessageBox<TransactionMsg>>,
     pub block_box: MessageBox<BlockMsg>,
 }
 
@@ -79,7 +85,12 @@ impl GlobalState {
         let mut node = topology::Node::new(node_id, node_address);
 
         // TODO: load the subscriptions from the config
-        node.add_message_subscription(topology::InterestLevel::High);
+        let message_interest = match config.mode {
+            NodeMode::Full => topology::InterestLevel::High,
+            // a mirror node does not accept fragments
+            NodeMode::Mirror => topology::InterestLevel::Low,
+        };
+        node.add_message_subscription(message_interest);
         node.add_block_subscription(topology::InterestLevel::High);
 
         let mut topology = P2pTopology::new(node.clone(), logger.clone());
@@ -132,10 +143,13 @@ impl ConnectionState {
     }
 }
 
+/// run the network tasks until the `shutdown` signal is received (or
+/// its sender is dropped), then close the connections with the peers
 pub fn run(
     config: Configuration,
     input: MessageQueue<NetworkMsg>,
     channels: Channels,
+    shutdown: oneshot::Receiver<()>,
     logger: Logger,
 ) {
     // TODO: the node needs to be saved/loaded
@@ -197,7 +211,18 @@ pub fn run(
             Ok(())
         });
 
-    tokio::run(listener.join4(connections, handle_cmds, gossip).map(|_| ()));
+    let network = listener
+        .join4(connections, handle_cmds, gossip)
+        .map(|_| ())
+        .select(shutdown.then(|_| Ok(())))
+        .then(|_| Ok::<(), ()>(()));
+
+    let mut runtime = Runtime::new().expect("cannot create the network runtime");
+    let _ = runtime.block_on(network);
+
+    // dropping the tasks of the runtime closes the connections
+    info!(logger, "closing the connections with the peers");
+    runtime.shutdown_now().wait().unwrap();
 }
 
 fn handle_network_input(
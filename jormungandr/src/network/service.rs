@@ -60,6 +60,15 @@ impl Node for NodeService {
     }
 }
 
+/// the error answered to the peers sending a request the node does not
+/// support yet
+fn unimplemented_request(request: &str) -> core_error::Error {
+    core_error::Error::new(
+        core_error::Code::Unimplemented,
+        format!("the `{}' request is not supported by the node", request),
+    )
+}
+
 impl From<intercom::Error> for core_error::Error {
     fn from(err: intercom::Error) -> Self {
         core_error::Error::new(err.code(), err)
@@ -131,7 +140,7 @@ impl BlockService for NodeService {
         _from: &[Self::BlockId],
         _to: &Self::BlockId,
     ) -> Self::PullBlocksFuture {
-        unimplemented!()
+        future::err(unimplemented_request("pull_blocks"))
     }
 
     fn pull_headers(
@@ -139,27 +148,30 @@ impl BlockService for NodeService {
         _from: &[Self::BlockId],
         _to: &Self::BlockId,
     ) -> Self::PullHeadersFuture {
-        unimplemented!()
+        future::err(unimplemented_request("pull_headers"))
     }
 
     fn pull_headers_to_tip(&mut self, _from: &[Self::BlockId]) -> Self::PullHeadersFuture {
-        unimplemented!()
+        future::err(unimplemented_request("pull_headers_to_tip"))
     }
 
-    fn push_headers<In>(&mut self, headers: In) -> Self::PushHeadersFuture
+    fn push_headers<In>(&mut self, _headers: In) -> Self::PushHeadersFuture
     where
         In: Stream<Item = Self::Header, Error = core_error::Error> + Send + 'static,
     {
-        unimplemented!();
-        future::ok(())
+        future::err(unimplemented_request("push_headers"))
     }
 
     fn on_uploaded_block(&mut self, block: Block) -> Self::OnUploadedBlockFuture {
-        self.channels
+        let result = self
+            .channels
             .block_box
             .try_send(BlockMsg::NetworkBlock(block))
-            .unwrap();
-        future::ok(())
+            .map_err(|err| {
+                error!(self.logger(), "cannot send the uploaded block: {}", err);
+                core_error::Error::new(core_error::Code::Internal, err.to_string())
+            });
+        future::result(result)
     }
 
     fn block_subscription<In>(
@@ -16,12 +16,15 @@ pub fn process_block_announcements<S>(
 where
     S: Stream<Item = Header, Error = core_error::Error> + Send + 'static,
 {
+    let send_logger = logger.clone();
     tokio::spawn(
         inbound
             .for_each(move |header| {
                 block_box
                     .try_send(BlockMsg::AnnouncedBlock(header, node_id))
-                    .unwrap();
+                    .unwrap_or_else(|err| {
+                        error!(send_logger, "cannot send the block announcement: {}", err)
+                    });
                 Ok(())
             })
             .map_err(move |err| {
@@ -0,0 +1,149 @@
+//! Authentication and access control of the REST API
+
+use crate::settings::start::RestAuthentication;
+use actix_web::{
+    http::{header, Method},
+    middleware::{Middleware, Started},
+    HttpRequest, HttpResponse, Result as ActixResult,
+};
+use cryptoxide::util::fixed_time_eq;
+use std::sync::Arc;
+
+/// The kind of access a route requires and a client is granted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+    /// query the state of the node and of the blockchain
+    ReadOnly,
+    /// submit messages to the node
+    Submit,
+    /// administrate the node, implies every other scope
+    Admin,
+}
+
+impl Scope {
+    fn grants(self, required: Scope) -> bool {
+        self == Scope::Admin || self == required
+    }
+}
+
+enum Rejection {
+    /// the client did not provide valid credentials
+    Unauthorized,
+    /// the client credentials do not grant the required scope
+    Forbidden,
+}
+
+/// The authentication settings shared by all the routes of the REST API.
+/// Without settings every request is accepted.
+pub struct Authentication {
+    settings: Option<RestAuthentication>,
+}
+
+impl Authentication {
+    pub fn new(settings: Option<RestAuthentication>) -> Self {
+        Authentication { settings }
+    }
+
+    /// the configured token of a bearer authorization, `None` if the
+    /// authorization does not carry one of the tokens of the settings
+    pub fn verified_token(&self, authorization: &str) -> Option<&str> {
+        let token = parse_bearer(authorization)?;
+        self.settings
+            .as_ref()?
+            .tokens
+            .iter()
+            .find(|rest_token| token_eq(&rest_token.token, token))
+            .map(|rest_token| rest_token.token.as_str())
+    }
+
+    fn check(&self, authorization: Option<&str>, required: Scope) -> Result<(), Rejection> {
+        let settings = match &self.settings {
+            Some(settings) => settings,
+            None => return Ok(()),
+        };
+        // the TLS layer already rejected the clients without a valid certificate
+        let client_certificates = settings.client_certificates.as_ref();
+        let mut granted = client_certificates
+            .map(|client_certificates| client_certificates.scopes.clone())
+            .unwrap_or_default();
+        match authorization {
+            Some(authorization) => {
+                let token = parse_bearer(authorization).ok_or(Rejection::Unauthorized)?;
+                let rest_token = settings
+                    .tokens
+                    .iter()
+                    .find(|rest_token| token_eq(&rest_token.token, token))
+                    .ok_or(Rejection::Unauthorized)?;
+                granted.extend(&rest_token.scopes);
+            }
+            None if client_certificates.is_none() => return Err(Rejection::Unauthorized),
+            None => (),
+        }
+        if granted.iter().any(|scope| scope.grants(required)) {
+            Ok(())
+        } else {
+            Err(Rejection::Forbidden)
+        }
+    }
+}
+
+fn parse_bearer(authorization: &str) -> Option<&str> {
+    let mut parts = authorization.trim().splitn(2, ' ');
+    match (parts.next(), parts.next()) {
+        (Some(scheme), Some(token)) if scheme.eq_ignore_ascii_case("bearer") => Some(token.trim()),
+        _ => None,
+    }
+}
+
+fn token_eq(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len() && fixed_time_eq(expected.as_bytes(), actual.as_bytes())
+}
+
+/// Middleware rejecting the requests not granted the given scope
+pub struct RequireScope {
+    authentication: Arc<Authentication>,
+    scope: Scope,
+    method: Option<Method>,
+}
+
+impl RequireScope {
+    pub fn new(authentication: Arc<Authentication>, scope: Scope) -> Self {
+        RequireScope {
+            authentication,
+            scope,
+            method: None,
+        }
+    }
+
+    /// only check the requests with the given method, for the resources
+    /// requiring a different scope depending on the method
+    pub fn for_method(self, method: Method) -> Self {
+        RequireScope {
+            method: Some(method),
+            ..self
+        }
+    }
+}
+
+impl<S> Middleware<S> for RequireScope {
+    fn start(&self, req: &HttpRequest<S>) -> ActixResult<Started> {
+        if let Some(method) = &self.method {
+            if req.method() != method {
+                return Ok(Started::Done);
+            }
+        }
+        let authorization = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .map(|value| value.to_str().unwrap_or(""));
+        let response = match self.authentication.check(authorization, self.scope) {
+            Ok(()) => return Ok(Started::Done),
+            Err(Rejection::Unauthorized) => HttpResponse::Unauthorized()
+                .header(header::WWW_AUTHENTICATE, "Bearer")
+                .finish(),
+            Err(Rejection::Forbidden) => HttpResponse::Forbidden().finish(),
+        };
+        Ok(Started::Response(response))
+    }
+}
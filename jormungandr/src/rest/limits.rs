@@ -0,0 +1,265 @@
+//! Limits protecting the REST API from abusive clients
+
+use crate::rest::Authentication;
+use crate::settings::start::RestLimits;
+use actix_web::{
+    http::header,
+    middleware::{Middleware, Started},
+    HttpRequest, HttpResponse, Result as ActixResult,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// the maximum size of a request body if not configured
+pub const DEFAULT_MAX_BODY_SIZE: usize = 256 * 1024;
+/// the maximum time to receive a request body if not configured
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// the maximum number of blocks replayed to rebuild a ledger state no
+/// longer held by the node, if not configured
+pub const DEFAULT_MAX_REPLAYED_BLOCKS: u64 = 10_000;
+
+/// above this number of tracked clients, the client which did not send a
+/// request for the longest time is forgotten
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// All the limits of the REST API, shared by the workers of the server
+#[derive(Clone)]
+pub struct Limits {
+    pub rate_limit: Option<RateLimit>,
+    /// shared by the queries going through the whole ledger
+    pub ledger_queries: ConcurrencyLimit,
+    pub body: BodyLimits,
+}
+
+impl Limits {
+    pub fn new(config: Option<&RestLimits>, authentication: Arc<Authentication>) -> Self {
+        let per_ip = config.and_then(|config| config.requests_per_minute_per_ip);
+        let per_token = config.and_then(|config| config.requests_per_minute_per_token);
+        let rate_limit = match (per_ip, per_token) {
+            (None, None) => None,
+            (per_ip, per_token) => Some(RateLimit {
+                per_ip: per_ip.map(|limit| Arc::new(RateLimiter::new(limit))),
+                per_token: per_token.map(|limit| Arc::new(RateLimiter::new(limit))),
+                authentication,
+            }),
+        };
+        Limits {
+            rate_limit,
+            ledger_queries: ConcurrencyLimit::new(
+                config.and_then(|config| config.max_concurrent_utxo_queries),
+            ),
+            body: BodyLimits {
+                max_size: config
+                    .and_then(|config| config.max_body_size)
+                    .unwrap_or(DEFAULT_MAX_BODY_SIZE),
+                timeout: config
+                    .and_then(|config| config.request_timeout)
+                    .map(|timeout| *timeout.as_ref())
+                    .unwrap_or(DEFAULT_REQUEST_TIMEOUT),
+            },
+        }
+    }
+}
+
+/// The limits on the body of the requests
+#[derive(Clone, Copy)]
+pub struct BodyLimits {
+    pub max_size: usize,
+    pub timeout: Duration,
+}
+
+/// Token bucket allowing `limit` requests per minute for every key
+struct RateLimiter {
+    limit: u32,
+    buckets: Mutex<Buckets>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    last_use: u64,
+}
+
+/// The buckets of the tracked clients, forgetting the least recently
+/// used one above `MAX_TRACKED_CLIENTS`
+#[derive(Default)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    by_use: BTreeMap<u64, String>,
+    uses: u64,
+}
+
+impl Buckets {
+    /// the bucket of the key, a new one being full
+    fn use_bucket(&mut self, key: &str, capacity: f64, now: Instant) -> &mut Bucket {
+        self.uses += 1;
+        let last_use = self.uses;
+        match self.by_key.get(key) {
+            Some(bucket) => {
+                self.by_use.remove(&bucket.last_use);
+            }
+            None => {
+                if self.by_key.len() >= MAX_TRACKED_CLIENTS {
+                    self.forget_least_recently_used();
+                }
+                self.by_key.insert(
+                    key.to_owned(),
+                    Bucket {
+                        tokens: capacity,
+                        updated: now,
+                        last_use,
+                    },
+                );
+            }
+        }
+        self.by_use.insert(last_use, key.to_owned());
+        let bucket = self.by_key.get_mut(key).unwrap();
+        bucket.last_use = last_use;
+        bucket
+    }
+
+    fn forget_least_recently_used(&mut self) {
+        let oldest = self.by_use.keys().next().cloned();
+        if let Some(key) = oldest.and_then(|oldest| self.by_use.remove(&oldest)) {
+            self.by_key.remove(&key);
+        }
+    }
+}
+
+impl RateLimiter {
+    fn new(limit: u32) -> Self {
+        RateLimiter {
+            limit,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        f64::from(self.limit) / 60.0
+    }
+
+    /// take a token from the bucket of the key, on failure returns the
+    /// number of seconds to wait for the next token
+    fn acquire(&self, key: &str) -> Result<(), u64> {
+        let now = Instant::now();
+        let capacity = f64::from(self.limit);
+        let refill_per_sec = self.refill_per_sec();
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.use_bucket(key, capacity, now);
+        bucket.tokens =
+            (bucket.tokens + refill_per_sec * secs_since(bucket.updated, now)).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / refill_per_sec).ceil() as u64)
+        }
+    }
+}
+
+fn secs_since(earlier: Instant, now: Instant) -> f64 {
+    let elapsed = now.duration_since(earlier);
+    elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9
+}
+
+fn too_many_requests(retry_after: u64) -> Started {
+    Started::Response(
+        HttpResponse::TooManyRequests()
+            .header(header::RETRY_AFTER, retry_after.to_string())
+            .finish(),
+    )
+}
+
+/// Middleware rejecting the requests of the clients exceeding their
+/// number of requests per minute, per IP address and per bearer token.
+/// Only the tokens of the settings have a bucket of their own, the
+/// requests with another token count for the IP address of the client.
+#[derive(Clone)]
+pub struct RateLimit {
+    per_ip: Option<Arc<RateLimiter>>,
+    per_token: Option<Arc<RateLimiter>>,
+    authentication: Arc<Authentication>,
+}
+
+impl<S> Middleware<S> for RateLimit {
+    fn start(&self, req: &HttpRequest<S>) -> ActixResult<Started> {
+        let ip = req.peer_addr().map(|addr| addr.ip());
+        if let Some(per_ip) = &self.per_ip {
+            if let Err(retry_after) = per_ip.acquire(&ip_key(ip)) {
+                return Ok(too_many_requests(retry_after));
+            }
+        }
+        if let Some(per_token) = &self.per_token {
+            let token = req
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|authorization| self.authentication.verified_token(authorization));
+            let key = match token {
+                Some(token) => format!("token:{}", token),
+                None => ip_key(ip),
+            };
+            if let Err(retry_after) = per_token.acquire(&key) {
+                return Ok(too_many_requests(retry_after));
+            }
+        }
+        Ok(Started::Done)
+    }
+}
+
+fn ip_key(ip: Option<IpAddr>) -> String {
+    match ip {
+        Some(ip) => format!("ip:{}", ip),
+        None => "ip:".to_owned(),
+    }
+}
+
+/// Middleware rejecting the requests above the given number of requests
+/// being processed at the same time
+#[derive(Clone)]
+pub struct ConcurrencyLimit {
+    max: Option<usize>,
+    current: Arc<AtomicUsize>,
+}
+
+/// Releases its slot of the concurrency limit when the request is dropped
+struct ConcurrencySlot(Arc<AtomicUsize>);
+
+impl Drop for ConcurrencySlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max: Option<usize>) -> Self {
+        ConcurrencyLimit {
+            max,
+            current: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+impl<S> Middleware<S> for ConcurrencyLimit {
+    fn start(&self, req: &HttpRequest<S>) -> ActixResult<Started> {
+        let max = match self.max {
+            Some(max) => max,
+            None => return Ok(Started::Done),
+        };
+        if self.current.fetch_add(1, Ordering::SeqCst) >= max {
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            return Ok(too_many_requests(1));
+        }
+        req.extensions_mut()
+            .insert(ConcurrencySlot(self.current.clone()));
+        Ok(Started::Done)
+    }
+}
@@ -1,20 +1,26 @@
 //! REST API of the node
 
+mod auth;
+mod limits;
 mod server;
 
 pub mod v0;
 
+pub use self::auth::{Authentication, RequireScope, Scope};
+pub use self::limits::{BodyLimits, Limits, DEFAULT_MAX_REPLAYED_BLOCKS};
 pub use self::server::{Error, Server};
 
 use std::sync::{Arc, Mutex};
 
 use crate::blockchain::BlockchainR;
 use crate::fragment::Logs;
-use crate::settings::start::{Error as ConfigError, Rest};
+use crate::log::LogLevels;
+use crate::settings::start::{Error as ConfigError, Rest, RunningConfig};
 use crate::stats_counter::StatsCounter;
 
 use crate::intercom::TransactionMsg;
 use crate::utils::async_msg::MessageBox;
+use crate::utils::task::{ServicesStatus, ShutdownTrigger};
 
 #[derive(Clone)]
 pub struct Context {
@@ -22,11 +28,31 @@ pub struct Context {
     pub blockchain: BlockchainR,
     pub transaction_task: Arc<Mutex<MessageBox<TransactionMsg>>>,
     pub logs: Arc<Mutex<Logs>>,
+    pub services: ServicesStatus,
+    pub shutdown: ShutdownTrigger,
+    pub config: Arc<RunningConfig>,
+    pub log_levels: LogLevels,
 }
 
 pub fn start_rest_server(config: &Rest, context: Context) -> Result<Server, ConfigError> {
-    Server::start(config.pkcs12.clone(), config.listen.clone(), move || {
-        vec![v0::app(context.clone()).boxed()]
-    })
+    let client_ca = config
+        .authentication
+        .as_ref()
+        .and_then(|authentication| authentication.client_certificates.as_ref())
+        .map(|client_certificates| client_certificates.trusted_ca.clone());
+    let request_timeout = config
+        .limits
+        .as_ref()
+        .and_then(|limits| limits.request_timeout)
+        .map(|timeout| *timeout.as_ref());
+    let authentication = Arc::new(Authentication::new(config.authentication.clone()));
+    let limits = Limits::new(config.limits.as_ref(), authentication.clone());
+    Server::start(
+        config.pkcs12.clone(),
+        client_ca,
+        config.listen.clone(),
+        request_timeout,
+        move || vec![v0::app(context.clone(), authentication.clone(), limits.clone()).boxed()],
+    )
     .map_err(|e| e.into())
 }
@@ -1,4 +1,5 @@
 use native_tls::Error as TlsError;
+use openssl::error::ErrorStack;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::io::Error as IoError;
@@ -7,6 +8,10 @@ use std::io::Error as IoError;
 pub enum Error {
     Pkcs12LoadFailed(IoError),
     Pkcs12Invalid(TlsError),
+    Pkcs12InvalidForClientAuth(ErrorStack),
+    ClientCaInvalid(ErrorStack),
+    ClientCaWithoutPkcs12,
+    TlsSetupFailed(ErrorStack),
     BindFailed(IoError),
     ServerAlreadyStopped,
     ServerStopTimeout,
@@ -18,6 +23,16 @@ impl Display for Error {
         match *self {
             Error::Pkcs12LoadFailed(_) => write!(f, "Failed to load PKCS12 identity file"),
             Error::Pkcs12Invalid(_) => write!(f, "Invalid PKCS12 identity file"),
+            Error::Pkcs12InvalidForClientAuth(_) => write!(
+                f,
+                "Invalid PKCS12 identity file for client certificate verification"
+            ),
+            Error::ClientCaInvalid(_) => write!(f, "Invalid trusted client CA file"),
+            Error::ClientCaWithoutPkcs12 => write!(
+                f,
+                "Client certificate verification requires a PKCS12 identity file"
+            ),
+            Error::TlsSetupFailed(_) => write!(f, "Failed to set up TLS"),
             Error::BindFailed(_) => write!(f, "Failed to bind the port"),
             Error::ServerAlreadyStopped => write!(f, "Couldn't stop server, it's already stopped"),
             Error::ServerStopTimeout => write!(f, "Timeout during server stopping"),
@@ -31,6 +46,9 @@ impl StdError for Error {
         match *self {
             Error::Pkcs12LoadFailed(ref cause) => Some(cause),
             Error::Pkcs12Invalid(ref cause) => Some(cause),
+            Error::Pkcs12InvalidForClientAuth(ref cause) => Some(cause),
+            Error::ClientCaInvalid(ref cause) => Some(cause),
+            Error::TlsSetupFailed(ref cause) => Some(cause),
             Error::BindFailed(ref cause) => Some(cause),
             _ => None,
         }
@@ -12,17 +12,32 @@ use actix_web::{
 };
 use futures::Future;
 use native_tls::{Identity, TlsAcceptor};
+use openssl::{
+    pkcs12::Pkcs12,
+    ssl::{SslAcceptor, SslAcceptorBuilder, SslMethod, SslVerifyMode},
+    x509::X509Name,
+};
 
 use std::{
     fs,
     net::{SocketAddr, ToSocketAddrs},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::mpsc::sync_channel,
     thread,
+    time::Duration,
 };
 
 pub type ServerResult<T> = Result<T, Error>;
 
+/// The TLS layer of the server
+pub enum Tls {
+    /// Serve with the PKCS12 identity
+    Identity(TlsAcceptor),
+    /// Serve with the PKCS12 identity and require the clients to present
+    /// a certificate signed by one of the trusted CAs
+    ClientAuth(SslAcceptorBuilder),
+}
+
 #[derive(Clone)]
 pub struct Server {
     addr: Addr<ActixServer>,
@@ -31,18 +46,21 @@ pub struct Server {
 impl Server {
     pub fn start<F, H>(
         pkcs12: Option<PathBuf>,
+        client_ca: Option<PathBuf>,
         address: SocketAddr,
+        client_timeout: Option<Duration>,
         handler: F,
     ) -> ServerResult<Self>
     where
         F: Fn() -> H + Clone + Send + 'static,
         H: IntoHttpHandler + 'static,
     {
-        let tls = load_tls_acceptor(pkcs12)?;
+        let tls = load_tls(pkcs12, client_ca)?;
         let (sender, receiver) = sync_channel::<ServerResult<Server>>(0);
         thread::spawn(move || {
             let actix_system = System::builder().build();
-            let server_handler = start_server_curr_actix_system(address, tls, handler);
+            let server_handler =
+                start_server_curr_actix_system(address, tls, client_timeout, handler);
             let run_system = server_handler.is_ok();
             let _ = sender.send(server_handler);
             if run_system {
@@ -64,6 +82,19 @@ impl Server {
     }
 }
 
+pub fn load_tls(
+    pkcs12_opt: Option<PathBuf>,
+    client_ca_opt: Option<PathBuf>,
+) -> ServerResult<Option<Tls>> {
+    match (pkcs12_opt, client_ca_opt) {
+        (pkcs12_opt, None) => Ok(load_tls_acceptor(pkcs12_opt)?.map(Tls::Identity)),
+        (None, Some(_)) => Err(Error::ClientCaWithoutPkcs12),
+        (Some(pkcs12), Some(client_ca)) => {
+            load_client_auth_acceptor(pkcs12, &client_ca).map(|tls| Some(Tls::ClientAuth(tls)))
+        }
+    }
+}
+
 pub fn load_tls_acceptor(pkcs12_opt: Option<PathBuf>) -> ServerResult<Option<TlsAcceptor>> {
     let pkcs12_path = match pkcs12_opt {
         Some(pkcs12) => pkcs12,
@@ -75,21 +106,53 @@ pub fn load_tls_acceptor(pkcs12_opt: Option<PathBuf>) -> ServerResult<Option<Tls
     Ok(Some(tls))
 }
 
+fn load_client_auth_acceptor(
+    pkcs12: PathBuf,
+    client_ca: &Path,
+) -> ServerResult<SslAcceptorBuilder> {
+    let pkcs12_data = fs::read(pkcs12).map_err(|e| Error::Pkcs12LoadFailed(e))?;
+    let identity = Pkcs12::from_der(&pkcs12_data)
+        .and_then(|pkcs12| pkcs12.parse(""))
+        .map_err(|e| Error::Pkcs12InvalidForClientAuth(e))?;
+    let mut tls = SslAcceptor::mozilla_intermediate(SslMethod::tls())
+        .map_err(|e| Error::TlsSetupFailed(e))?;
+    tls.set_private_key(&identity.pkey)
+        .and_then(|()| tls.set_certificate(&identity.cert))
+        .map_err(|e| Error::Pkcs12InvalidForClientAuth(e))?;
+    for cert in identity.chain.into_iter().flatten() {
+        tls.add_extra_chain_cert(cert)
+            .map_err(|e| Error::Pkcs12InvalidForClientAuth(e))?;
+    }
+    let client_ca_names =
+        X509Name::load_client_ca_file(client_ca).map_err(|e| Error::ClientCaInvalid(e))?;
+    tls.set_ca_file(client_ca)
+        .map_err(|e| Error::ClientCaInvalid(e))?;
+    tls.set_client_ca_list(client_ca_names);
+    tls.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    Ok(tls)
+}
+
 fn start_server_curr_actix_system<F, H>(
     address: impl ToSocketAddrs,
-    tls_opt: Option<TlsAcceptor>,
+    tls_opt: Option<Tls>,
+    client_timeout: Option<Duration>,
     handler: F,
 ) -> ServerResult<Server>
 where
     F: Fn() -> H + Clone + Send + 'static,
     H: IntoHttpHandler + 'static,
 {
-    let server = server::new(handler)
+    let mut server = server::new(handler)
         .workers(1)
         .system_exit()
         .disable_signals();
+    if let Some(timeout) = client_timeout {
+        let timeout_ms = timeout.as_secs() * 1000 + u64::from(timeout.subsec_millis());
+        server = server.client_timeout(timeout_ms);
+    }
     let bound_server = match tls_opt {
-        Some(tls) => server.bind_tls(address, tls),
+        Some(Tls::Identity(tls)) => server.bind_tls(address, tls),
+        Some(Tls::ClientAuth(tls)) => server.bind_ssl(address, tls),
         None => server.bind(address),
     }
     .map_err(|err| Error::BindFailed(err))?;
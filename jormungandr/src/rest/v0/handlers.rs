@@ -1,7 +1,10 @@
 use jormungandr_lib::interfaces::*;
 
-use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError, ErrorNotFound};
-use actix_web::{Error as ActixError, HttpMessage, HttpRequest};
+use actix_web::error::{
+    Error, ErrorBadRequest, ErrorForbidden, ErrorInternalServerError, ErrorNotFound,
+    ErrorRequestTimeout, ErrorServiceUnavailable,
+};
+use actix_web::{Error as ActixError, FromRequest, HttpMessage, HttpRequest, HttpResponse};
 use actix_web::{Json, Path, Query, Responder, State};
 use chain_core::property::{Deserialize, Serialize};
 use chain_crypto::{Blake2b256, PublicKey};
@@ -12,86 +15,403 @@ use chain_impl_mockchain::message::Message;
 use chain_storage::store;
 
 use bytes::{Bytes, IntoBuf};
+use futures::future::{self, Either};
 use futures::Future;
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use tokio::timer::Timeout;
 
+use crate::blockcfg::Ledger;
+use crate::blockchain::{Blockchain, LedgerReplayError};
 use crate::intercom::TransactionMsg;
+use crate::log::Levels;
+use crate::rest::{BodyLimits, DEFAULT_MAX_REPLAYED_BLOCKS};
+use crate::settings::logging::LogLevel;
+use crate::settings::start::NodeMode;
+use crate::utils::task::ShutdownReason;
 
 pub type Context = crate::rest::Context;
 
-pub fn get_utxos(context: State<Context>) -> impl Responder {
-    let blockchain = context.blockchain.lock_read();
-    let utxos = blockchain
-        .multiverse
-        .get(&blockchain.get_tip().unwrap())
-        .unwrap()
-        .utxos();
-    let utxos = utxos.map(UTxOInfo::from).collect::<Vec<_>>();
-    Json(utxos)
+/// name of the response header holding the cursor to the next page of UTxOs
+pub const NEXT_CURSOR_HEADER: &str = "Next-Cursor";
+
+const MAX_UTXO_COUNT: usize = 1000;
+const MAX_ACCOUNT_COUNT: usize = 1000;
+
+/// the block whose ledger state is queried, the tip if not set
+#[derive(Deserialize)]
+pub struct LedgerAt {
+    /// the block ID
+    at: Option<String>,
+    /// the date, `epoch.slot`, the ledger state is the one of the last
+    /// block of the tip's branch not after this date
+    at_date: Option<String>,
+}
+
+/// the maximum number of blocks replayed to rebuild a ledger state, as
+/// configured or `DEFAULT_MAX_REPLAYED_BLOCKS`
+fn max_replayed_blocks(context: &Context) -> u64 {
+    context
+        .config
+        .rest
+        .as_ref()
+        .and_then(|rest| rest.limits.as_ref())
+        .and_then(|limits| limits.max_replayed_blocks)
+        .unwrap_or(DEFAULT_MAX_REPLAYED_BLOCKS)
+}
+
+/// the ledger is cheap to clone, this allows not holding the blockchain
+/// lock while going through the ledger or while replaying it. The date is
+/// searched without holding the lock either.
+fn ledger_at(context: &Context, at: &LedgerAt) -> Result<Ledger, Error> {
+    let blockchain = &context.blockchain;
+    let block_id = match (&at.at, &at.at_date) {
+        (None, None) => blockchain
+            .lock_read()
+            .get_tip()
+            .map_err(|e| ErrorInternalServerError(e))?,
+        (Some(block_id), None) => parse_block_hash(block_id)?,
+        (None, Some(date)) => {
+            let date: BlockDate = date.parse().map_err(|e| ErrorBadRequest(e))?;
+            let (storage, tip) = {
+                let blockchain = blockchain.lock_read();
+                let tip = blockchain
+                    .get_tip()
+                    .map_err(|e| ErrorInternalServerError(e))?;
+                (blockchain.storage.clone(), tip)
+            };
+            Blockchain::find_block_at_date(&storage, &tip, date.into())
+                .map_err(|e| ErrorInternalServerError(e))?
+                .ok_or_else(|| ErrorNotFound("no block at or before this date"))?
+        }
+        (Some(_), Some(_)) => return Err(ErrorBadRequest("use either `at` or `at_date`")),
+    };
+    let replay = blockchain
+        .lock_read()
+        .ledger_replay(&block_id, max_replayed_blocks(context))
+        .map_err(|e| match e {
+            LedgerReplayError::Storage { .. } => ErrorNotFound(e),
+            LedgerReplayError::TooDeep { .. } => ErrorBadRequest(e),
+            LedgerReplayError::Ledger { .. } => ErrorInternalServerError(e),
+        })?;
+    replay.replay().map_err(|e| ErrorInternalServerError(e))
+}
+
+#[derive(Deserialize)]
+pub struct UtxoQuery {
+    address: Option<String>,
+    transaction_id: Option<String>,
+    min_value: Option<u64>,
+    after: Option<String>,
+    limit: Option<usize>,
+}
+
+/// UTxOs are paginated in the order of their pointers
+type UtxoPointer = (jormungandr_lib::crypto::hash::Hash, u8);
+
+fn parse_utxo_pointer(cursor: &str) -> Result<UtxoPointer, Error> {
+    let mut parts = cursor.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(transaction_id), Some(index)) => Ok((
+            transaction_id.parse().map_err(|e| ErrorBadRequest(e))?,
+            index.parse().map_err(|e| ErrorBadRequest(e))?,
+        )),
+        _ => Err(ErrorBadRequest("cursor must be <transaction_id>:<index>")),
+    }
+}
+
+fn utxo_pointer(utxo: &UTxOInfo) -> UtxoPointer {
+    (*utxo.transaction_id(), utxo.index_in_transaction())
+}
+
+pub fn get_utxos(
+    context: State<Context>,
+    query: Query<UtxoQuery>,
+    at: Query<LedgerAt>,
+) -> Result<HttpResponse, Error> {
+    let address = match &query.address {
+        Some(address) => Some(Address::from_str(address).map_err(|e| ErrorBadRequest(e))?),
+        None => None,
+    };
+    let transaction_id = match &query.transaction_id {
+        Some(transaction_id) => Some(
+            jormungandr_lib::crypto::hash::Hash::from_str(transaction_id)
+                .map_err(|e| ErrorBadRequest(e))?,
+        ),
+        None => None,
+    };
+    let min_value = query.min_value.map(Value::from);
+    let after = match &query.after {
+        Some(after) => Some(parse_utxo_pointer(after)?),
+        None => None,
+    };
+    // the UTxOs are only paginated when asked for, all the matching UTxOs
+    // are returned otherwise
+    let limit = match (query.limit, &after) {
+        (None, None) => None,
+        (limit, _) => Some(limit.unwrap_or(MAX_UTXO_COUNT).min(MAX_UTXO_COUNT).max(1)),
+    };
+
+    // keep the `limit + 1` first matching UTxOs, the extra one tells
+    // whether there is a next page
+    let mut page = BTreeMap::new();
+    for utxo in ledger_at(&context, &at)?.utxos().map(UTxOInfo::from) {
+        let pointer = utxo_pointer(&utxo);
+        let skip = after.map_or(false, |after| pointer <= after)
+            || address
+                .as_ref()
+                .map_or(false, |address| utxo.address() != address)
+            || transaction_id.map_or(false, |id| utxo.transaction_id() != &id)
+            || min_value.map_or(false, |min_value| utxo.associated_fund() < &min_value);
+        if skip {
+            continue;
+        }
+        page.insert(pointer, utxo);
+        if limit.map_or(false, |limit| page.len() > limit + 1) {
+            let last = *page.keys().next_back().unwrap();
+            page.remove(&last);
+        }
+    }
+    let mut response = HttpResponse::Ok();
+    if limit.map_or(false, |limit| page.len() > limit) {
+        let last = *page.keys().next_back().unwrap();
+        page.remove(&last);
+        let (transaction_id, index) = page.keys().next_back().unwrap();
+        response.header(NEXT_CURSOR_HEADER, format!("{}:{}", transaction_id, index));
+    }
+    Ok(response.json(page.into_iter().map(|(_, utxo)| utxo).collect::<Vec<_>>()))
 }
 
 pub fn get_account_state(
     context: State<Context>,
     account_id_hex: Path<String>,
+    at: Query<LedgerAt>,
 ) -> Result<impl Responder, Error> {
     let account_id = parse_account_id(&account_id_hex)?;
-    let blockchain = context.blockchain.lock_read();
-    let state = blockchain
-        .multiverse
-        .get(&blockchain.get_tip().unwrap())
-        .unwrap()
+    let state = ledger_at(&context, &at)?
         .accounts()
         .get_state(&account_id)
         .map_err(|e| ErrorNotFound(e))?;
     Ok(Json(AccountState::from(state)))
 }
 
+/// get the state of all the accounts listed in the body, the accounts
+/// not found in the ledger are `null`
+pub fn get_accounts_state(
+    request: &HttpRequest<Context>,
+    limits: BodyLimits,
+) -> impl Future<Item = impl Responder + 'static, Error = impl Into<ActixError> + 'static> + 'static
+{
+    let context = request.state().clone();
+    let at = Query::<LedgerAt>::extract(request);
+    read_body(request, limits).map(move |body| -> Result<_, ActixError> {
+        let account_ids: Vec<String> =
+            serde_json::from_slice(&body).map_err(|e| ErrorBadRequest(e))?;
+        if account_ids.len() > MAX_ACCOUNT_COUNT {
+            return Err(ErrorBadRequest(format!(
+                "at most {} accounts can be queried at once",
+                MAX_ACCOUNT_COUNT
+            )));
+        }
+        let ledger = ledger_at(&context, &at?)?;
+        let accounts = ledger.accounts();
+        let mut states = BTreeMap::new();
+        for account_id_hex in account_ids {
+            let account_id = parse_account_id(&account_id_hex)?;
+            let state = accounts.get_state(&account_id).ok().map(AccountState::from);
+            states.insert(account_id_hex, state);
+        }
+        Ok(Json(states))
+    })
+}
+
 fn parse_account_id(id_hex: &str) -> Result<Identifier, Error> {
     PublicKey::<AccountAlg>::from_str(id_hex)
         .map(Into::into)
         .map_err(|e| ErrorBadRequest(e))
 }
 
-pub fn get_message_logs(context: State<Context>) -> impl Responder {
+pub fn get_message_logs(context: State<Context>) -> Result<impl Responder, Error> {
     let logs = context.logs.lock().unwrap();
-    let logs = logs.logs().wait().unwrap();
-    Json(logs)
+    let logs = logs
+        .logs()
+        .wait()
+        .map_err(|()| ErrorInternalServerError("cannot access the fragment logs"))?;
+    Ok(Json(logs))
 }
 
 pub fn post_message(
     request: &HttpRequest<Context>,
+    limits: BodyLimits,
 ) -> impl Future<Item = impl Responder + 'static, Error = impl Into<ActixError> + 'static> + 'static
 {
+    // the body of a rejected fragment is not even read
+    if request.state().config.mode == NodeMode::Mirror {
+        return Either::A(future::err(ErrorForbidden(
+            "a mirror node does not accept fragments",
+        )));
+    }
     let sender = request.state().transaction_task.clone();
-    request.body().map(move |message| -> Result<_, ActixError> {
-        let msg = Message::deserialize(message.into_buf()).map_err(|e| {
-            println!("{}", e);
-            ErrorBadRequest(e)
-        })?;
+    let body = read_body(request, limits).map(move |message| -> Result<_, ActixError> {
+        let msg = Message::deserialize(message.into_buf()).map_err(|e| ErrorBadRequest(e))?;
         let msg = TransactionMsg::SendTransaction(FragmentOrigin::Rest, vec![msg]);
-        sender.lock().unwrap().try_send(msg).unwrap();
+        sender
+            .lock()
+            .unwrap()
+            .try_send(msg)
+            .map_err(|_| ErrorServiceUnavailable("cannot queue the fragment for processing"))?;
         Ok("")
+    });
+    Either::B(body)
+}
+
+fn read_body(
+    request: &HttpRequest<Context>,
+    limits: BodyLimits,
+) -> impl Future<Item = Bytes, Error = ActixError> {
+    let body = request.body().limit(limits.max_size);
+    Timeout::new(body, limits.timeout).map_err(|e| {
+        if e.is_elapsed() {
+            ErrorRequestTimeout("timeout while receiving the request body")
+        } else if let Some(e) = e.into_inner() {
+            ActixError::from(e)
+        } else {
+            ErrorInternalServerError("timer failure")
+        }
     })
 }
 
-pub fn get_tip(settings: State<Context>) -> impl Responder {
+pub fn get_tip(settings: State<Context>) -> Result<impl Responder, Error> {
     settings
         .blockchain
         .lock_read()
         .get_tip()
-        .unwrap()
-        .to_string()
+        .map(|tip| tip.to_string())
+        .map_err(|e| ErrorInternalServerError(e))
 }
 
 pub fn get_stats_counter(context: State<Context>) -> impl Responder {
     let stats = &context.stats_counter;
+    let unconnected_block_cnt = context.blockchain.lock_read().unconnected_blocks.len();
     Json(json!({
         "txRecvCnt": stats.get_tx_recv_cnt(),
         "blockRecvCnt": stats.get_block_recv_cnt(),
+        "unconnectedBlockCnt": unconnected_block_cnt,
+        "reorgCnt": stats.get_reorg_cnt(),
         "uptime": stats.get_uptime_sec(),
     }))
 }
 
+/// the stake distribution the leaders of an epoch are elected with
+#[derive(Serialize)]
+pub struct EpochStakeDistribution {
+    epoch: u32,
+    /// the epoch at the end of which the stake distribution was taken,
+    /// not set for the distribution of the block0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_epoch: Option<u32>,
+    unassigned: u64,
+    dangling: u64,
+    pools: BTreeMap<String, u64>,
+}
+
+pub fn get_stake_distributions(context: State<Context>) -> impl Responder {
+    let snapshots: Vec<_> = {
+        let blockchain = context.blockchain.lock_read();
+        let leaderships = &blockchain.leaderships;
+        leaderships
+            .epochs()
+            .map(|epoch| {
+                let (snapshot_epoch, ledger) = leaderships.stake_snapshot(epoch);
+                (epoch, snapshot_epoch, ledger.clone())
+            })
+            .collect()
+    };
+    let distributions: Vec<_> = snapshots
+        .into_iter()
+        .map(|(epoch, snapshot_epoch, ledger)| {
+            let distribution = ledger.get_stake_distribution();
+            EpochStakeDistribution {
+                epoch,
+                snapshot_epoch,
+                unassigned: distribution.unassigned.0,
+                dangling: distribution.dangling.0,
+                pools: distribution
+                    .to_pools
+                    .iter()
+                    .map(|(pool_id, pool)| (pool_id.to_string(), pool.total_stake.0))
+                    .collect(),
+            }
+        })
+        .collect();
+    Json(distributions)
+}
+
+pub fn get_running_config(context: State<Context>) -> impl Responder {
+    Json(context.config.as_ref().clone())
+}
+
+/// the log levels of the node, as reported by the REST API
+#[derive(Serialize)]
+pub struct LogLevelsInfo {
+    default: LogLevel,
+    tasks: BTreeMap<String, LogLevel>,
+}
+
+impl From<Levels> for LogLevelsInfo {
+    fn from(levels: Levels) -> Self {
+        LogLevelsInfo {
+            default: levels.default.into(),
+            tasks: levels
+                .tasks
+                .into_iter()
+                .map(|(task, level)| (task, level.into()))
+                .collect(),
+        }
+    }
+}
+
+/// the changes of the log levels, a task without level uses the default
+/// level again
+#[derive(Deserialize)]
+pub struct LogLevelsUpdate {
+    default: Option<LogLevel>,
+    #[serde(default)]
+    tasks: BTreeMap<String, Option<LogLevel>>,
+}
+
+pub fn get_log_levels(context: State<Context>) -> impl Responder {
+    Json(LogLevelsInfo::from(context.log_levels.get()))
+}
+
+pub fn post_log_levels(
+    request: &HttpRequest<Context>,
+    limits: BodyLimits,
+) -> impl Future<Item = impl Responder + 'static, Error = impl Into<ActixError> + 'static> + 'static
+{
+    let log_levels = request.state().log_levels.clone();
+    read_body(request, limits).map(move |body| -> Result<_, ActixError> {
+        let update: LogLevelsUpdate =
+            serde_json::from_slice(&body).map_err(|e| ErrorBadRequest(e))?;
+        if let Some(default) = update.default {
+            log_levels.set_default(default.into());
+        }
+        for (task, level) in update.tasks {
+            log_levels.set_task(task, level.map(Into::into));
+        }
+        Ok(Json(LogLevelsInfo::from(log_levels.get())))
+    })
+}
+
+pub fn get_services_status(context: State<Context>) -> impl Responder {
+    Json(context.services.get())
+}
+
+pub fn post_shutdown(context: State<Context>) -> impl Responder {
+    context.shutdown.request(ShutdownReason::AdminRequest);
+    HttpResponse::Accepted().finish()
+}
+
 pub fn get_block_id(
     context: State<Context>,
     block_id_hex: Path<String>,
@@ -101,7 +421,6 @@ pub fn get_block_id(
     let block = blockchain
         .storage
         .read()
-        .unwrap()
         .get_block(&block_id)
         .map_err(|e| ErrorBadRequest(e))?
         .0
@@ -126,8 +445,11 @@ pub fn get_block_next_id(
     // Assuming that during update whole blockchain is write-locked
     // FIXME: don't hog the blockchain lock.
     let blockchain = context.blockchain.lock_read();
-    let storage = blockchain.storage.read().unwrap();
-    store::iterate_range(&*storage, &block_id, &blockchain.get_tip().unwrap())
+    let tip = blockchain
+        .get_tip()
+        .map_err(|e| ErrorInternalServerError(e))?;
+    let storage = blockchain.storage.read();
+    store::iterate_range(&*storage, &block_id, &tip)
         .map_err(|e| ErrorBadRequest(e))?
         .take(query_params.get_count())
         .try_fold(Bytes::new(), |mut bytes, res| {
@@ -1,24 +1,85 @@
 mod handlers;
 
-use actix_web::App;
+use crate::rest::{Authentication, Limits, RequireScope, Scope};
+use actix_web::{http::Method, App};
+use std::sync::Arc;
 
-pub fn app(context: handlers::Context) -> App<handlers::Context> {
-    App::with_state(context)
-        .prefix("/api/v0")
-        .resource("/account/{account_id}", |r| {
-            r.get().with(handlers::get_account_state)
-        })
-        .resource("/block/{block_id}", |r| {
-            r.get().with(handlers::get_block_id)
-        })
-        .resource("/block/{block_id}/next_id", |r| {
-            r.get().with(handlers::get_block_next_id)
-        })
-        .resource("/fragment/logs", |r| {
-            r.get().with(handlers::get_message_logs)
-        })
-        .resource("/message", |r| r.post().a(handlers::post_message))
-        .resource("/node/stats", |r| r.get().with(handlers::get_stats_counter))
-        .resource("/tip", |r| r.get().with(handlers::get_tip))
-        .resource("/utxo", |r| r.get().with(handlers::get_utxos))
+pub fn app(
+    context: handlers::Context,
+    authentication: Arc<Authentication>,
+    limits: Limits,
+) -> App<handlers::Context> {
+    let require = |scope| RequireScope::new(authentication.clone(), scope);
+    let mut app = App::with_state(context).prefix("/api/v0");
+    let Limits {
+        rate_limit,
+        ledger_queries,
+        body: body_limits,
+    } = limits;
+    if let Some(rate_limit) = rate_limit {
+        app = app.middleware(rate_limit);
+    }
+    app.resource("/account/{account_id}", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_account_state)
+    })
+    .resource("/accounts", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.middleware(ledger_queries.clone());
+        r.post()
+            .a(move |request| handlers::get_accounts_state(request, body_limits))
+    })
+    .resource("/block/{block_id}", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_block_id)
+    })
+    .resource("/block/{block_id}/next_id", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_block_next_id)
+    })
+    .resource("/fragment/logs", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_message_logs)
+    })
+    .resource("/message", |r| {
+        r.middleware(require(Scope::Submit));
+        r.post()
+            .a(move |request| handlers::post_message(request, body_limits))
+    })
+    .resource("/node/config", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_running_config)
+    })
+    .resource("/node/log_levels", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.middleware(require(Scope::Admin).for_method(Method::POST));
+        r.get().with(handlers::get_log_levels);
+        r.post()
+            .a(move |request| handlers::post_log_levels(request, body_limits))
+    })
+    .resource("/node/services", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_services_status)
+    })
+    .resource("/node/shutdown", |r| {
+        r.middleware(require(Scope::Admin));
+        r.post().with(handlers::post_shutdown)
+    })
+    .resource("/node/stats", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_stats_counter)
+    })
+    .resource("/stake_distribution", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_stake_distributions)
+    })
+    .resource("/tip", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.get().with(handlers::get_tip)
+    })
+    .resource("/utxo", |r| {
+        r.middleware(require(Scope::ReadOnly));
+        r.middleware(ledger_queries);
+        r.get().with(handlers::get_utxos)
+    })
 }
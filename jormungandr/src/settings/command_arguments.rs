@@ -4,7 +4,10 @@ use structopt::StructOpt;
 
 use crate::{
     blockcfg::HeaderHash,
-    settings::logging::{LogFormat, LogOutput},
+    settings::{
+        logging::{LogFormat, LogOutput},
+        start::StorageBackend,
+    },
 };
 
 #[derive(StructOpt, Debug)]
@@ -41,9 +44,10 @@ pub struct StartArguments {
     #[structopt(long = "storage", parse(from_os_str))]
     pub storage: Option<PathBuf>,
 
-    /// Set the node config (in YAML format) to use as general configuration
+    /// Set the node config (in YAML format) to use as general configuration.
+    /// Required to start the node.
     #[structopt(long = "config", parse(from_os_str))]
-    pub node_config: PathBuf,
+    pub node_config: Option<PathBuf>,
 
     /// Set the secret node config (in YAML format). Can be given
     /// multiple times.
@@ -61,6 +65,98 @@ pub struct StartArguments {
     pub block_0_hash: Option<HeaderHash>,
 }
 
+#[derive(StructOpt, Debug)]
+pub struct VerifyStorageArguments {
+    /// Path to the blockchain pool storage directory to verify
+    #[structopt(long = "storage", parse(from_os_str))]
+    pub storage: PathBuf,
+
+    /// The hash of the genesis block the blockchain of the storage is
+    /// expected to start with
+    #[structopt(long = "genesis-block-hash", parse(try_from_str))]
+    pub block_0_hash: Option<HeaderHash>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct ExportStorageArguments {
+    /// Path to the blockchain pool storage directory to export the blocks of
+    #[structopt(long = "storage", parse(from_os_str))]
+    pub storage: PathBuf,
+
+    /// Path of the archive to create
+    #[structopt(long = "output", parse(from_os_str))]
+    pub output: PathBuf,
+
+    /// The first block to export, the genesis block if not set
+    #[structopt(long = "from", parse(try_from_str))]
+    pub from: Option<HeaderHash>,
+
+    /// The last block to export, the tip of the storage if not set
+    #[structopt(long = "to", parse(try_from_str))]
+    pub to: Option<HeaderHash>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct ImportStorageArguments {
+    /// Path to the blockchain pool storage directory to import the blocks
+    /// into, created if it does not exist
+    #[structopt(long = "storage", parse(from_os_str))]
+    pub storage: PathBuf,
+
+    /// Path of the archive to import
+    #[structopt(long = "input", parse(from_os_str))]
+    pub input: PathBuf,
+
+    /// The hash of the genesis block the archived blocks are expected
+    /// to descend from
+    #[structopt(long = "genesis-block-hash", parse(try_from_str))]
+    pub block_0_hash: Option<HeaderHash>,
+
+    /// The database of the storage if it is created, "sqlite" or "sled".
+    /// Defaults to "sqlite".
+    #[structopt(long = "storage-backend", parse(try_from_str))]
+    pub backend: Option<StorageBackend>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct MigrateStorageArguments {
+    /// Path to the blockchain pool storage directory to migrate
+    #[structopt(long = "storage", parse(from_os_str))]
+    pub storage: PathBuf,
+
+    /// The database to migrate the storage to, "sqlite" or "sled"
+    #[structopt(long = "to", parse(try_from_str))]
+    pub to: StorageBackend,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum Command {
+    /// Verify the blockchain held in the storage without starting the node.
+    /// The blocks are replayed from the genesis block to the tip, checking
+    /// their leader and applying them to the ledger. The first inconsistent
+    /// block is reported along with statistics for each epoch.
+    #[structopt(name = "verify-storage")]
+    VerifyStorage(VerifyStorageArguments),
+
+    /// Export the blocks of the storage, the whole blockchain or a range
+    /// of the tip's branch, to an archive file.
+    #[structopt(name = "export-storage")]
+    ExportStorage(ExportStorageArguments),
+
+    /// Import the blocks of an archive file into a new storage, or on top
+    /// of the tip of an existing one. The resulting blockchain is verified
+    /// and the import is reverted if it is inconsistent.
+    #[structopt(name = "import-storage")]
+    ImportStorage(ImportStorageArguments),
+
+    /// Copy the blockchain of the storage into a database of another
+    /// backend, to be used with the `storage_backend` setting. Only the
+    /// blocks of the tip's branch are copied, the previous database is
+    /// kept with the `.migrated` extension.
+    #[structopt(name = "migrate-storage")]
+    MigrateStorage(MigrateStorageArguments),
+}
+
 #[derive(StructOpt, Debug)]
 #[structopt(
     name = "jormungandr",
@@ -84,8 +180,21 @@ pub struct CommandLine {
     #[structopt(long = "log-output", parse(try_from_str))]
     pub log_output: Option<LogOutput>,
 
+    /// Check the node configuration and the command line arguments, report
+    /// all the errors found and exit without starting the node.
+    #[structopt(long = "validate-config")]
+    pub validate_config: bool,
+
+    /// Print the effective settings of the node, as YAML and with the
+    /// secrets redacted, and exit without starting the node.
+    #[structopt(long = "print-config")]
+    pub print_config: bool,
+
     #[structopt(flatten)]
     pub start_arguments: StartArguments,
+
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
 }
 
 impl CommandLine {
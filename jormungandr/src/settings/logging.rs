@@ -1,4 +1,4 @@
-use crate::log::AsyncableDrain;
+use crate::log::{AsyncableDrain, LogLevels, TaskLevelFilter};
 use slog::{Drain, Logger};
 use slog_async::Async;
 #[cfg(feature = "gelf")]
@@ -8,6 +8,7 @@ use slog_journald::JournaldDrain;
 #[cfg(unix)]
 use slog_syslog::Facility;
 use slog_term::TermDecorator;
+use std::collections::BTreeMap;
 use std::error;
 use std::fmt::{self, Display};
 use std::io;
@@ -16,10 +17,51 @@ use std::str::FromStr;
 #[derive(Debug)]
 pub struct LogSettings {
     pub verbosity: slog::Level,
+    /// the levels overriding the verbosity for some tasks
+    pub tasks: BTreeMap<String, slog::Level>,
     pub format: LogFormat,
     pub output: LogOutput,
 }
 
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// Level of the logs, a record is emitted if its level is at least as
+/// important as this one.
+pub enum LogLevel {
+    Critical,
+    Error,
+    Warning,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for slog::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Critical => slog::Level::Critical,
+            LogLevel::Error => slog::Level::Error,
+            LogLevel::Warning => slog::Level::Warning,
+            LogLevel::Info => slog::Level::Info,
+            LogLevel::Debug => slog::Level::Debug,
+            LogLevel::Trace => slog::Level::Trace,
+        }
+    }
+}
+
+impl From<slog::Level> for LogLevel {
+    fn from(level: slog::Level) -> Self {
+        match level {
+            slog::Level::Critical => LogLevel::Critical,
+            slog::Level::Error => LogLevel::Error,
+            slog::Level::Warning => LogLevel::Warning,
+            slog::Level::Info => LogLevel::Info,
+            slog::Level::Debug => LogLevel::Debug,
+            slog::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 /// Format of the logger.
@@ -84,10 +126,13 @@ impl FromStr for LogOutput {
 }
 
 impl LogSettings {
-    pub fn to_logger(&self) -> Result<Logger, Error> {
+    /// build the logger, the returned levels change the levels of the
+    /// logger while it is running
+    pub fn to_logger(&self) -> Result<(Logger, LogLevels), Error> {
+        let levels = LogLevels::new(self.verbosity, self.tasks.clone());
         let drain = self.output.to_logger(&self.format)?.fuse();
-        let drain = slog::LevelFilter::new(drain, self.verbosity).fuse();
-        Ok(slog::Logger::root(drain, o!()))
+        let drain = TaskLevelFilter::new(drain, levels.clone()).fuse();
+        Ok((slog::Logger::root(drain, o!()), levels))
     }
 }
 
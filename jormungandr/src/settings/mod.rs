@@ -2,7 +2,10 @@ mod command_arguments;
 pub mod logging;
 pub mod start;
 
-pub use self::command_arguments::CommandLine;
+pub use self::command_arguments::{
+    Command, CommandLine, ExportStorageArguments, ImportStorageArguments, MigrateStorageArguments,
+    VerifyStorageArguments,
+};
 pub use self::start::Error;
 use crate::blockcfg::HeaderHash;
 use std::path::PathBuf;
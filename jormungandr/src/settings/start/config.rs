@@ -1,35 +1,167 @@
 use crate::{
     network::p2p::topology::{NodeId, NEW_BLOCKS_TOPIC, NEW_MESSAGES_TOPIC},
-    settings::logging::{LogFormat, LogOutput},
+    rest::Scope,
+    settings::logging::{LogFormat, LogLevel, LogOutput},
 };
 
+use jormungandr_lib::time::Duration;
 use poldercast;
 use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 
-use std::{collections::BTreeMap, fmt, net::SocketAddr, path::PathBuf};
+use std::{collections::BTreeMap, fmt, net::SocketAddr, path::PathBuf, str::FromStr};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
+    /// the services run by the node, `full` if not set
+    pub mode: Option<NodeMode>,
     pub secret_files: Option<Vec<PathBuf>>,
     pub legacy_peers: Option<Vec<SocketAddr>>,
     pub grpc_peers: Option<Vec<SocketAddr>>,
     pub storage: Option<PathBuf>,
+    /// the database of the storage directory, SQLite if not set
+    pub storage_backend: Option<StorageBackend>,
+    /// keep only the last epochs of blocks in the storage
+    pub pruning: Option<Pruning>,
     pub logger: Option<ConfigLogSettings>,
     pub rest: Option<Rest>,
     pub peer_2_peer: P2pConfig,
+    pub mempool: Option<ConfigMempoolSettings>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ConfigLogSettings {
     pub verbosity: Option<u8>,
+    /// the levels overriding the verbosity for some tasks
+    pub tasks: Option<BTreeMap<String, LogLevel>>,
     pub format: Option<LogFormat>,
     pub output: Option<LogOutput>,
 }
 
+/// the role of the node in the network
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeMode {
+    /// the node accepts fragments and creates blocks if it has leader
+    /// secrets
+    Full,
+    /// the node only synchronizes and serves the blocks, for the explorer
+    /// and API nodes
+    Mirror,
+}
+
+/// the database holding the blocks of a storage directory
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Sqlite,
+    /// the embedded log-structured key-value store sled
+    Sled,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Pruning {
+    /// number of epochs whose blocks are kept whole, the epoch of the tip
+    /// included. Only the headers of the older blocks are kept.
+    pub keep_epochs: u32,
+}
+
+impl Default for NodeMode {
+    fn default() -> Self {
+        NodeMode::Full
+    }
+}
+
+impl Default for StorageBackend {
+    fn default() -> Self {
+        StorageBackend::Sqlite
+    }
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            "sled" => Ok(StorageBackend::Sled),
+            other => Err(format!("unknown storage backend '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+            StorageBackend::Sled => write!(f, "sled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigMempoolSettings {
+    /// time a fragment stays in the MemPool if it is not included in a block
+    pub fragment_ttl: Option<Duration>,
+    /// time the log of a fragment is kept after its last update
+    pub log_ttl: Option<Duration>,
+    /// interval between two removals of the expired fragments and logs
+    pub garbage_collection_interval: Option<Duration>,
+    /// interval between two savings of the MemPool in the storage
+    pub persistence_interval: Option<Duration>,
+    /// maximum number of fragments selected from the MemPool for a block
+    pub max_fragments_per_block: Option<usize>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Rest {
     pub listen: SocketAddr,
     pub pkcs12: Option<PathBuf>,
+    /// when set, every request needs to be authenticated
+    pub authentication: Option<RestAuthentication>,
+    /// limits protecting the node from abusive clients
+    pub limits: Option<RestLimits>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestAuthentication {
+    /// the static bearer tokens accepted by the REST API
+    #[serde(default)]
+    pub tokens: Vec<RestToken>,
+    /// require the clients to present a certificate signed by a trusted CA
+    pub client_certificates: Option<RestClientCertificates>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestLimits {
+    /// maximum number of requests per minute from a single IP address
+    pub requests_per_minute_per_ip: Option<u32>,
+    /// maximum number of requests per minute with a single bearer token
+    pub requests_per_minute_per_token: Option<u32>,
+    /// maximum size, in bytes, of a request body
+    pub max_body_size: Option<usize>,
+    /// maximum time for a client to send a request
+    pub request_timeout: Option<Duration>,
+    /// maximum number of UTxO set and accounts queries processed at the same
+    /// time
+    pub max_concurrent_utxo_queries: Option<usize>,
+    /// maximum number of blocks replayed to rebuild a past ledger state
+    pub max_replayed_blocks: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestToken {
+    pub token: String,
+    pub scopes: Vec<Scope>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RestClientCertificates {
+    /// PEM file with the certificates of the CAs trusted to sign the
+    /// client certificates
+    pub trusted_ca: PathBuf,
+    /// the scopes granted to every client with a valid certificate
+    #[serde(default)]
+    pub scopes: Vec<Scope>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
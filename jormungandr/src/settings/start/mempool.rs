@@ -0,0 +1,90 @@
+use crate::settings::start::config::ConfigMempoolSettings;
+use jormungandr_lib::time::Duration;
+
+/// default time a fragment stays in the MemPool: 1h
+const DEFAULT_FRAGMENT_TTL: u64 = 3600;
+/// default time a log is kept after its last update: 2h
+const DEFAULT_LOG_TTL: u64 = 3600 * 2;
+/// default interval between two garbage collections: 15min
+const DEFAULT_GARBAGE_COLLECTION_INTERVAL: u64 = 3600 / 4;
+/// default interval between two savings of the MemPool: 1min
+const DEFAULT_PERSISTENCE_INTERVAL: u64 = 60;
+const DEFAULT_MAX_FRAGMENTS_PER_BLOCK: usize = 250;
+
+custom_error! {pub Error
+    ZeroDuration { field: &'static str } = "`{field}' cannot be zero",
+    ZeroFragmentsPerBlock = "`max_fragments_per_block' cannot be zero",
+    LogTtlTooShort = "`log_ttl' cannot be shorter than `fragment_ttl', the log of a pending fragment would be lost",
+}
+
+/// the MemPool settings, with the default values applied
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MempoolSettings {
+    pub fragment_ttl: Duration,
+    pub log_ttl: Duration,
+    pub garbage_collection_interval: Duration,
+    pub persistence_interval: Duration,
+    pub max_fragments_per_block: usize,
+}
+
+impl Default for MempoolSettings {
+    fn default() -> Self {
+        MempoolSettings {
+            fragment_ttl: Duration::new(DEFAULT_FRAGMENT_TTL, 0),
+            log_ttl: Duration::new(DEFAULT_LOG_TTL, 0),
+            garbage_collection_interval: Duration::new(DEFAULT_GARBAGE_COLLECTION_INTERVAL, 0),
+            persistence_interval: Duration::new(DEFAULT_PERSISTENCE_INTERVAL, 0),
+            max_fragments_per_block: DEFAULT_MAX_FRAGMENTS_PER_BLOCK,
+        }
+    }
+}
+
+impl MempoolSettings {
+    /// apply the values of the `mempool` section of the node configuration
+    /// over the defaults, and check the result is usable
+    pub fn new(config: Option<&ConfigMempoolSettings>) -> Result<Self, Error> {
+        let default = MempoolSettings::default();
+        let settings = match config {
+            None => default,
+            Some(config) => MempoolSettings {
+                fragment_ttl: config.fragment_ttl.unwrap_or(default.fragment_ttl),
+                log_ttl: config.log_ttl.unwrap_or(default.log_ttl),
+                garbage_collection_interval: config
+                    .garbage_collection_interval
+                    .unwrap_or(default.garbage_collection_interval),
+                persistence_interval: config
+                    .persistence_interval
+                    .unwrap_or(default.persistence_interval),
+                max_fragments_per_block: config
+                    .max_fragments_per_block
+                    .unwrap_or(default.max_fragments_per_block),
+            },
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let durations = [
+            ("fragment_ttl", &self.fragment_ttl),
+            ("log_ttl", &self.log_ttl),
+            (
+                "garbage_collection_interval",
+                &self.garbage_collection_interval,
+            ),
+            ("persistence_interval", &self.persistence_interval),
+        ];
+        for (field, duration) in durations.iter() {
+            if duration.as_ref() == &std::time::Duration::from_secs(0) {
+                return Err(Error::ZeroDuration { field: *field });
+            }
+        }
+        if self.max_fragments_per_block == 0 {
+            return Err(Error::ZeroFragmentsPerBlock);
+        }
+        if self.log_ttl.as_ref() < self.fragment_ttl.as_ref() {
+            return Err(Error::LogTtlTooShort);
+        }
+        Ok(())
+    }
+}
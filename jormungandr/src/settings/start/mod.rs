@@ -1,31 +1,113 @@
 mod config;
+mod mempool;
 pub mod network;
 
-pub use self::config::Rest;
-use self::config::{Config, ConfigLogSettings};
+pub use self::config::{NodeMode, Pruning, Rest, RestAuthentication, RestLimits, StorageBackend};
+use self::config::{Config, ConfigLogSettings, P2pConfig};
+pub use self::mempool::{Error as MempoolError, MempoolSettings};
 use self::network::Protocol;
+use crate::log::LogLevels;
 use crate::rest::Error as RestError;
-use crate::settings::logging::{self, LogFormat, LogOutput, LogSettings};
+use crate::settings::logging::{self, LogFormat, LogLevel, LogOutput, LogSettings};
 use crate::settings::{command_arguments::*, Block0Info};
 use slog::Logger;
 
-use std::{collections::BTreeMap, fs::File, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+/// placeholder of the secrets in the configuration reported by the node
+const REDACTED: &str = "<redacted>";
 
 custom_error! {pub Error
    ConfigIo { source: std::io::Error } = "Cannot read the node configuration file: {source}",
    Config { source: serde_yaml::Error } = "Error while parsing the node configuration file: {source}",
    Rest { source: RestError } = "The Rest configuration is invalid: {source}",
+   Mempool { source: MempoolError } = "The mempool configuration is invalid: {source}",
+   ExpectedNodeConfig = "Cannot start the node without a node configuration, use `--config'",
    ExpectedBlock0Info = "Cannot start the node without the information to retrieve the genesis block",
    TooMuchBlock0Info = "Use only `--genesis-block-hash' or `--genesis-block'",
+   File { source: std::io::Error, description: &'static str, path: String } = "Cannot read the {description} `{path}': {source}",
+   StorageNotDirectory { path: String } = "The storage `{path}' is not a directory",
+   PruningWithoutStorage = "The blocks cannot be pruned without a storage directory, use `--storage'",
+   PruningWithBackend = "`storage_backend' cannot be set along with the pruning, the pruned blocks have a storage of their own",
+   NoEpochKept = "`keep_epochs' of the pruning cannot be zero, the blocks of the tip's epoch are needed",
+   ZeroRestLimit { limit: &'static str } = "The REST limit `{limit}' cannot be zero, remove it to disable the limit",
+   AddressConflict { address: SocketAddr, first: &'static str, second: &'static str } = "The {first} and the {second} both use {address}",
 }
 
 /// Overall Settings for node
 pub struct Settings {
+    pub mode: NodeMode,
     pub network: network::Configuration,
     pub storage: Option<PathBuf>,
+    pub storage_backend: StorageBackend,
+    pub pruning: Option<Pruning>,
     pub block_0: Block0Info,
     pub leadership: Vec<PathBuf>,
     pub rest: Option<Rest>,
+    pub mempool: MempoolSettings,
+    pub logger: ConfigLogSettings,
+}
+
+/// the effective configuration of the running node, as reported by the
+/// REST API, the secrets being redacted
+#[derive(Clone, Serialize)]
+pub struct RunningConfig {
+    pub mode: NodeMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis_block: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genesis_block_hash: Option<String>,
+    pub storage: Option<PathBuf>,
+    pub storage_backend: StorageBackend,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pruning: Option<Pruning>,
+    pub logger: ConfigLogSettings,
+    pub secret_files: Vec<PathBuf>,
+    pub rest: Option<Rest>,
+    pub peer_2_peer: P2pConfig,
+    pub mempool: MempoolSettings,
+}
+
+impl Settings {
+    pub fn running_config(&self) -> RunningConfig {
+        let rest = self.rest.clone().map(|mut rest| {
+            if let Some(authentication) = rest.authentication.as_mut() {
+                for token in authentication.tokens.iter_mut() {
+                    token.token = REDACTED.to_owned();
+                }
+            }
+            rest
+        });
+        let (genesis_block, genesis_block_hash) = match &self.block_0 {
+            Block0Info::Path(path) => (Some(path.clone()), None),
+            Block0Info::Hash(hash) => (None, Some(hash.to_string())),
+        };
+        let network = &self.network;
+        RunningConfig {
+            mode: self.mode,
+            genesis_block,
+            genesis_block_hash,
+            storage: self.storage.clone(),
+            storage_backend: self.storage_backend,
+            pruning: self.pruning,
+            logger: self.logger.clone(),
+            secret_files: self.leadership.clone(),
+            rest,
+            peer_2_peer: P2pConfig {
+                public_id: network.public_id.clone(),
+                public_address: network.public_address.clone(),
+                listen: network.listen.clone(),
+                trusted_peers: Some(network.trusted_peers.clone()),
+                topics_of_interests: Some(network.subscriptions.clone()),
+            },
+            mempool: self.mempool.clone(),
+        }
+    }
 }
 
 pub struct RawSettings {
@@ -35,7 +117,12 @@ pub struct RawSettings {
 
 impl RawSettings {
     pub fn load(command_line: CommandLine) -> Result<Self, Error> {
-        let config_file = File::open(&command_line.start_arguments.node_config)?;
+        let node_config = command_line
+            .start_arguments
+            .node_config
+            .as_ref()
+            .ok_or(Error::ExpectedNodeConfig)?;
+        let config_file = File::open(node_config)?;
         let config = serde_yaml::from_reader(config_file)?;
         Ok(Self {
             command_line,
@@ -43,9 +130,14 @@ impl RawSettings {
         })
     }
 
-    pub fn to_logger(&self) -> Result<Logger, logging::Error> {
+    pub fn to_logger(&self) -> Result<(Logger, LogLevels), logging::Error> {
         LogSettings {
             verbosity: self.logger_verbosity(),
+            tasks: self
+                .logger_tasks()
+                .into_iter()
+                .map(|(task, level)| (task, level.into()))
+                .collect(),
             format: self.logger_format(),
             output: self.logger_output(),
         }
@@ -53,18 +145,105 @@ impl RawSettings {
     }
 
     fn logger_verbosity(&self) -> slog::Level {
+        match self.logger_verbosity_level() {
+            0 => slog::Level::Info,
+            1 => slog::Level::Debug,
+            _ => slog::Level::Trace,
+        }
+    }
+
+    fn logger_verbosity_level(&self) -> u8 {
         let cmd_level = match self.command_line.verbose {
             0 => None,
             level => Some(level),
         };
         let config_logger = self.config.logger.as_ref();
         let config_level = config_logger.and_then(|logger| logger.verbosity.clone());
-        let level = cmd_level.or(config_level).unwrap_or(0);
-        match level {
-            0 => slog::Level::Info,
-            1 => slog::Level::Debug,
-            _ => slog::Level::Trace,
+        cmd_level.or(config_level).unwrap_or(0)
+    }
+
+    /// check the settings without starting the node, all the errors found
+    /// are returned instead of only the first one
+    pub fn validate(&self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        let command_arguments = &self.command_line.start_arguments;
+        let config = &self.config;
+
+        if let Err(error) = block_0_info(command_arguments) {
+            errors.push(error);
+        }
+        if let Err(source) = MempoolSettings::new(config.mempool.as_ref()) {
+            errors.push(Error::Mempool { source });
+        }
+
+        let mut files: Vec<(&'static str, &Path)> = Vec::new();
+        let secret_files = config.secret_files.iter().flatten();
+        for secret in command_arguments.secret.iter().chain(secret_files) {
+            files.push(("secret file", secret.as_path()));
+        }
+        if let Some(path) = &command_arguments.block_0_path {
+            files.push(("genesis block", path.as_path()));
+        }
+        if let Some(rest) = &config.rest {
+            if let Some(pkcs12) = &rest.pkcs12 {
+                files.push(("REST PKCS12 archive", pkcs12.as_path()));
+            }
+            let client_certificates = rest
+                .authentication
+                .as_ref()
+                .and_then(|authentication| authentication.client_certificates.as_ref());
+            if let Some(client_certificates) = client_certificates {
+                files.push(("REST trusted CA", client_certificates.trusted_ca.as_path()));
+            }
+        }
+        for (description, path) in files {
+            if let Err(source) = File::open(path) {
+                errors.push(Error::File {
+                    source,
+                    description,
+                    path: path.display().to_string(),
+                });
+            }
+        }
+
+        if let Some(storage) = storage(command_arguments, config) {
+            if storage.exists() && !storage.is_dir() {
+                errors.push(Error::StorageNotDirectory {
+                    path: storage.display().to_string(),
+                });
+            }
         }
+        if let Err(error) = pruning(command_arguments, config) {
+            errors.push(error);
+        }
+        if let Some(limits) = config.rest.as_ref().and_then(|rest| rest.limits.as_ref()) {
+            errors.extend(rest_limits(limits).err());
+        }
+
+        let p2p = &config.peer_2_peer;
+        let p2p_listen = p2p.listen.or_else(|| {
+            p2p.public_address
+                .as_ref()
+                .and_then(|address| address.to_socketaddr())
+        });
+        if let (Some(rest), Some(p2p_listen)) = (&config.rest, p2p_listen) {
+            if addresses_overlap(&rest.listen, &p2p_listen) {
+                errors.push(Error::AddressConflict {
+                    address: rest.listen,
+                    first: "REST server",
+                    second: "P2P listener",
+                });
+            }
+        }
+
+        errors
+    }
+
+    fn logger_tasks(&self) -> BTreeMap<String, LogLevel> {
+        let config_logger = self.config.logger.as_ref();
+        config_logger
+            .and_then(|logger| logger.tasks.clone())
+            .unwrap_or_default()
     }
 
     fn logger_format(&self) -> LogFormat {
@@ -87,6 +266,12 @@ impl RawSettings {
     ///
     /// This function will print&exit if anything is not as it should be.
     pub fn try_into_settings(self, logger: &Logger) -> Result<Settings, Error> {
+        let logger_settings = ConfigLogSettings {
+            verbosity: Some(self.logger_verbosity_level()),
+            tasks: Some(self.logger_tasks()),
+            format: Some(self.logger_format()),
+            output: Some(self.logger_output()),
+        };
         let RawSettings {
             command_line,
             config,
@@ -94,44 +279,118 @@ impl RawSettings {
         let command_arguments = &command_line.start_arguments;
         let network = generate_network(&command_arguments, &config);
 
-        let storage = match (command_arguments.storage.as_ref(), config.storage) {
-            (Some(path), _) => Some(path.clone()),
-            (None, Some(path)) => Some(path.clone()),
-            (None, None) => None,
-        };
+        let storage = storage(command_arguments, &config);
+        let pruning = pruning(command_arguments, &config)?;
+        if let Some(limits) = config.rest.as_ref().and_then(|rest| rest.limits.as_ref()) {
+            rest_limits(limits)?;
+        }
 
         let mut leadership = command_arguments.secret.clone();
         if let Some(secret_files) = config.secret_files {
             leadership.extend(secret_files);
         }
 
-        if leadership.is_empty() {
-            warn!(
-                logger,
-                "Node started without path to the stored secret keys"
-            );
-        };
+        let mode = config.mode.unwrap_or_default();
+        match mode {
+            NodeMode::Mirror if !leadership.is_empty() => {
+                warn!(
+                    logger,
+                    "the secret files are ignored, a mirror node does not create blocks"
+                );
+            }
+            NodeMode::Full if leadership.is_empty() => {
+                warn!(
+                    logger,
+                    "Node started without path to the stored secret keys"
+                );
+            }
+            _ => {}
+        }
 
-        let block0_info = match (
-            &command_arguments.block_0_path,
-            &command_arguments.block_0_hash,
-        ) {
-            (None, None) => return Err(Error::ExpectedBlock0Info),
-            (Some(_path), Some(_hash)) => return Err(Error::TooMuchBlock0Info),
-            (Some(path), None) => Block0Info::Path(path.clone()),
-            (None, Some(hash)) => Block0Info::Hash(hash.clone()),
-        };
+        let block0_info = block_0_info(command_arguments)?;
+
+        let mempool = MempoolSettings::new(config.mempool.as_ref())?;
 
         Ok(Settings {
+            mode,
             storage: storage,
+            storage_backend: config.storage_backend.unwrap_or_default(),
+            pruning,
             block_0: block0_info,
             network: network,
             leadership,
             rest: config.rest,
+            mempool,
+            logger: logger_settings,
         })
     }
 }
 
+fn storage(command_arguments: &StartArguments, config: &Config) -> Option<PathBuf> {
+    command_arguments
+        .storage
+        .clone()
+        .or_else(|| config.storage.clone())
+}
+
+fn pruning(command_arguments: &StartArguments, config: &Config) -> Result<Option<Pruning>, Error> {
+    match config.pruning {
+        Some(_) if storage(command_arguments, config).is_none() => {
+            Err(Error::PruningWithoutStorage)
+        }
+        Some(_) if config.storage_backend.is_some() => Err(Error::PruningWithBackend),
+        Some(Pruning { keep_epochs: 0 }) => Err(Error::NoEpochKept),
+        pruning => Ok(pruning),
+    }
+}
+
+/// a zero limit would reject every request, the limits which are not
+/// wanted are left unset instead
+fn rest_limits(limits: &RestLimits) -> Result<(), Error> {
+    let zero_limit = if limits.requests_per_minute_per_ip == Some(0) {
+        Some("requests_per_minute_per_ip")
+    } else if limits.requests_per_minute_per_token == Some(0) {
+        Some("requests_per_minute_per_token")
+    } else if limits.max_body_size == Some(0) {
+        Some("max_body_size")
+    } else if limits.max_concurrent_utxo_queries == Some(0) {
+        Some("max_concurrent_utxo_queries")
+    } else if limits.max_replayed_blocks == Some(0) {
+        Some("max_replayed_blocks")
+    } else if limits.request_timeout.map_or(false, |timeout| {
+        *timeout.as_ref() == std::time::Duration::from_secs(0)
+    }) {
+        Some("request_timeout")
+    } else {
+        None
+    };
+    match zero_limit {
+        Some(limit) => Err(Error::ZeroRestLimit { limit }),
+        None => Ok(()),
+    }
+}
+
+fn block_0_info(command_arguments: &StartArguments) -> Result<Block0Info, Error> {
+    match (
+        &command_arguments.block_0_path,
+        &command_arguments.block_0_hash,
+    ) {
+        (None, None) => Err(Error::ExpectedBlock0Info),
+        (Some(_path), Some(_hash)) => Err(Error::TooMuchBlock0Info),
+        (Some(path), None) => Ok(Block0Info::Path(path.clone())),
+        (None, Some(hash)) => Ok(Block0Info::Hash(hash.clone())),
+    }
+}
+
+/// two listening addresses overlap if they use the same port on the same
+/// interface, or if one of them listens on all the interfaces
+fn addresses_overlap(first: &SocketAddr, second: &SocketAddr) -> bool {
+    first.port() == second.port()
+        && (first.ip() == second.ip()
+            || first.ip().is_unspecified()
+            || second.ip().is_unspecified())
+}
+
 fn generate_network(
     _command_arguments: &StartArguments,
     config: &Config,
@@ -148,6 +407,7 @@ fn generate_network(
             .topics_of_interests
             .clone()
             .unwrap_or(BTreeMap::new()),
+        mode: config.mode.unwrap_or_default(),
         timeout: std::time::Duration::from_secs(15),
     }
 }
@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, net::SocketAddr, str, time::Duration};
 
 use crate::{
     network::p2p::topology::NodeId,
-    settings::start::config::{Address, InterestLevel, Topic, TrustedPeer},
+    settings::start::config::{Address, InterestLevel, NodeMode, Topic, TrustedPeer},
 };
 
 /// Protocol to use for a connection.
@@ -65,6 +65,9 @@ pub struct Configuration {
     /// the topic we are interested to hear about
     pub subscriptions: BTreeMap<Topic, InterestLevel>,
 
+    /// a mirror node advertises a low interest in the fragments
+    pub mode: NodeMode,
+
     /// the default value for the timeout for inactive connection
     pub timeout: Duration,
 }
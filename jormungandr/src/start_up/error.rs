@@ -1,13 +1,18 @@
 use crate::{
-    blockcfg, blockchain, network, secure,
-    settings::{self, logging},
+    blockcfg::{self, HeaderHash},
+    blockchain, network, secure,
+    settings::{self, logging, start::StorageBackend},
+    utils::task,
 };
 use chain_storage::error::Error as StorageError;
 use std::io;
 
 custom_error! {pub ErrorKind
    SQLite = "SQLite file",
-   Block0 = "Block0"
+   Block0 = "Block0",
+   Tip = "the blockchain tip",
+   Archive = "the block archive",
+   Storage = "the block storage"
 }
 
 custom_error! {pub Error
@@ -17,9 +22,23 @@ custom_error! {pub Error
     ParseError{ source: io::Error, reason: ErrorKind} = "Parsing error on {reason}",
     StorageError { source: StorageError } = "Storage error",
     Blockchain { source: blockchain::LoadError } = "Error while loading the blockchain state",
+    BlockchainState { source: blockchain::QueryError } = "Error while reading the blockchain state",
     Block0 { source: blockcfg::Block0Error } = "Error in the genesis-block",
     FetchBlock0 { source: network::FetchBlockError } = "Error fetching the genesis block from the network",
-    NodeSecrets { source: secure::NodeSecretFromFileError} = "Error while loading the node's secrets."
+    NodeSecrets { source: secure::NodeSecretFromFileError} = "Error while loading the node's secrets.",
+    Service { source: task::Error } = "A service of the node failed",
+    StorageFlush { source: blockchain::QueryError, reason: ErrorKind } = "Error while writing {reason} to the storage on shutdown",
+    Block0Mismatch { block0_id: HeaderHash } = "The storage holds a blockchain which does not start with the genesis block {block0_id}",
+    Block0Unavailable { block0_id: HeaderHash } = "The genesis block {block0_id} is not in the storage and there is no trusted peer to fetch it from",
+    InvalidConfiguration { count: usize } = "The node configuration has {count} error(s)",
+    PrintConfiguration { source: serde_yaml::Error } = "Cannot print the node configuration",
+    StorageNotFound { path: String } = "The storage `{path}' does not exist",
+    StorageVerification { source: blockchain::VerifyError } = "Cannot verify the storage",
+    InconsistentStorage { inconsistency: blockchain::Inconsistency } = "The storage is inconsistent at the {inconsistency}",
+    Archive { source: blockchain::ArchiveError } = "Cannot export or import the block archive",
+    StorageBackendMismatch { expected: StorageBackend, found: StorageBackend } = "The storage holds a {found} database instead of {expected}, set `storage_backend' accordingly or use `migrate-storage'",
+    StorageExists { path: String } = "The storage `{path}' already exists",
+    StorageMigration { source: blockchain::MigrateError } = "Cannot migrate the storage"
 }
 
 impl Error {
@@ -32,9 +51,23 @@ impl Error {
             Error::ParseError { .. } => 4,
             Error::StorageError { .. } => 5,
             Error::Blockchain { .. } => 6,
+            Error::BlockchainState { .. } => 6,
             Error::Block0 { .. } => 7,
             Error::NodeSecrets { .. } => 8,
             Error::FetchBlock0 { .. } => 9,
+            Error::Service { .. } => 10,
+            Error::StorageFlush { .. } => 11,
+            Error::Block0Mismatch { .. } => 7,
+            Error::Block0Unavailable { .. } => 7,
+            Error::InvalidConfiguration { .. } => 2,
+            Error::PrintConfiguration { .. } => 2,
+            Error::StorageNotFound { .. } => 5,
+            Error::StorageVerification { .. } => 5,
+            Error::InconsistentStorage { .. } => 12,
+            Error::Archive { .. } => 13,
+            Error::StorageBackendMismatch { .. } => 5,
+            Error::StorageExists { .. } => 5,
+            Error::StorageMigration { .. } => 14,
         }
     }
 }
@@ -3,37 +3,151 @@ mod error;
 pub use self::error::{Error, ErrorKind};
 use crate::{
     blockcfg::Block,
-    blockchain::{Blockchain, BlockchainR},
+    blockchain::{Blockchain, BlockchainR, LOCAL_BLOCKCHAIN_TIP_TAG},
     leadership::EpochParameters,
-    network,
-    settings::start::Settings,
+    network, secure,
+    settings::{
+        start::{Settings, StorageBackend},
+        Block0Info,
+    },
+    storage::{Flusher, PrunedBlockStore, Pruner, SledBlockStore},
 };
 use chain_storage::{memory::MemoryBlockStore, store::BlockStore};
 use chain_storage_sqlite::SQLiteBlockStore;
 use slog::Logger;
+use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 
 pub type NodeStorage = Box<BlockStore<Block = Block> + Send + Sync>;
 
-/// prepare the block storage from the given settings
+/// name of the SQLite file, in the storage directory, holding the blocks
+const SQLITE_FILE: &str = "blocks.sqlite";
+/// name of the sled database directory, in the storage directory, holding
+/// the blocks
+const SLED_DIR: &str = "blocks.sled";
+/// name of the directory, in the storage directory, of the pruned store
+const PRUNED_DIR: &str = "pruned";
+
+/// prepare the block storage from the given settings, with the pruner of
+/// the storage if its blocks are pruned and the flusher of the storage if
+/// its writes are only flushed periodically
 ///
-pub fn prepare_storage(setting: &Settings, logger: &Logger) -> Result<NodeStorage, Error> {
-    match &setting.storage {
-        None => {
+pub fn prepare_storage(
+    setting: &Settings,
+    logger: &Logger,
+) -> Result<(NodeStorage, Option<Pruner>, Option<Flusher>), Error> {
+    match (&setting.storage, setting.pruning) {
+        (None, _) => {
             info!(logger, "storing blockchain in memory");
-            Ok(Box::new(MemoryBlockStore::new()))
+            Ok((Box::new(MemoryBlockStore::new()), None, None))
         }
-        Some(dir) => {
-            std::fs::create_dir_all(dir).map_err(|err| Error::IO {
-                source: err,
-                reason: ErrorKind::SQLite,
-            })?;
-            let mut sqlite = dir.clone();
-            sqlite.push("blocks.sqlite");
-            info!(logger, "storing blockchain in '{:?}'", sqlite);
-            Ok(Box::new(SQLiteBlockStore::new(sqlite)))
+        (Some(dir), None) => {
+            let backend = setting.storage_backend;
+            match storage_backend(dir) {
+                Some(found) if found != backend => {
+                    return Err(Error::StorageBackendMismatch {
+                        expected: backend,
+                        found,
+                    });
+                }
+                _ => {}
+            }
+            info!(
+                logger,
+                "storing blockchain in '{:?}'",
+                storage_path(dir, backend)
+            );
+            let (storage, flusher) = open_database(dir, backend)?;
+            Ok((storage, None, flusher))
         }
+        (Some(dir), Some(pruning)) => {
+            let dir = dir.join(PRUNED_DIR);
+            info!(
+                logger,
+                "storing the last {} epochs of blockchain in '{:?}'", pruning.keep_epochs, dir
+            );
+            let mut storage = PrunedBlockStore::open(&dir)?;
+            if storage.is_pruned()? {
+                warn!(
+                    logger,
+                    "the storage has been pruned, the blockchain will be synchronized again from the genesis block"
+                );
+                storage.reset()?;
+            }
+            let pruner = storage.pruner(pruning.keep_epochs);
+            let flusher = storage.flusher();
+            Ok((Box::new(storage), Some(pruner), Some(flusher)))
+        }
+    }
+}
+
+/// the SQLite file or the sled directory holding the blocks of the
+/// storage directory
+pub fn storage_path(dir: &Path, backend: StorageBackend) -> PathBuf {
+    match backend {
+        StorageBackend::Sqlite => dir.join(SQLITE_FILE),
+        StorageBackend::Sled => dir.join(SLED_DIR),
+    }
+}
+
+/// the database of the blocks of the storage directory, `None` if there
+/// is none yet
+pub fn storage_backend(dir: &Path) -> Option<StorageBackend> {
+    [StorageBackend::Sqlite, StorageBackend::Sled]
+        .iter()
+        .cloned()
+        .find(|backend| storage_path(dir, *backend).exists())
+}
+
+/// open the block storage of the given directory, creating it with the
+/// given database if needed. A sled database is flushed when the storage
+/// is dropped.
+pub fn create_storage(dir: &Path, backend: StorageBackend) -> Result<NodeStorage, Error> {
+    open_database(dir, backend).map(|(storage, _)| storage)
+}
+
+/// the storage of the given directory, with its flusher if the database
+/// only flushes its writes periodically
+fn open_database(
+    dir: &Path,
+    backend: StorageBackend,
+) -> Result<(NodeStorage, Option<Flusher>), Error> {
+    std::fs::create_dir_all(dir).map_err(|err| Error::IO {
+        source: err,
+        reason: ErrorKind::Storage,
+    })?;
+    let path = storage_path(dir, backend);
+    match backend {
+        StorageBackend::Sqlite => Ok((Box::new(SQLiteBlockStore::new(path)), None)),
+        StorageBackend::Sled => {
+            let storage = SledBlockStore::open(&path)?;
+            let flusher = storage.flusher();
+            Ok((Box::new(storage), Some(flusher)))
+        }
+    }
+}
+
+/// open the block storage of the given directory, which must exist
+pub fn open_storage(dir: &Path) -> Result<NodeStorage, Error> {
+    match storage_backend(dir) {
+        Some(backend) => create_storage(dir, backend),
+        None => Err(Error::StorageNotFound {
+            path: dir.display().to_string(),
+        }),
+    }
+}
+
+/// remove the database of the given storage directory
+pub fn remove_storage(dir: &Path, backend: StorageBackend) -> Result<(), Error> {
+    let path = storage_path(dir, backend);
+    match backend {
+        StorageBackend::Sqlite => std::fs::remove_file(path),
+        StorageBackend::Sled => std::fs::remove_dir_all(path),
     }
+    .map_err(|err| Error::IO {
+        source: err,
+        reason: ErrorKind::Storage,
+    })
 }
 
 /// loading the block 0 is not as trivial as it seems,
@@ -48,20 +162,10 @@ pub fn prepare_block_0(
     storage: &NodeStorage,
     logger: &Logger,
 ) -> Result<Block, Error> {
-    use crate::settings::Block0Info;
     match &settings.block_0 {
         Block0Info::Path(path) => {
-            use chain_core::property::Deserialize as _;
             debug!(logger, "parsing block0 from file path `{:?}'", path);
-            let f = std::fs::File::open(path).map_err(|err| Error::IO {
-                source: err,
-                reason: ErrorKind::Block0,
-            })?;
-            let reader = std::io::BufReader::new(f);
-            Block::deserialize(reader).map_err(|err| Error::ParseError {
-                source: err,
-                reason: ErrorKind::Block0,
-            })
+            read_block_0(path)
         }
         Block0Info::Hash(block0_id) => {
             if storage.block_exists(&block0_id)? {
@@ -85,10 +189,79 @@ pub fn prepare_block_0(
 pub fn load_blockchain(
     block0: Block,
     storage: NodeStorage,
+    pruner: Option<Pruner>,
+    flusher: Option<Flusher>,
     epoch_event: mpsc::Sender<EpochParameters>,
     logger: &Logger,
 ) -> Result<BlockchainR, Error> {
     let mut blockchain_data = Blockchain::load(block0, storage, epoch_event, logger)?;
+    blockchain_data.pruner = pruner;
+    blockchain_data.flusher = flusher;
     blockchain_data.initial()?;
     Ok(blockchain_data.into())
 }
+
+fn read_block_0(path: &Path) -> Result<Block, Error> {
+    use chain_core::property::Deserialize as _;
+    let f = std::fs::File::open(path).map_err(|err| Error::IO {
+        source: err,
+        reason: ErrorKind::Block0,
+    })?;
+    let reader = std::io::BufReader::new(f);
+    Block::deserialize(reader).map_err(|err| Error::ParseError {
+        source: err,
+        reason: ErrorKind::Block0,
+    })
+}
+
+/// check the secret files and the genesis block of the given settings
+/// without starting the node, all the errors found are returned instead
+/// of only the first one
+pub fn validate(settings: &Settings, logger: &Logger) -> Vec<Error> {
+    let mut errors = Vec::new();
+    for path in &settings.leadership {
+        if let Err(source) = secure::NodeSecret::load_from_file(path) {
+            errors.push(Error::NodeSecrets { source });
+        }
+    }
+    if let Err(error) = validate_block_0(settings, logger) {
+        errors.push(error);
+    }
+    errors
+}
+
+/// the genesis block needs to be the one of the blockchain already in the
+/// storage, if any, and to be retrievable if only its hash is given
+fn validate_block_0(settings: &Settings, logger: &Logger) -> Result<(), Error> {
+    use chain_core::property::Block as _;
+
+    let block0_id = match &settings.block_0 {
+        Block0Info::Path(path) => read_block_0(path)?.id(),
+        Block0Info::Hash(hash) => hash.clone(),
+    };
+    // the storage is not created if it does not exist yet
+    let dir = settings
+        .storage
+        .as_ref()
+        .filter(|dir| settings.pruning.is_none() && storage_backend(dir).is_some());
+
+    let in_storage = match dir {
+        Some(dir) => {
+            debug!(logger, "checking block0 {} in '{:?}'", block0_id, dir);
+            let storage = open_storage(dir)?;
+            let in_storage = storage.block_exists(&block0_id)?;
+            if !in_storage && storage.get_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?.is_some() {
+                return Err(Error::Block0Mismatch { block0_id });
+            }
+            in_storage
+        }
+        None => false,
+    };
+
+    match settings.block_0 {
+        Block0Info::Hash(_) if !in_storage && settings.network.trusted_peers.is_empty() => {
+            Err(Error::Block0Unavailable { block0_id })
+        }
+        _ => Ok(()),
+    }
+}
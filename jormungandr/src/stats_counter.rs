@@ -11,6 +11,7 @@ pub struct StatsCounter {
 struct StatsCounterImpl {
     tx_recv_cnt: AtomicUsize,
     block_recv_cnt: AtomicUsize,
+    reorg_cnt: AtomicUsize,
     start_time: Instant,
 }
 
@@ -19,6 +20,7 @@ impl Default for StatsCounterImpl {
         Self {
             tx_recv_cnt: AtomicUsize::default(),
             block_recv_cnt: AtomicUsize::default(),
+            reorg_cnt: AtomicUsize::default(),
             start_time: Instant::now(),
         }
     }
@@ -43,6 +45,14 @@ impl StatsCounter {
         self.stats.block_recv_cnt.load(Ordering::Relaxed) as u64
     }
 
+    pub fn add_reorg_cnt(&self, count: usize) {
+        self.stats.reorg_cnt.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn get_reorg_cnt(&self) -> u64 {
+        self.stats.reorg_cnt.load(Ordering::Relaxed) as u64
+    }
+
     pub fn get_uptime_sec(&self) -> u64 {
         self.stats.start_time.elapsed().as_secs()
     }
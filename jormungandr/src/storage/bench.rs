@@ -0,0 +1,75 @@
+//! Throughput of the block stores of a storage directory, the blocks being
+//! added the way the node adds them to the tip. Run with:
+//!
+//! ```text
+//! cargo +nightly bench --features with-bench storage::bench
+//! ```
+
+use super::SledBlockStore;
+use crate::blockcfg::{mock, Block};
+use crate::start_up::NodeStorage;
+use chain_core::property::Block as _;
+use chain_storage::store;
+use chain_storage_sqlite::SQLiteBlockStore;
+use std::path::Path;
+use test::Bencher;
+
+const BLOCKS: u32 = 1000;
+const TIP_TAG: &str = "tip";
+
+fn sqlite(dir: &Path) -> NodeStorage {
+    Box::new(SQLiteBlockStore::new(dir.join("blocks.sqlite")))
+}
+
+fn sled(dir: &Path) -> NodeStorage {
+    Box::new(SledBlockStore::open(&dir.join("blocks.sled")).unwrap())
+}
+
+fn put_tips(storage: &mut NodeStorage, blocks: &[Block]) {
+    for block in blocks {
+        storage.put_block(block).unwrap();
+        storage.put_tag(TIP_TAG, &block.id()).unwrap();
+    }
+}
+
+fn insert(b: &mut Bencher, open: fn(&Path) -> NodeStorage) {
+    let blocks = mock::chain(BLOCKS);
+    b.iter(|| {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        put_tips(&mut open(dir.as_ref()), &blocks);
+    });
+}
+
+fn iterate_range(b: &mut Bencher, open: fn(&Path) -> NodeStorage) {
+    let blocks = mock::chain(BLOCKS);
+    let dir = mktemp::Temp::new_dir().unwrap();
+    let mut storage = open(dir.as_ref());
+    put_tips(&mut storage, &blocks);
+    let from = blocks.first().unwrap().id();
+    let to = blocks.last().unwrap().id();
+    b.iter(|| {
+        for info in store::iterate_range(&storage, &from, &to).unwrap() {
+            storage.get_block(&info.unwrap().block_hash).unwrap();
+        }
+    });
+}
+
+#[bench]
+fn insert_sqlite(b: &mut Bencher) {
+    insert(b, sqlite)
+}
+
+#[bench]
+fn insert_sled(b: &mut Bencher) {
+    insert(b, sled)
+}
+
+#[bench]
+fn iterate_range_sqlite(b: &mut Bencher) {
+    iterate_range(b, sqlite)
+}
+
+#[bench]
+fn iterate_range_sled(b: &mut Bencher) {
+    iterate_range(b, sled)
+}
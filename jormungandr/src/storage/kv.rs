@@ -0,0 +1,295 @@
+//! Block store on [sled], an embedded log-structured key-value store,
+//! writing less than SQLite for every block added.
+//!
+//! The writes are made durable by the periodic flush of sled, the
+//! [`Flusher`] of the store flushes them when they must not be lost,
+//! and the store is flushed when it is dropped.
+//!
+//! The keys of the store, the hashes being in hexadecimal:
+//!
+//! ```text
+//! block/<hash>  the serialized block
+//! info/<hash>   the depth of the block and its back links, as text:
+//!               `<depth> <distance>:<hash> ...`
+//! tag/<name>    the hash of the tagged block
+//! ```
+//!
+//! [sled]: https://docs.rs/sled
+//! [`Flusher`]: ./struct.Flusher.html
+
+use crate::blockcfg::{Block, HeaderHash};
+use chain_core::property::{Block as _, Deserialize as _, Serialize as _};
+use chain_storage::{
+    error::Error as StorageError,
+    store::{BackLink, BlockInfo, BlockStore},
+};
+use sled::{Batch, Db};
+use std::{error, fmt, path::Path};
+
+const BLOCK_PREFIX: &str = "block/";
+const INFO_PREFIX: &str = "info/";
+const TAG_PREFIX: &str = "tag/";
+
+#[derive(Clone)]
+pub struct SledBlockStore {
+    db: Db,
+}
+
+/// flushes the pending writes of a [`SledBlockStore`] to the disk
+///
+/// [`SledBlockStore`]: ./struct.SledBlockStore.html
+#[derive(Clone)]
+pub struct Flusher {
+    db: Db,
+}
+
+/// an entry of the store which cannot be decoded
+#[derive(Debug)]
+struct Corrupted {
+    key: String,
+}
+
+impl SledBlockStore {
+    /// open the store of the given directory, creating it if needed
+    pub fn open(dir: &Path) -> Result<Self, StorageError> {
+        let db = sled::open(dir).map_err(backend_error)?;
+        Ok(SledBlockStore { db })
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        Flusher {
+            db: self.db.clone(),
+        }
+    }
+
+    /// the hashes of the stored blocks, whose bodies may have been
+    /// removed
+    pub(super) fn block_hashes(&self) -> Result<Vec<HeaderHash>, StorageError> {
+        self.hashes(INFO_PREFIX)
+    }
+
+    /// the hashes of the blocks whose bodies are stored
+    pub(super) fn body_hashes(&self) -> Result<Vec<HeaderHash>, StorageError> {
+        self.hashes(BLOCK_PREFIX)
+    }
+
+    /// remove the body of the block, the block is still found with its
+    /// info
+    pub(super) fn remove_body(&self, hash: &HeaderHash) -> Result<(), StorageError> {
+        self.remove(&block_key(hash))
+    }
+
+    /// remove the block and its info
+    pub(super) fn remove_block(&self, hash: &HeaderHash) -> Result<(), StorageError> {
+        let mut batch = Batch::default();
+        batch.remove(block_key(hash).as_bytes());
+        batch.remove(info_key(hash).as_bytes());
+        self.db.apply_batch(batch).map_err(backend_error)
+    }
+
+    pub(super) fn remove_tag(&self, tag_name: &str) -> Result<(), StorageError> {
+        self.remove(&tag_key(tag_name))
+    }
+
+    fn hashes(&self, prefix: &str) -> Result<Vec<HeaderHash>, StorageError> {
+        let mut hashes = Vec::new();
+        for key in self.db.scan_prefix(prefix).keys() {
+            let key = key.map_err(backend_error)?;
+            let hash = decode_hash(&key[prefix.len()..]).ok_or_else(|| {
+                backend_error(Corrupted {
+                    key: String::from_utf8_lossy(&key).into_owned(),
+                })
+            })?;
+            hashes.push(hash);
+        }
+        Ok(hashes)
+    }
+
+    pub(super) fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.db
+            .get(key)
+            .map(|value| value.map(|value| value.to_vec()))
+            .map_err(backend_error)
+    }
+
+    pub(super) fn insert(&self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .insert(key, value)
+            .map(|_| ())
+            .map_err(backend_error)
+    }
+
+    pub(super) fn remove(&self, key: &str) -> Result<(), StorageError> {
+        self.db.remove(key).map(|_| ()).map_err(backend_error)
+    }
+}
+
+impl BlockStore for SledBlockStore {
+    type Block = Block;
+
+    fn put_block_internal(
+        &mut self,
+        block: &Block,
+        block_info: BlockInfo<HeaderHash>,
+    ) -> Result<(), StorageError> {
+        let hash = block.id();
+        let block = block.serialize_as_vec().map_err(backend_error)?;
+        // the block and its info are written together, a block is never
+        // found without its info or the other way around
+        let mut batch = Batch::default();
+        batch.insert(block_key(&hash).as_bytes(), block);
+        batch.insert(
+            info_key(&hash).as_bytes(),
+            encode_info(&block_info).as_bytes(),
+        );
+        self.db.apply_batch(batch).map_err(backend_error)
+    }
+
+    fn get_block(
+        &self,
+        block_hash: &HeaderHash,
+    ) -> Result<(Block, BlockInfo<HeaderHash>), StorageError> {
+        let block_info = self.get_block_info(block_hash)?;
+        let key = block_key(block_hash);
+        let block = self.get(&key)?.ok_or(StorageError::BlockNotFound)?;
+        let block =
+            Block::deserialize(block.as_slice()).map_err(|_| backend_error(Corrupted { key }))?;
+        Ok((block, block_info))
+    }
+
+    fn get_block_info(
+        &self,
+        block_hash: &HeaderHash,
+    ) -> Result<BlockInfo<HeaderHash>, StorageError> {
+        let key = info_key(block_hash);
+        let info = self.get(&key)?.ok_or(StorageError::BlockNotFound)?;
+        decode_info(block_hash, &info).ok_or_else(|| backend_error(Corrupted { key }))
+    }
+
+    fn put_tag(&mut self, tag_name: &str, block_hash: &HeaderHash) -> Result<(), StorageError> {
+        self.insert(&tag_key(tag_name), block_hash.to_string().as_bytes())
+    }
+
+    fn get_tag(&self, tag_name: &str) -> Result<Option<HeaderHash>, StorageError> {
+        let key = tag_key(tag_name);
+        match self.get(&key)? {
+            None => Ok(None),
+            Some(hash) => decode_hash(&hash)
+                .map(Some)
+                .ok_or_else(|| backend_error(Corrupted { key })),
+        }
+    }
+}
+
+fn block_key(hash: &HeaderHash) -> String {
+    format!("{}{}", BLOCK_PREFIX, hash)
+}
+
+fn info_key(hash: &HeaderHash) -> String {
+    format!("{}{}", INFO_PREFIX, hash)
+}
+
+fn tag_key(tag_name: &str) -> String {
+    format!("{}{}", TAG_PREFIX, tag_name)
+}
+
+fn encode_info(block_info: &BlockInfo<HeaderHash>) -> String {
+    let mut info = block_info.depth.to_string();
+    for back_link in block_info.back_links.iter() {
+        info.push_str(&format!(" {}:{}", back_link.distance, back_link.block_hash));
+    }
+    info
+}
+
+fn decode_info(block_hash: &HeaderHash, info: &[u8]) -> Option<BlockInfo<HeaderHash>> {
+    let mut fields = std::str::from_utf8(info).ok()?.split(' ');
+    let depth = fields.next()?.parse().ok()?;
+    let mut back_links = Vec::new();
+    for field in fields {
+        let mut back_link = field.splitn(2, ':');
+        let distance = back_link.next()?.parse().ok()?;
+        let block_hash = back_link.next()?.parse().ok()?;
+        back_links.push(BackLink {
+            distance,
+            block_hash,
+        });
+    }
+    Some(BlockInfo {
+        block_hash: block_hash.clone(),
+        depth,
+        back_links,
+    })
+}
+
+fn decode_hash(hash: &[u8]) -> Option<HeaderHash> {
+    std::str::from_utf8(hash).ok()?.parse().ok()
+}
+
+fn backend_error<E>(err: E) -> StorageError
+where
+    E: Into<Box<dyn error::Error + Send + Sync>>,
+{
+    StorageError::BackendError(err.into())
+}
+
+impl Flusher {
+    /// make the blocks and the tags written so far durable
+    pub fn flush(&self) -> Result<(), StorageError> {
+        self.db.flush().map(|_| ()).map_err(backend_error)
+    }
+}
+
+impl fmt::Display for Corrupted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the entry `{}' of the storage is corrupted", self.key)
+    }
+}
+
+impl error::Error for Corrupted {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockcfg::mock;
+    use chain_core::property::Block as _;
+    use chain_storage::store;
+
+    #[test]
+    fn blocks_and_tags_are_read_back_after_reopening() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let blocks = mock::chain(10);
+        let tip = blocks.last().unwrap().id();
+        {
+            let mut storage = SledBlockStore::open(dir.as_ref()).unwrap();
+            for block in blocks.iter() {
+                storage.put_block(block).unwrap();
+            }
+            storage.put_tag("tip", &tip).unwrap();
+        }
+
+        let storage = SledBlockStore::open(dir.as_ref()).unwrap();
+        assert_eq!(storage.get_tag("tip").unwrap(), Some(tip.clone()));
+        assert_eq!(storage.get_tag("other").unwrap(), None);
+        let (block, info) = storage.get_block(&tip).unwrap();
+        assert_eq!(block.id(), tip);
+        assert_eq!(info.depth, 9);
+        let range = store::iterate_range(&storage, &blocks[0].id(), &tip)
+            .unwrap()
+            .map(|info| info.unwrap().block_hash)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            range,
+            blocks[1..]
+                .iter()
+                .map(|block| block.id())
+                .collect::<Vec<_>>()
+        );
+        let mut builder = BlockBuilder::new();
+        builder.date(BlockDate {
+            epoch: 1,
+            slot_id: 0,
+        });
+        let unknown = builder.make_genesis_block();
+        assert!(!storage.block_exists(&unknown.id()).unwrap());
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(all(test, feature = "with-bench"))]
+mod bench;
+mod kv;
+mod pruned;
+
+pub use self::kv::{Flusher, SledBlockStore};
+pub use self::pruned::{PrunedBlockStore, Pruner};
@@ -0,0 +1,308 @@
+//! Block store of a pruned node: the info of every block of the tip's
+//! branch is kept, so the ancestors of a stored block are always known,
+//! but the bodies of the blocks older than the last epochs are deleted,
+//! with the blocks of the other branches.
+//!
+//! The store is a [`SledBlockStore`], holding in addition:
+//!
+//! ```text
+//! pruned  present once bodies have been deleted
+//! ```
+//!
+//! The ledger cannot be serialized by `chain-impl-mockchain` yet, its
+//! state is only held in memory by the running node. A pruned store
+//! cannot be replayed from the block 0 anymore, it has to be [`reset`]
+//! before the node restarts. Restarting from the store needs the ledger
+//! state at the prune boundary to be persisted, which depends on that
+//! serialization.
+//!
+//! [`SledBlockStore`]: ./struct.SledBlockStore.html
+//! [`reset`]: ./struct.PrunedBlockStore.html#method.reset
+
+use super::{Flusher, SledBlockStore};
+use crate::{
+    blockcfg::{Block, Epoch, HeaderHash},
+    blockchain::LOCAL_BLOCKCHAIN_TIP_TAG,
+};
+use chain_core::property::Block as _;
+use chain_storage::{
+    error::Error as StorageError,
+    store::{BlockInfo, BlockStore},
+};
+use std::path::Path;
+
+const PRUNED_KEY: &str = "pruned";
+
+pub struct PrunedBlockStore {
+    store: SledBlockStore,
+}
+
+/// deletes the bodies of the blocks of a [`PrunedBlockStore`] older than
+/// the last `keep_epochs` epochs
+///
+/// [`PrunedBlockStore`]: ./struct.PrunedBlockStore.html
+#[derive(Clone)]
+pub struct Pruner {
+    store: SledBlockStore,
+    keep_epochs: u32,
+}
+
+impl PrunedBlockStore {
+    /// open the store of the given directory, creating it if needed
+    pub fn open(dir: &Path) -> Result<Self, StorageError> {
+        Ok(PrunedBlockStore {
+            store: SledBlockStore::open(dir)?,
+        })
+    }
+
+    /// the pruner of the store, keeping the blocks of the current epoch
+    /// and of the `keep_epochs - 1` epochs before
+    pub fn pruner(&self, keep_epochs: u32) -> Pruner {
+        Pruner {
+            store: self.store.clone(),
+            keep_epochs,
+        }
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.store.flusher()
+    }
+
+    /// whether bodies of blocks have been deleted, the blockchain of the
+    /// store cannot be replayed from the block 0 then
+    pub fn is_pruned(&self) -> Result<bool, StorageError> {
+        Ok(self.store.get(PRUNED_KEY)?.is_some())
+    }
+
+    /// delete every block but the block 0, and the tip, so the node
+    /// synchronizes the blockchain again from its block 0
+    pub fn reset(&mut self) -> Result<(), StorageError> {
+        self.store.remove_tag(LOCAL_BLOCKCHAIN_TIP_TAG)?;
+        for hash in self.store.block_hashes()? {
+            if self.store.get_block_info(&hash)?.depth != 0 {
+                self.store.remove_block(&hash)?;
+            }
+        }
+        self.store.remove(PRUNED_KEY)
+    }
+}
+
+impl BlockStore for PrunedBlockStore {
+    type Block = Block;
+
+    fn put_block_internal(
+        &mut self,
+        block: &Block,
+        block_info: BlockInfo<HeaderHash>,
+    ) -> Result<(), StorageError> {
+        self.store.put_block_internal(block, block_info)
+    }
+
+    fn get_block(
+        &self,
+        block_hash: &HeaderHash,
+    ) -> Result<(Block, BlockInfo<HeaderHash>), StorageError> {
+        self.store.get_block(block_hash)
+    }
+
+    fn get_block_info(
+        &self,
+        block_hash: &HeaderHash,
+    ) -> Result<BlockInfo<HeaderHash>, StorageError> {
+        self.store.get_block_info(block_hash)
+    }
+
+    fn put_tag(&mut self, tag_name: &str, block_hash: &HeaderHash) -> Result<(), StorageError> {
+        self.store.put_tag(tag_name, block_hash)
+    }
+
+    fn get_tag(&self, tag_name: &str) -> Result<Option<HeaderHash>, StorageError> {
+        self.store.get_tag(tag_name)
+    }
+}
+
+impl Pruner {
+    /// delete the bodies of the blocks of the tip's branch older than the
+    /// epochs to keep once the tip is in the given epoch, and the blocks
+    /// of the other branches as old as them. The block 0 is never pruned.
+    /// Returns the number of bodies deleted.
+    pub fn prune(&self, tip: &HeaderHash, tip_epoch: Epoch) -> Result<usize, StorageError> {
+        let oldest_kept = (tip_epoch + 1).saturating_sub(self.keep_epochs);
+        let boundary = match self.boundary(tip, oldest_kept)? {
+            Some(boundary) => boundary,
+            None => return Ok(0),
+        };
+        // marked first, an interrupted pruning is not taken for a
+        // complete store
+        self.store.insert(PRUNED_KEY, &[])?;
+        let mut pruned = 0;
+        for hash in self.store.body_hashes()? {
+            let info = self.store.get_block_info(&hash)?;
+            if info.depth == 0 || info.depth > boundary.depth {
+                continue;
+            }
+            if ancestor_at_depth(&self.store, &boundary, info.depth)? == hash {
+                self.store.remove_body(&hash)?;
+            } else {
+                self.store.remove_block(&hash)?;
+            }
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
+    /// the last block of the tip's branch older than the given epoch,
+    /// `None` if there is none but the block 0. The branch is walked back
+    /// from the tip until a block of an older epoch, or a block already
+    /// pruned, is found.
+    fn boundary(
+        &self,
+        tip: &HeaderHash,
+        oldest_kept: Epoch,
+    ) -> Result<Option<BlockInfo<HeaderHash>>, StorageError> {
+        let mut info = self.store.get_block_info(tip)?;
+        loop {
+            if info.depth == 0 {
+                return Ok(None);
+            }
+            match self.store.get_block(&info.block_hash) {
+                Ok((block, _)) if block.date().epoch >= oldest_kept => {
+                    info = self.store.get_block_info(&block.parent_id())?;
+                }
+                Ok(_) | Err(StorageError::BlockNotFound) => return Ok(Some(info)),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// the hash of the ancestor at the given depth of a block, going back
+/// with the longest back links not passing it
+fn ancestor_at_depth(
+    store: &SledBlockStore,
+    block_info: &BlockInfo<HeaderHash>,
+    depth: u64,
+) -> Result<HeaderHash, StorageError> {
+    let mut current = block_info.clone();
+    while current.depth > depth {
+        let remaining = current.depth - depth;
+        let back_link = current
+            .back_links
+            .iter()
+            .filter(|back_link| back_link.distance <= remaining)
+            .max_by_key(|back_link| back_link.distance)
+            .ok_or(StorageError::BlockNotFound)?;
+        current = store.get_block_info(&back_link.block_hash)?;
+    }
+    Ok(current.block_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockcfg::mock::{block_0, block_after};
+    use crate::blockcfg::BlockDate;
+    use chain_storage::store;
+
+    /// the block 0 followed by a block in each of the epochs 0 to 3
+    fn blocks() -> Vec<Block> {
+        let mut blocks = vec![block_0()];
+        for epoch in 0..4 {
+            let parent = blocks.last().unwrap().clone();
+            blocks.push(block_after(&parent, BlockDate { epoch, slot_id: 1 }));
+        }
+        blocks
+    }
+
+    #[test]
+    fn pruned_blocks_keep_their_infos() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let mut storage = PrunedBlockStore::open(dir.as_ref()).unwrap();
+        let blocks = blocks();
+        for block in blocks.iter() {
+            storage.put_block(block).unwrap();
+        }
+        let tip = blocks.last().unwrap().id();
+        storage.put_tag("tip", &tip).unwrap();
+
+        let pruner = storage.pruner(2);
+        assert!(!storage.is_pruned().unwrap());
+        // the blocks of the epochs 2 and 3 are kept
+        assert_eq!(pruner.prune(&tip, 3).unwrap(), 2);
+        assert!(storage.is_pruned().unwrap());
+
+        assert!(storage.get_block(&blocks[0].id()).is_ok());
+        for block in &blocks[1..3] {
+            assert!(storage.block_exists(&block.id()).unwrap());
+            match storage.get_block(&block.id()) {
+                Err(StorageError::BlockNotFound) => {}
+                _ => panic!("the body of the block {} is not pruned", block.id()),
+            }
+        }
+        for block in &blocks[3..] {
+            assert!(storage.get_block(&block.id()).is_ok());
+        }
+        assert_eq!(storage.get_block_info(&tip).unwrap().depth, 4);
+        let ancestors = store::iterate_range(&storage, &blocks[0].id(), &tip)
+            .unwrap()
+            .map(|info| info.unwrap().block_hash)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ancestors,
+            blocks[1..]
+                .iter()
+                .map(|block| block.id())
+                .collect::<Vec<_>>()
+        );
+
+        // the pruning goes on from the blocks already pruned
+        assert_eq!(pruner.prune(&tip, 4).unwrap(), 1);
+        assert!(storage.block_exists(&blocks[3].id()).unwrap());
+        assert!(storage.get_block(&blocks[4].id()).is_ok());
+    }
+
+    #[test]
+    fn old_blocks_of_other_branches_are_deleted() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let mut storage = PrunedBlockStore::open(dir.as_ref()).unwrap();
+        let blocks = blocks();
+        let fork = block_after(
+            &blocks[1],
+            BlockDate {
+                epoch: 0,
+                slot_id: 2,
+            },
+        );
+        for block in blocks.iter().chain(Some(&fork)) {
+            storage.put_block(block).unwrap();
+        }
+
+        let pruner = storage.pruner(1);
+        assert_eq!(pruner.prune(&blocks[4].id(), 3).unwrap(), 4);
+        assert!(!storage.block_exists(&fork.id()).unwrap());
+        for block in &blocks[..4] {
+            assert!(storage.block_exists(&block.id()).unwrap());
+        }
+    }
+
+    #[test]
+    fn reset_store_only_keeps_the_block_0() {
+        let dir = mktemp::Temp::new_dir().unwrap();
+        let mut storage = PrunedBlockStore::open(dir.as_ref()).unwrap();
+        let blocks = blocks();
+        for block in blocks.iter() {
+            storage.put_block(block).unwrap();
+        }
+        let tip = blocks.last().unwrap().id();
+        storage.put_tag(LOCAL_BLOCKCHAIN_TIP_TAG, &tip).unwrap();
+        storage.pruner(1).prune(&tip, 3).unwrap();
+
+        storage.reset().unwrap();
+        assert!(!storage.is_pruned().unwrap());
+        assert_eq!(storage.get_tag(LOCAL_BLOCKCHAIN_TIP_TAG).unwrap(), None);
+        assert!(storage.get_block(&blocks[0].id()).is_ok());
+        for block in &blocks[1..] {
+            assert!(!storage.block_exists(&block.id()).unwrap());
+        }
+    }
+}
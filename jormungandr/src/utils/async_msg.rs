@@ -3,21 +3,30 @@
 
 use futures::prelude::*;
 use futures::sync::mpsc::{self, Receiver, Sender, TrySendError};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 /// The output end of an in-memory FIFO channel.
-pub struct MessageBox<Msg>(Sender<Msg>);
+pub struct MessageBox<Msg>(Sender<Msg>, QueueDepth);
 
 /// The input end of an in-memory FIFO channel.
 /// This can be read asynchronously in a Tokio task using its
 /// Stream implementation.
-pub struct MessageQueue<Msg>(Receiver<Msg>);
+pub struct MessageQueue<Msg>(Receiver<Msg>, QueueDepth);
+
+/// The number of messages sent over a channel and not read yet
+#[derive(Clone, Default)]
+pub struct QueueDepth(Arc<AtomicUsize>);
 
 /// Constructs an in-memory channel and returns the output and input halves.
 /// The parameter specifies the number of messages that are allowed
 /// to be pending in the channel.
 pub fn channel<Msg>(buffer: usize) -> (MessageBox<Msg>, MessageQueue<Msg>) {
     let (tx, rx) = mpsc::channel(buffer);
-    (MessageBox(tx), MessageQueue(rx))
+    let depth = QueueDepth::default();
+    (MessageBox(tx, depth.clone()), MessageQueue(rx, depth))
 }
 
 impl<Msg> MessageBox<Msg> {
@@ -31,7 +40,33 @@ impl<Msg> MessageBox<Msg> {
     /// If the channel is full or the receiving MessageQueue has been dropped,
     /// an error is returned in `Err`.
     pub fn try_send(&mut self, a: Msg) -> Result<(), TrySendError<Msg>> {
-        self.0.try_send(a)
+        self.1.increment();
+        self.0.try_send(a).map_err(|e| {
+            self.1.decrement();
+            e
+        })
+    }
+}
+
+impl<Msg> MessageQueue<Msg> {
+    /// Get a handle on the number of messages waiting in the queue.
+    pub fn depth(&self) -> QueueDepth {
+        self.1.clone()
+    }
+}
+
+impl QueueDepth {
+    /// The number of messages waiting to be read.
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn decrement(&self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
@@ -39,12 +74,16 @@ impl<Msg> Stream for MessageQueue<Msg> {
     type Item = Msg;
     type Error = ();
     fn poll(&mut self) -> Poll<Option<Msg>, ()> {
-        self.0.poll()
+        let polled = self.0.poll();
+        if let Ok(Async::Ready(Some(_))) = &polled {
+            self.1.decrement();
+        }
+        polled
     }
 }
 
 impl<Msg> Clone for MessageBox<Msg> {
     fn clone(&self) -> Self {
-        MessageBox(self.0.clone())
+        MessageBox(self.0.clone(), self.1.clone())
     }
 }
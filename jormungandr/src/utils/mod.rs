@@ -1,3 +1,4 @@
 pub mod async_msg;
 pub mod borrow;
+pub mod signal;
 pub mod task;
@@ -0,0 +1,49 @@
+//! Handling of the signals requesting the node to shut down
+
+use crate::utils::task::{ShutdownReason, ShutdownTrigger};
+use futures::{Future, Stream};
+use slog::Logger;
+use std::{io, thread};
+
+type Signals = Box<dyn Stream<Item = &'static str, Error = io::Error> + Send>;
+
+/// listen in the background for the signals requesting the node to shut
+/// down (`SIGINT` and, on unix, `SIGTERM`), the shutdown is triggered on
+/// the first one received
+pub fn handle_shutdown_signals(trigger: ShutdownTrigger, logger: Logger) {
+    let listener = shutdown_signals().into_future().then(move |result| {
+        match result {
+            Ok((Some(signal), _)) => {
+                info!(logger, "received {}, shutting down", signal);
+                trigger.request(ShutdownReason::Signal(signal));
+            }
+            Ok((None, _)) => {}
+            Err((error, _)) => {
+                error!(logger, "cannot listen to the shutdown signals: {}", error)
+            }
+        }
+        Ok(())
+    });
+
+    thread::Builder::new()
+        .name("signals".to_owned())
+        .spawn(move || tokio::run(listener))
+        .unwrap_or_else(|err| panic!("Cannot spawn thread: {}", err));
+}
+
+#[cfg(unix)]
+fn shutdown_signals() -> Signals {
+    use tokio_signal::unix::{Signal, SIGTERM};
+
+    let sigterm = Signal::new(SIGTERM).flatten_stream().map(|_| "SIGTERM");
+    Box::new(ctrl_c().select(sigterm))
+}
+
+#[cfg(not(unix))]
+fn shutdown_signals() -> Signals {
+    Box::new(ctrl_c())
+}
+
+fn ctrl_c() -> impl Stream<Item = &'static str, Error = io::Error> {
+    tokio_signal::ctrl_c().flatten_stream().map(|()| "SIGINT")
+}
@@ -4,16 +4,34 @@
 //! in order to more finely organize and control the different
 //! modules utilized in jormungandr.
 //!
+//! The services are supervised: when a service panics or returns while
+//! it was expected to keep running, its [`RestartPolicy`] decides if it
+//! is restarted or if the node has to stop. The status of every service
+//! can be retrieved at any time with [`ServicesStatus`].
+//!
+//! Once a shutdown has been requested with a [`ShutdownTrigger`], the
+//! services are stopped one by one in the order chosen by the node, the
+//! services terminating from then on are not considered as failed.
+//!
 
-use crate::utils::async_msg::{self, MessageBox};
+use crate::utils::async_msg::{self, MessageBox, MessageQueue, QueueDepth};
+use futures::sync::oneshot;
 use slog::Logger;
 use std::{
-    sync::mpsc::{self, Sender},
+    collections::BTreeMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex, PoisonError, RwLock,
+    },
     thread,
     time::{Duration, Instant},
 };
 use tokio::prelude::*;
 use tokio::runtime;
+use tokio::timer::Delay;
 
 // Limit on the length of a task message queue
 const MESSAGE_QUEUE_LEN: usize = 1000;
@@ -21,10 +39,30 @@ const MESSAGE_QUEUE_LEN: usize = 1000;
 // Stack size of a task thread
 const TASK_STACK_SIZE: usize = 2 * 1024 * 1024;
 
+// Delay before restarting a service, multiplied by the number of
+// times the service has been restarted already
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+// Upper bound of the delay before restarting a service
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+// Time given to a service to process the inputs left in its queue when
+// the node shuts down
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+custom_error! {pub Error
+    ServiceFailed { name: &'static str } = "service {name} terminated unexpectedly"
+}
+
 /// hold onto the different services created
 pub struct Services {
     logger: Logger,
     services: Vec<Service>,
+    status: ServicesStatus,
+    drains: BTreeMap<&'static str, oneshot::Sender<()>>,
+    shutting_down: Arc<AtomicBool>,
+    event_sender: Sender<Event>,
+    event_receiver: Receiver<Event>,
 }
 
 /// wrap up a service
@@ -65,7 +103,7 @@ pub struct TokioServiceInfo {
     executor: runtime::TaskExecutor,
 }
 
-pub struct TaskMessageBox<Msg>(Sender<Msg>);
+pub struct TaskMessageBox<Msg>(Sender<Msg>, QueueDepth);
 
 /// Input for the different task with input service
 ///
@@ -79,6 +117,102 @@ pub enum Input<Msg> {
     Input(Msg),
 }
 
+/// What to do when a service panics or returns while it was expected
+/// to keep running
+#[derive(Clone, Copy, Debug)]
+pub enum RestartPolicy {
+    /// stop the node
+    FailFast,
+    /// restart the service, at most `max_restarts` times before
+    /// stopping the node
+    Restart { max_restarts: u32 },
+}
+
+/// the state of a service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceState {
+    /// the service is running
+    Running,
+    /// the service terminated unexpectedly and is about to be restarted
+    Restarting,
+    /// the service terminated unexpectedly and will not be restarted
+    Failed,
+    /// the service has no more inputs to process and stopped
+    Stopped,
+}
+
+/// snapshot of the status of a service
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceStatus {
+    pub state: ServiceState,
+    /// number of times the service has been restarted
+    pub restart_count: u32,
+    /// seconds since the service was last started
+    pub uptime: u64,
+    /// number of inputs waiting to be processed, if the service has
+    /// an input queue
+    pub queue_depth: Option<usize>,
+}
+
+/// status of all the services, shared with whoever needs to report it
+#[derive(Clone, Default)]
+pub struct ServicesStatus(Arc<RwLock<BTreeMap<&'static str, StatusEntry>>>);
+
+struct StatusEntry {
+    state: ServiceState,
+    restart_count: u32,
+    started: Instant,
+    queue: Option<QueueDepth>,
+}
+
+/// why the node is shutting down
+#[derive(Clone, Copy, Debug)]
+pub enum ShutdownReason {
+    /// the node received the given signal
+    Signal(&'static str),
+    /// the shutdown has been requested through the REST API
+    AdminRequest,
+    /// all the services stopped on their own
+    ServicesStopped,
+}
+
+/// Handle to request the shutdown of the node, it can be shared
+/// between threads
+#[derive(Clone)]
+pub struct ShutdownTrigger(Arc<Mutex<Sender<Event>>>);
+
+/// reported to [`Services::wait`]
+enum Event {
+    Stopped(&'static str),
+    Failed(&'static str),
+    ShutdownRequested(ShutdownReason),
+}
+
+/// apply the restart policy of a service and keep its status up to date
+#[derive(Clone)]
+struct Supervisor {
+    name: &'static str,
+    policy: RestartPolicy,
+    status: ServicesStatus,
+    shutting_down: Arc<AtomicBool>,
+    event_sender: Sender<Event>,
+    logger: Logger,
+}
+
+/// reads a message queue shared between the successive runs of a service,
+/// so that the messages are not lost when the service is restarted
+struct SharedQueue<Msg>(Arc<Mutex<DrainableQueue<Msg>>>);
+
+/// a message queue which ends once it is empty after it has been asked
+/// to drain
+struct DrainableQueue<Msg> {
+    queue: MessageQueue<Msg>,
+    drain: oneshot::Receiver<()>,
+    draining: bool,
+}
+
 enum Inner {
     Tokio { runtime: runtime::Runtime },
     Thread { handler: thread::JoinHandle<()> },
@@ -87,79 +221,324 @@ enum Inner {
 impl Services {
     /// create a new set of services
     pub fn new(logger: Logger) -> Self {
+        let (event_sender, event_receiver) = mpsc::channel();
         Services {
             logger: logger,
             services: Vec::new(),
+            status: ServicesStatus::default(),
+            drains: BTreeMap::new(),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            event_sender,
+            event_receiver,
         }
     }
 
+    /// get a handle to request the shutdown of the node, which makes
+    /// [`Services::wait`] return
+    pub fn shutdown_trigger(&self) -> ShutdownTrigger {
+        ShutdownTrigger(Arc::new(Mutex::new(self.event_sender.clone())))
+    }
+
+    /// get the status of the services, kept up to date as the services
+    /// run, fail and get restarted
+    pub fn status(&self) -> ServicesStatus {
+        self.status.clone()
+    }
+
+    /// report the depth of the given queue in the status of the service,
+    /// for services reading their inputs from a queue they were given
+    pub fn watch_queue(&self, name: &'static str, queue: QueueDepth) {
+        self.status.update(name, |entry| entry.queue = Some(queue));
+    }
+
     /// spawn a service in a thread. the service will run as long as the
     /// given function does not return. As soon as the function return
-    /// the service stop
+    /// the service stop and, unless the node is shutting down, the node
+    /// fails.
     ///
     pub fn spawn<F>(&mut self, name: &'static str, f: F)
     where
         F: FnOnce(ThreadServiceInfo) -> (),
         F: Send + 'static,
     {
-        let now = Instant::now();
-        let thread_service_info = ThreadServiceInfo {
-            name: name,
-            up_time: now,
-            logger: self.logger.new(o!(::log::KEY_TASK => name)).into_erased(),
-        };
-
-        let handler = thread::Builder::new()
-            .name(name.to_owned())
-            // .stack_size(2 * 1024 * 1024)
-            .spawn(move || {
-                info!(thread_service_info.logger, "starting task");
-                f(thread_service_info)
-            })
-            .unwrap_or_else(|err| panic!("Cannot spawn thread: {}", err));
-
-        let task = Service::new_handler(name, handler, now);
-        self.services.push(task);
+        let supervisor = self.supervisor(name, RestartPolicy::FailFast, None);
+        let logger = self.service_logger(name);
+
+        self.spawn_thread(name, move || {
+            let thread_service_info = ThreadServiceInfo::new(name, logger);
+            info!(thread_service_info.logger, "starting task");
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(thread_service_info)));
+            supervisor.terminated(result.is_err());
+        });
     }
 
     /// spawn a service that will be launched for every given inputs
     ///
-    /// the service will stop once there is no more input to read: the function
-    /// will be called one last time with `Input::Shutdown` and then will return
+    /// the function processing the inputs is built by `make`. the service
+    /// will stop once there is no more input to read: the function will be
+    /// called one last time with `Input::Shutdown` and then will return.
+    /// If the function panics, the service is restarted with a function
+    /// freshly built by `make`, or the node fails, depending on the given
+    /// policy.
     ///
-    pub fn spawn_with_inputs<F, Msg>(&mut self, name: &'static str, mut f: F) -> TaskMessageBox<Msg>
+    pub fn spawn_with_inputs<M, F, Msg>(
+        &mut self,
+        name: &'static str,
+        policy: RestartPolicy,
+        mut make: M,
+    ) -> TaskMessageBox<Msg>
     where
+        M: FnMut() -> F,
+        M: Send + 'static,
         F: FnMut(&ThreadServiceInfo, Input<Msg>) -> (),
-        F: Send + 'static,
         Msg: Send + 'static,
     {
         let (tx, rx) = mpsc::channel::<Msg>();
-
-        self.spawn(name, move |info| loop {
-            match rx.recv() {
-                Ok(msg) => f(&info, Input::Input(msg)),
-                Err(err) => {
-                    warn!(
-                        info.logger,
-                        "Shutting down service {} (up since {}): {}",
-                        name,
-                        humantime::format_duration(info.up_time()),
-                        err
-                    );
-                    f(&info, Input::Shutdown);
+        let depth = QueueDepth::default();
+        let supervisor = self.supervisor(name, policy, Some(depth.clone()));
+        let logger = self.service_logger(name);
+
+        {
+            let depth = depth.clone();
+            self.spawn_thread(name, move || loop {
+                let info = ThreadServiceInfo::new(name, logger.clone());
+                info!(info.logger, "starting task");
+                let mut f = make();
+                let result = panic::catch_unwind(AssertUnwindSafe(|| loop {
+                    match rx.recv() {
+                        Ok(msg) => {
+                            depth.decrement();
+                            f(&info, Input::Input(msg))
+                        }
+                        Err(err) => {
+                            warn!(
+                                info.logger,
+                                "Shutting down service {} (up since {}): {}",
+                                name,
+                                humantime::format_duration(info.up_time()),
+                                err
+                            );
+                            f(&info, Input::Shutdown);
+                            break;
+                        }
+                    }
+                }));
+                if result.is_ok() {
+                    supervisor.stopped();
                     break;
                 }
-            }
-        });
+                match supervisor.terminated(true) {
+                    Some(delay) => {
+                        thread::sleep(delay);
+                        supervisor.restarted();
+                    }
+                    None => break,
+                }
+            });
+        }
 
-        TaskMessageBox(tx)
+        TaskMessageBox(tx, depth)
     }
 
     /// Spawn the given Future in a new dedicated runtime
     ///
     /// * utilising one thread only;
     /// * 2MiB stack size max
+    ///
+    /// As soon as the future completes or panics the node fails, unless it
+    /// is shutting down.
     pub fn spawn_future<F, T>(&mut self, name: &'static str, f: F)
+    where
+        F: FnOnce(TokioServiceInfo) -> T,
+        T: Future<Item = (), Error = ()> + Send + 'static,
+    {
+        let supervisor = self.supervisor(name, RestartPolicy::FailFast, None);
+
+        self.spawn_runtime(name, move |future_service_info| {
+            AssertUnwindSafe(f(future_service_info))
+                .catch_unwind()
+                .then(move |result| {
+                    supervisor.terminated(result.is_err());
+                    Ok(())
+                })
+        });
+    }
+
+    /// Spawn a tokio service that will await messages and will be executed
+    /// sequentially for every received inputs
+    ///
+    /// * utilising one thread only;
+    /// * 2MiB stack size max
+    ///
+    /// The function processing the inputs is built by `make`. A future it
+    /// returns failing is logged and the next input is processed. If the
+    /// function panics, the service is restarted with a function freshly
+    /// built by `make`, or the node fails, depending on the given policy.
+    /// The inputs which have not been processed yet are kept for the
+    /// restarted service. On shutdown, the service can be asked to process
+    /// the inputs left in its queue with [`Services::drain`].
+    ///
+    pub fn spawn_future_with_inputs<M, F, Msg, T>(
+        &mut self,
+        name: &'static str,
+        policy: RestartPolicy,
+        make: M,
+    ) -> MessageBox<Msg>
+    where
+        M: FnMut() -> F,
+        M: Send + 'static,
+        F: FnMut(&TokioServiceInfo, Input<Msg>) -> T,
+        F: Send + 'static,
+        Msg: Send + 'static,
+        T: IntoFuture<Item = (), Error = ()> + Send + 'static,
+        <T as futures::IntoFuture>::Future: Send,
+    {
+        let (msg_box, msg_queue) = async_msg::channel(MESSAGE_QUEUE_LEN);
+        let supervisor = self.supervisor(name, policy, Some(msg_queue.depth()));
+        let (drain_sender, drain) = oneshot::channel();
+        self.drains.insert(name, drain_sender);
+        let msg_queue = Arc::new(Mutex::new(DrainableQueue {
+            queue: msg_queue,
+            drain,
+            draining: false,
+        }));
+
+        self.spawn_runtime(name, move |future_service_info| {
+            run_with_inputs(future_service_info, msg_queue, make, supervisor)
+        });
+        msg_box
+    }
+
+    /// wait until the node has to shut down. this function will block
+    /// until a shutdown is requested or all services have stopped, or
+    /// until one of the services fails and cannot be restarted.
+    ///
+    /// From the moment this function returns successfully, the services
+    /// terminating are considered as stopped and are not restarted.
+    ///
+    pub fn wait(&self) -> Result<ShutdownReason, Error> {
+        let mut running = self.services.len();
+        let reason = loop {
+            if running == 0 {
+                break ShutdownReason::ServicesStopped;
+            }
+            match self.event_receiver.recv() {
+                Ok(Event::Stopped(_)) => running -= 1,
+                Ok(Event::Failed(name)) => return Err(Error::ServiceFailed { name }),
+                Ok(Event::ShutdownRequested(reason)) => break reason,
+                Err(_) => break ShutdownReason::ServicesStopped,
+            }
+        };
+        self.shutting_down.store(true, Ordering::SeqCst);
+        Ok(reason)
+    }
+
+    /// stop the given service: the tasks of a tokio service are dropped,
+    /// a thread service is waited for so it has to be made to return first
+    ///
+    pub fn stop(&mut self, name: &'static str) {
+        let position = self
+            .services
+            .iter()
+            .position(|service| service.name == name);
+        if let Some(position) = position {
+            let service = self.services.remove(position);
+            self.status
+                .update(name, |entry| entry.state = ServiceState::Stopped);
+            match service.inner {
+                Inner::Thread { handler } => {
+                    if handler.join().is_err() {
+                        warn!(self.logger, "service {} panicked while stopping", name);
+                    }
+                }
+                Inner::Tokio { runtime } => runtime.shutdown_now().wait().unwrap(),
+            }
+        }
+    }
+
+    /// stop the given service once it has processed all the inputs left
+    /// in its queue, or after a timeout
+    ///
+    pub fn drain(&mut self, name: &'static str) {
+        if let Some(drain) = self.drains.remove(name) {
+            let _ = drain.send(());
+            let deadline = Instant::now() + DRAIN_TIMEOUT;
+            loop {
+                let now = Instant::now();
+                let timeout = if deadline > now {
+                    deadline.duration_since(now)
+                } else {
+                    Duration::from_secs(0)
+                };
+                match self.event_receiver.recv_timeout(timeout) {
+                    Ok(Event::Stopped(stopped)) if stopped == name => break,
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => {
+                        warn!(
+                            self.logger,
+                            "service {} did not process its queue within {}",
+                            name,
+                            humantime::format_duration(DRAIN_TIMEOUT)
+                        );
+                        break;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }
+        self.stop(name)
+    }
+
+    /// stop all the services left: the tokio services are stopped and the
+    /// thread services are left to terminate with the node
+    ///
+    pub fn stop_all(self) {
+        for service in self.services {
+            if let Inner::Tokio { runtime } = service.inner {
+                runtime.shutdown_now().wait().unwrap()
+            }
+        }
+    }
+
+    fn service_logger(&self, name: &'static str) -> Logger {
+        self.logger.new(o!(::log::KEY_TASK => name)).into_erased()
+    }
+
+    fn supervisor(
+        &self,
+        name: &'static str,
+        policy: RestartPolicy,
+        queue: Option<QueueDepth>,
+    ) -> Supervisor {
+        self.status.register(name, queue);
+        Supervisor {
+            name,
+            policy,
+            status: self.status.clone(),
+            shutting_down: self.shutting_down.clone(),
+            event_sender: self.event_sender.clone(),
+            logger: self.service_logger(name),
+        }
+    }
+
+    fn spawn_thread<F>(&mut self, name: &'static str, f: F)
+    where
+        F: FnOnce() -> (),
+        F: Send + 'static,
+    {
+        let now = Instant::now();
+
+        let handler = thread::Builder::new()
+            .name(name.to_owned())
+            // .stack_size(2 * 1024 * 1024)
+            .spawn(f)
+            .unwrap_or_else(|err| panic!("Cannot spawn thread: {}", err));
+
+        let task = Service::new_handler(name, handler, now);
+        self.services.push(task);
+    }
+
+    fn spawn_runtime<F, T>(&mut self, name: &'static str, f: F)
     where
         F: FnOnce(TokioServiceInfo) -> T,
         T: Future<Item = (), Error = ()> + Send + 'static,
@@ -179,7 +558,7 @@ impl Services {
         let future_service_info = TokioServiceInfo {
             name: name,
             up_time: now,
-            logger: self.logger.new(o!(::log::KEY_TASK => name)).into_erased(),
+            logger: self.service_logger(name),
             executor: executor,
         };
 
@@ -188,49 +567,219 @@ impl Services {
         let task = Service::new_runtime(name, runtime, now);
         self.services.push(task);
     }
+}
 
-    /// Spawn a tokio service that will await messages and will be executed
-    /// sequentially for every received inputs
-    ///
-    /// * utilising one thread only;
-    /// * 2MiB stack size max
-    ///
-    pub fn spawn_future_with_inputs<F, Msg, T>(
-        &mut self,
-        name: &'static str,
-        mut f: F,
-    ) -> MessageBox<Msg>
-    where
-        F: FnMut(&TokioServiceInfo, Input<Msg>) -> T,
-        F: Send + 'static,
-        Msg: Send + 'static,
-        T: IntoFuture<Item = (), Error = ()> + Send + 'static,
-        <T as futures::IntoFuture>::Future: Send,
-    {
-        let (msg_box, msg_queue) = async_msg::channel(MESSAGE_QUEUE_LEN);
-        self.spawn_future(name, move |future_service_info| {
-            msg_queue
-                .map(Input::Input)
-                .chain(stream::once(Ok(Input::Shutdown)))
-                .for_each(move |input| f(&future_service_info, input))
-        });
-        msg_box
+/// process the inputs of the queue until it is closed, restarting the
+/// service on the same executor if it fails and its policy allows it.
+/// Every run processes the inputs with a function freshly built by `make`.
+fn run_with_inputs<M, F, Msg, T>(
+    info: TokioServiceInfo,
+    queue: Arc<Mutex<DrainableQueue<Msg>>>,
+    mut make: M,
+    supervisor: Supervisor,
+) -> Box<dyn Future<Item = (), Error = ()> + Send>
+where
+    M: FnMut() -> F,
+    M: Send + 'static,
+    F: FnMut(&TokioServiceInfo, Input<Msg>) -> T,
+    F: Send + 'static,
+    Msg: Send + 'static,
+    T: IntoFuture<Item = (), Error = ()> + Send + 'static,
+    <T as futures::IntoFuture>::Future: Send,
+{
+    let name = info.name;
+    let logger = info.logger.clone();
+    let executor = info.executor.clone();
+
+    let inputs = {
+        let mut f = make();
+        let logger = logger.clone();
+        SharedQueue(queue.clone())
+            .map(Input::Input)
+            .chain(stream::once(Ok(Input::Shutdown)))
+            .for_each(move |input| {
+                let logger = logger.clone();
+                // a failed input does not make the service fail, only a
+                // panic or the queue failing does
+                f(&info, input).into_future().or_else(move |()| {
+                    warn!(logger, "failed to process an input");
+                    Ok(())
+                })
+            })
+    };
+
+    let supervised = AssertUnwindSafe(inputs).catch_unwind().then(move |result| {
+        let panicked = match result {
+            Ok(Ok(())) => {
+                supervisor.stopped();
+                return Ok(());
+            }
+            Ok(Err(())) => false,
+            Err(_) => true,
+        };
+        if let Some(delay) = supervisor.terminated(panicked) {
+            let restart_executor = executor.clone();
+            let restart = Delay::new(Instant::now() + delay).then(move |_| {
+                supervisor.restarted();
+                let info = TokioServiceInfo {
+                    name,
+                    up_time: Instant::now(),
+                    logger,
+                    executor: restart_executor,
+                };
+                run_with_inputs(info, queue, make, supervisor)
+            });
+            executor.spawn(restart);
+        }
+        Ok(())
+    });
+    Box::new(supervised)
+}
+
+impl<Msg> Stream for SharedQueue<Msg> {
+    type Item = Msg;
+    type Error = ();
+    fn poll(&mut self) -> Poll<Option<Msg>, ()> {
+        let mut queue = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Async::Ready(msg) = queue.queue.poll()? {
+            return Ok(Async::Ready(msg));
+        }
+        if !queue.draining {
+            match queue.drain.poll() {
+                Ok(Async::Ready(())) => queue.draining = true,
+                // the drain handle is dropped along with the services
+                Ok(Async::NotReady) | Err(oneshot::Canceled) => return Ok(Async::NotReady),
+            }
+        }
+        Ok(Async::Ready(None))
+    }
+}
+
+impl ShutdownTrigger {
+    /// request the node to shut down
+    pub fn request(&self, reason: ShutdownReason) {
+        let sender = self.0.lock().unwrap();
+        let _ = sender.send(Event::ShutdownRequested(reason));
+    }
+}
+
+impl fmt::Display for ShutdownReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShutdownReason::Signal(signal) => write!(f, "received {}", signal),
+            ShutdownReason::AdminRequest => write!(f, "requested through the REST API"),
+            ShutdownReason::ServicesStopped => write!(f, "all the services stopped"),
+        }
+    }
+}
+
+impl Supervisor {
+    /// the service has no more inputs to process and stopped
+    fn stopped(&self) {
+        info!(self.logger, "service stopped");
+        self.status
+            .update(self.name, |entry| entry.state = ServiceState::Stopped);
+        let _ = self.event_sender.send(Event::Stopped(self.name));
     }
 
-    /// join on all the started services. this function will block
-    /// until all services return
+    /// the service terminated while it was expected to keep running
     ///
-    pub fn wait_all(self) {
-        for service in self.services {
-            match service.inner {
-                Inner::Thread { handler } => handler.join().unwrap(),
-                Inner::Tokio { runtime } => runtime.shutdown_on_idle().wait().unwrap(),
+    /// returns the delay to wait before restarting the service, or `None`
+    /// if the service is not to be restarted, in which case the node is
+    /// notified of the failure.
+    fn terminated(&self, panicked: bool) -> Option<Duration> {
+        if self.shutting_down.load(Ordering::SeqCst) {
+            self.stopped();
+            return None;
+        }
+        let cause = if panicked { "panicked" } else { "returned" };
+        let mut restart_count = 0;
+        self.status
+            .update(self.name, |entry| restart_count = entry.restart_count);
+        match self.policy {
+            RestartPolicy::Restart { max_restarts } if restart_count < max_restarts => {
+                let delay = std::cmp::min(RESTART_DELAY * (restart_count + 1), MAX_RESTART_DELAY);
+                warn!(
+                    self.logger,
+                    "service {}, restarting it in {}",
+                    cause,
+                    humantime::format_duration(delay);
+                    "restart_count" => restart_count + 1,
+                );
+                self.status.update(self.name, |entry| {
+                    entry.state = ServiceState::Restarting;
+                    entry.restart_count += 1;
+                });
+                Some(delay)
             }
+            _ => {
+                crit!(self.logger, "service {}, not restarting it", cause);
+                self.status
+                    .update(self.name, |entry| entry.state = ServiceState::Failed);
+                let _ = self.event_sender.send(Event::Failed(self.name));
+                None
+            }
+        }
+    }
+
+    /// the service is running again after having been restarted
+    fn restarted(&self) {
+        self.status.update(self.name, |entry| {
+            entry.state = ServiceState::Running;
+            entry.started = Instant::now();
+        });
+    }
+}
+
+impl ServicesStatus {
+    fn register(&self, name: &'static str, queue: Option<QueueDepth>) {
+        let entry = StatusEntry {
+            state: ServiceState::Running,
+            restart_count: 0,
+            started: Instant::now(),
+            queue,
+        };
+        self.0.write().unwrap().insert(name, entry);
+    }
+
+    fn update<F>(&self, name: &'static str, f: F)
+    where
+        F: FnOnce(&mut StatusEntry),
+    {
+        if let Some(entry) = self.0.write().unwrap().get_mut(name) {
+            f(entry)
         }
     }
+
+    /// get the current status of every service, by service name
+    pub fn get(&self) -> BTreeMap<&'static str, ServiceStatus> {
+        let now = Instant::now();
+        self.0
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, entry)| {
+                let status = ServiceStatus {
+                    state: entry.state,
+                    restart_count: entry.restart_count,
+                    uptime: now.duration_since(entry.started).as_secs(),
+                    queue_depth: entry.queue.as_ref().map(QueueDepth::get),
+                };
+                (*name, status)
+            })
+            .collect()
+    }
 }
 
 impl ThreadServiceInfo {
+    fn new(name: &'static str, logger: Logger) -> Self {
+        ThreadServiceInfo {
+            name,
+            up_time: Instant::now(),
+            logger,
+        }
+    }
+
     /// get the time this service has been running since
     #[inline]
     pub fn up_time(&self) -> Duration {
@@ -318,12 +867,13 @@ impl Service {
 
 impl<Msg> Clone for TaskMessageBox<Msg> {
     fn clone(&self) -> Self {
-        TaskMessageBox(self.0.clone())
+        TaskMessageBox(self.0.clone(), self.1.clone())
     }
 }
 
 impl<Msg> TaskMessageBox<Msg> {
     pub fn send_to(&self, a: Msg) {
+        self.1.increment();
         self.0.send(a).unwrap()
     }
 }